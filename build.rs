@@ -0,0 +1,76 @@
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Dependencies whose versions are shown in `--version`
+const REPORTED_DEPENDENCIES: [&str; 2] = ["ratatui", "rusqlite"];
+
+fn main() {
+    // Build metadata for `--version` and the `version` subcommand
+    println!("cargo:rustc-env=BUILD_GIT_HASH={}", git_hash().unwrap_or_else(|| "unknown".to_string()));
+    println!("cargo:rustc-env=BUILD_TIMESTAMP={}", build_timestamp());
+    println!("cargo:rustc-env=BUILD_TARGET={}", std::env::var("TARGET").unwrap_or_else(|_| "unknown".to_string()));
+
+    let lock = std::fs::read_to_string("Cargo.lock").unwrap_or_default();
+    for name in REPORTED_DEPENDENCIES {
+        let version = locked_version(&lock, name).unwrap_or_else(|| "unknown".to_string());
+        println!("cargo:rustc-env=BUILD_DEP_{}={}", name.to_uppercase(), version);
+    }
+
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=Cargo.lock");
+    // Missing paths would rerun the script on every build, e.g. when built from a source archive
+    for git_path in [".git/HEAD", ".git/refs"] {
+        if std::path::Path::new(git_path).exists() {
+            println!("cargo:rerun-if-changed={}", git_path);
+        }
+    }
+}
+
+/// Get the short hash of the checked out commit, if built from a Git checkout
+fn git_hash() -> Option<String> {
+    let output = Command::new("git").args(["rev-parse", "--short", "HEAD"]).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let hash = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    (!hash.is_empty()).then_some(hash)
+}
+
+/// Format the build time as `YYYY-MM-DD HH:MM:SS UTC`, honoring SOURCE_DATE_EPOCH for reproducible builds
+fn build_timestamp() -> String {
+    let secs = std::env::var("SOURCE_DATE_EPOCH").ok()
+        .and_then(|epoch| epoch.parse::<u64>().ok())
+        .unwrap_or_else(|| SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0));
+
+    // Convert days since the epoch to a civil date (Howard Hinnant's algorithm)
+    let days = (secs / 86400) as i64 + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days.rem_euclid(146097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    let time = secs % 86400;
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+        year, month, day, time / 3600, time % 3600 / 60, time % 60
+    )
+}
+
+/// Find the version of a package in Cargo.lock
+fn locked_version(lock: &str, name: &str) -> Option<String> {
+    let mut lines = lock.lines();
+    while let Some(line) = lines.next() {
+        if line.trim() == format!("name = \"{}\"", name) {
+            return lines.next()?
+                .trim()
+                .strip_prefix("version = \"")?
+                .strip_suffix('"')
+                .map(|version| version.to_string());
+        }
+    }
+    None
+}
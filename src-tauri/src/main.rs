@@ -1,19 +1,94 @@
 // Prevents additional console window on Windows in release
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-use std::process::Command;
+use notify::Watcher;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tauri::menu::{Menu, MenuItem, PredefinedMenuItem};
+use tauri::tray::TrayIconBuilder;
+use tauri::{AppHandle, Emitter, Manager, Runtime, State};
+use vscode_workspaces_editor::cli;
+use vscode_workspaces_editor::config::Config;
 use vscode_workspaces_editor::workspaces;
-use vscode_workspaces_editor::workspaces::Workspace;
+use vscode_workspaces_editor::workspaces::{SortOrder, Workspace, WorkspaceError, WorkspaceQuery, WorkspaceStats};
 use vscode_workspaces_editor::workspaces::get_known_vscode_paths as get_known_vscode_paths_impl;
 
+/// Structured error returned to the frontend, e.g. `{"code": "ALREADY_EXISTS", "message": "..."}`
+#[derive(Debug, Serialize)]
+struct CommandError {
+    code: String,
+    message: String,
+}
+
+impl From<anyhow::Error> for CommandError {
+    fn from(error: anyhow::Error) -> Self {
+        let code = error.downcast_ref::<WorkspaceError>()
+            .map(|e| e.code())
+            .unwrap_or("UNKNOWN");
+        
+        Self {
+            code: code.to_string(),
+            message: format!("{:#}", error),
+        }
+    }
+}
+
 #[tauri::command]
 async fn get_workspaces(profile_path: String) -> Result<Vec<Workspace>, String> {
-    workspaces::get_workspaces(&profile_path).map_err(|e| e.to_string())
+    workspaces::get_workspaces(&profile_path, None).map_err(|e| e.to_string())
+}
+
+/// Filter criteria sent by the frontend; unset fields are ignored
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct WorkspaceQueryParams {
+    text: Option<String>,
+    remote: Option<bool>,
+    workspace_type: Option<String>,
+    tag: Option<String>,
+    exists: Option<bool>,
+    host: Option<String>,
+    sort_by: Option<SortOrder>,
+    sort_desc: Option<bool>,
+    limit: Option<usize>,
+}
+
+impl From<WorkspaceQueryParams> for WorkspaceQuery {
+    fn from(params: WorkspaceQueryParams) -> Self {
+        // Without an explicit direction use the field's natural one (most recent first for last used)
+        let sort_by = params.sort_by.unwrap_or_default();
+        let sort_desc = params.sort_desc.unwrap_or_else(|| sort_by.default_descending());
+        
+        Self {
+            text: params.text,
+            remote: params.remote,
+            workspace_type: params.workspace_type,
+            tag: params.tag,
+            exists: params.exists,
+            host: params.host,
+            sort_by,
+            sort_desc,
+            limit: params.limit,
+        }
+    }
+}
+
+#[tauri::command]
+async fn search_workspaces(profile_path: String, query: WorkspaceQueryParams) -> Result<Vec<Workspace>, String> {
+    workspaces::query_workspaces(&profile_path, &query.into()).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn get_workspace_statistics(profile_path: String) -> Result<WorkspaceStats, String> {
+    let mut workspaces = workspaces::get_workspaces(&profile_path, None).map_err(|e| e.to_string())?;
+    Ok(workspaces::workspace_statistics(&mut workspaces))
 }
 
 #[tauri::command]
-async fn add_workspace(_profile_path: String, _workspace_path: String) -> Result<bool, String> {
-    Ok(true) // TODO: Implement add_workspace functionality
+async fn add_workspace<R: Runtime>(app: AppHandle<R>, profile_path: String, workspace_path: String) -> Result<bool, CommandError> {
+    workspaces::add_workspace(&profile_path, &workspace_path)?;
+    refresh_tray_menu(&app, Some(profile_path));
+    Ok(true)
 }
 
 #[tauri::command]
@@ -22,41 +97,178 @@ async fn edit_workspace(_profile_path: String, _workspace_id: String, _new_name:
 }
 
 #[tauri::command]
-async fn delete_workspace(profile_path: String, workspace_id: String) -> Result<bool, String> {
+async fn delete_workspace<R: Runtime>(app: AppHandle<R>, profile_path: String, workspace_id: String, dry_run: Option<bool>) -> Result<bool, String> {
     // Find the workspace with the given ID
-    let workspaces = workspaces::get_workspaces(&profile_path).map_err(|e| e.to_string())?;
+    let workspace = workspaces::get_workspace_by_id(&profile_path, &workspace_id)
+        .map_err(|e| e.to_string())?;
     
-    let workspace = workspaces.iter()
-        .find(|w| w.id == workspace_id)
-        .cloned();
+    let deleted = match workspace {
+        Some(ws) => workspaces::delete_workspace(&profile_path, &[ws], dry_run.unwrap_or(false))
+            .map_err(|e| e.to_string())?,
+        None => return Err(format!("Workspace with ID {} not found", workspace_id))
+    };
     
-    match workspace {
-        Some(ws) => workspaces::delete_workspace(&profile_path, &[ws]).map_err(|e| e.to_string()),
-        None => Err(format!("Workspace with ID {} not found", workspace_id))
-    }
+    refresh_tray_menu(&app, Some(profile_path));
+    Ok(deleted)
 }
 
 #[tauri::command]
-async fn open_workspace(workspace_path: String, original_path: Option<String>) -> Result<bool, String> {
+async fn open_workspace(workspace_path: String, original_path: Option<String>, editor_command: Option<String>) -> Result<bool, String> {
     // Use original_path if provided, otherwise fall back to workspace_path
     let path_to_open = original_path.unwrap_or(workspace_path);
     
-    // Actually implement opening VSCode with the workspace
-    #[cfg(target_os = "windows")]
-    let code_command = "code";
+    // Use the requested editor, falling back to the configured one
+    let editor_command = match editor_command {
+        Some(command) => command,
+        None => Config::load().unwrap_or_default().editor_command,
+    };
+    
+    cli::spawn_editor(&editor_command, &path_to_open).map_err(|e| e.to_string())?;
+    
+    if let Err(e) = workspaces::record_workspace_open(&path_to_open, workspaces::ActivitySource::Tauri) {
+        eprintln!("Failed to record workspace open: {}", e);
+    }
+    Ok(true)
+}
+
+/// The active filesystem watcher, if any
+#[derive(Default)]
+struct WatcherState(Mutex<Option<Box<dyn Watcher + Send>>>);
+
+#[tauri::command]
+async fn open_workspace_with_editor(workspace_path: String, editor_command: String) -> Result<bool, CommandError> {
+    if !workspaces::is_command_available(&editor_command) {
+        return Err(CommandError {
+            code: "EDITOR_NOT_FOUND".to_string(),
+            message: format!("Editor command '{}' was not found in PATH", editor_command),
+        });
+    }
+    
+    cli::spawn_editor(&editor_command, &workspace_path)?;
+    
+    if let Err(e) = workspaces::record_workspace_open(&workspace_path, workspaces::ActivitySource::Tauri) {
+        eprintln!("Failed to record workspace open: {}", e);
+    }
+    Ok(true)
+}
+
+#[tauri::command]
+async fn watch_workspaces<R: Runtime>(
+    profile_path: String,
+    window: tauri::Window<R>,
+    state: State<'_, WatcherState>,
+) -> Result<bool, String> {
+    let watcher = workspaces::watch_workspaces(&profile_path, move || {
+        if let Err(e) = window.emit("workspaces-changed", ()) {
+            eprintln!("Failed to emit workspaces-changed event: {}", e);
+        }
+    }).map_err(|e| format!("{:#}", e))?;
+    
+    // Replacing the previous watcher stops it
+    *state.0.lock().map_err(|e| e.to_string())? = Some(Box::new(watcher));
+    Ok(true)
+}
+
+#[tauri::command]
+async fn stop_watching(state: State<'_, WatcherState>) -> Result<bool, String> {
+    // Dropping the watcher stops it; returns whether one was running
+    let watcher = state.0.lock().map_err(|e| e.to_string())?.take();
+    Ok(watcher.is_some())
+}
+
+/// ID of the system tray icon
+const TRAY_ID: &str = "main";
+
+/// Number of recently used workspaces listed in the tray menu
+const TRAY_RECENT_COUNT: usize = 5;
+
+/// Profile whose workspaces are listed in the tray menu (the last one operated on)
+#[derive(Default)]
+struct TrayState(Mutex<Option<String>>);
+
+/// Build the tray menu: the most recently used workspaces of the profile, then Reload and Quit.
+/// Workspace items have the ID `open:<original URI>`.
+fn build_tray_menu<R: Runtime, M: Manager<R>>(manager: &M, profile_path: Option<&str>) -> tauri::Result<Menu<R>> {
+    let mut recent = match profile_path {
+        Some(profile_path) => workspaces::get_workspaces(profile_path, None).unwrap_or_else(|e| {
+            eprintln!("Failed to load workspaces for the tray menu: {}", e);
+            Vec::new()
+        }),
+        None => Vec::new(),
+    };
+    recent.sort_by(|a, b| b.last_used.cmp(&a.last_used));
+    
+    let menu = Menu::new(manager)?;
+    for workspace in recent.iter_mut().take(TRAY_RECENT_COUNT) {
+        let id = format!("open:{}", workspace.get_original_uri());
+        let item = MenuItem::with_id(manager, id, workspace.get_label(), true, None::<&str>)?;
+        menu.append(&item)?;
+    }
+    if recent.is_empty() {
+        menu.append(&MenuItem::with_id(manager, "empty", "No recent workspaces", false, None::<&str>)?)?;
+    }
     
-    #[cfg(target_os = "macos")]
-    let code_command = "code";
+    menu.append(&PredefinedMenuItem::separator(manager)?)?;
+    menu.append(&MenuItem::with_id(manager, "reload", "Reload", true, None::<&str>)?)?;
+    menu.append(&MenuItem::with_id(manager, "quit", "Quit", true, None::<&str>)?)?;
+    Ok(menu)
+}
+
+/// Rebuild the tray menu, switching it to `profile_path` if given.
+/// Does nothing when there is no tray icon (e.g. in tests).
+fn refresh_tray_menu<R: Runtime>(app: &AppHandle<R>, profile_path: Option<String>) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
     
-    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
-    let code_command = "code";
+    let profile_path = {
+        let state = app.state::<TrayState>();
+        let mut current = state.0.lock().unwrap_or_else(|e| e.into_inner());
+        if profile_path.is_some() {
+            *current = profile_path;
+        }
+        current.clone()
+    };
     
-    match Command::new(code_command)
-        .arg(path_to_open)
-        .spawn() {
-            Ok(_) => Ok(true),
-            Err(e) => Err(e.to_string()),
+    match build_tray_menu(app, profile_path.as_deref()) {
+        Ok(menu) => {
+            if let Err(e) = tray.set_menu(Some(menu)) {
+                eprintln!("Failed to update the tray menu: {}", e);
+            }
         }
+        Err(e) => eprintln!("Failed to build the tray menu: {}", e),
+    }
+}
+
+/// Create the tray icon listing the recent workspaces of the default profile
+fn create_tray<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<()> {
+    let profile_path = workspaces::get_default_profile_path().ok();
+    *app.state::<TrayState>().0.lock().unwrap_or_else(|e| e.into_inner()) = profile_path.clone();
+    
+    let menu = build_tray_menu(app, profile_path.as_deref())?;
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .tooltip("VSCode Workspaces Editor")
+        .menu(&menu)
+        .on_menu_event(|app, event| match event.id().as_ref() {
+            "reload" => refresh_tray_menu(app, None),
+            "quit" => app.exit(0),
+            id => {
+                if let Some(path) = id.strip_prefix("open:") {
+                    let path = path.to_string();
+                    tauri::async_runtime::spawn(async move {
+                        if let Err(e) = open_workspace(path.clone(), Some(path), None).await {
+                            eprintln!("Failed to open workspace: {}", e);
+                        }
+                    });
+                }
+            }
+        });
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+    builder.build(app)?;
+    
+    Ok(())
 }
 
 #[tauri::command]
@@ -74,18 +286,142 @@ fn get_known_vscode_paths() -> Result<Vec<String>, String> {
     Ok(get_known_vscode_paths_impl())
 }
 
-fn main() {
-    tauri::Builder::default()
+/// Register managed state and all commands on the builder
+fn register_commands<R: Runtime>(builder: tauri::Builder<R>) -> tauri::Builder<R> {
+    builder
+        .manage(WatcherState::default())
+        .manage(TrayState::default())
         .invoke_handler(tauri::generate_handler![
             get_workspaces,
+            search_workspaces,
+            get_workspace_statistics,
             add_workspace,
             edit_workspace,
             delete_workspace,
             open_workspace,
+            open_workspace_with_editor,
+            watch_workspaces,
+            stop_watching,
             get_default_profile_path,
             workspace_exists,
             get_known_vscode_paths
         ])
+}
+
+fn main() {
+    register_commands(tauri::Builder::default())
+        .setup(|app| {
+            create_tray(app.handle())?;
+            Ok(())
+        })
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use tauri::ipc::{CallbackFn, InvokeBody};
+    use tauri::test::{get_ipc_response, mock_builder, mock_context, noop_assets, INVOKE_KEY};
+    use tauri::webview::InvokeRequest;
+
+    /// Create a temporary profile with one storage entry per folder
+    fn create_profile(name: &str, folders: &[String]) -> PathBuf {
+        let profile = std::env::temp_dir()
+            .join(format!("vscode-workspaces-editor-test-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&profile);
+        
+        for (i, folder) in folders.iter().enumerate() {
+            let dir = profile.join("User/workspaceStorage").join(format!("ws{}", i));
+            fs::create_dir_all(&dir).unwrap();
+            let content = serde_json::json!({ "folder": format!("file://{}", folder) });
+            fs::write(dir.join("workspace.json"), content.to_string()).unwrap();
+        }
+        
+        profile
+    }
+
+    /// Invoke the search_workspaces command through the mock runtime
+    fn invoke_search(profile: &Path, query: serde_json::Value) -> Vec<Workspace> {
+        let app = register_commands(mock_builder())
+            .build(mock_context(noop_assets()))
+            .expect("failed to build app");
+        let webview = tauri::WebviewWindowBuilder::new(&app, "main", Default::default())
+            .build()
+            .unwrap();
+        
+        let response = get_ipc_response(
+            &webview,
+            InvokeRequest {
+                cmd: "search_workspaces".into(),
+                callback: CallbackFn(0),
+                error: CallbackFn(1),
+                url: if cfg!(windows) { "http://tauri.localhost" } else { "tauri://localhost" }
+                    .parse()
+                    .unwrap(),
+                body: InvokeBody::Json(serde_json::json!({
+                    "profilePath": profile.to_string_lossy(),
+                    "query": query,
+                })),
+                headers: Default::default(),
+                invoke_key: INVOKE_KEY.to_string(),
+            },
+        )
+        .expect("search_workspaces failed");
+        
+        response.deserialize().unwrap()
+    }
+
+    #[test]
+    fn test_search_workspaces_by_text() {
+        let profile = create_profile("text", &[
+            "/projects/alpha-service".to_string(),
+            "/projects/beta-service".to_string(),
+        ]);
+        
+        let results = invoke_search(&profile, serde_json::json!({ "text": "ALPHA" }));
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].path, "/projects/alpha-service");
+        
+        let results = invoke_search(&profile, serde_json::json!({}));
+        assert_eq!(results.len(), 2);
+        
+        let results = invoke_search(&profile, serde_json::json!({ "sortBy": "name", "limit": 1 }));
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].path, "/projects/alpha-service");
+        
+        let results = invoke_search(&profile, serde_json::json!({ "sortBy": "name", "sortDesc": true }));
+        assert_eq!(results[0].path, "/projects/beta-service");
+        
+        fs::remove_dir_all(&profile).unwrap();
+    }
+
+    #[test]
+    fn test_search_workspaces_by_existence_and_type() {
+        let existing = std::env::temp_dir()
+            .join(format!("vscode-workspaces-editor-test-existing-{}", std::process::id()));
+        fs::create_dir_all(&existing).unwrap();
+        let existing_path = existing.to_string_lossy().replace('\\', "/");
+        
+        let profile = create_profile("exists", &[
+            existing_path.clone(),
+            "/definitely/missing/project".to_string(),
+        ]);
+        
+        let results = invoke_search(&profile, serde_json::json!({ "exists": true }));
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].path, existing_path);
+        
+        let results = invoke_search(&profile, serde_json::json!({ "exists": false, "workspaceType": "folder" }));
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].path, "/definitely/missing/project");
+        
+        let results = invoke_search(&profile, serde_json::json!({ "remote": true }));
+        assert!(results.is_empty());
+        
+        fs::remove_dir_all(&profile).unwrap();
+        fs::remove_dir_all(&existing).unwrap();
+    }
 } 
\ No newline at end of file
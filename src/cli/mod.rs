@@ -1,26 +1,95 @@
-use crate::workspaces::Workspace;
-use crate::workspaces::WorkspaceSource;
-use anyhow::Result;
+use crate::workspaces::{self, Workspace, WorkspaceComparison, WorkspaceSource, WorkspaceStats};
+use crate::workspaces::parser::WorkspaceType;
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::fs;
 use std::io::{self, Write};
-use std::process::Command;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Whether status messages are suppressed (`--quiet`)
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Suppress status messages on stdout, e.g. for scripts capturing JSON output.
+/// Errors and the requested output itself are still printed.
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+/// Check whether status messages are suppressed
+pub fn is_quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+/// Whether local workspaces opened with `open_workspace` are added to zoxide
+static UPDATE_ZOXIDE: AtomicBool = AtomicBool::new(false);
+
+/// Add local workspaces to the zoxide database when they are opened (`--update-zoxide`)
+pub fn set_update_zoxide(update: bool) {
+    UPDATE_ZOXIDE.store(update, Ordering::Relaxed);
+}
+
+/// Print a status message to stdout unless `--quiet` was given
+macro_rules! status {
+    ($($arg:tt)*) => {
+        if !$crate::cli::is_quiet() {
+            println!($($arg)*);
+        }
+    };
+}
 
 /// List workspaces in the specified format
 pub fn list_workspaces(workspaces: &[Workspace], format: &str) -> Result<()> {
     match format.to_lowercase().as_str() {
         "json" => output_json(workspaces)?,
+        "ndjson" => output_ndjson(workspaces)?,
+        "md" | "markdown" => output_markdown(workspaces)?,
+        "fzf" => output_fzf(workspaces)?,
+        "dmenu" => output_dmenu(workspaces)?,
         _ => output_text(workspaces)?,
     }
     
     Ok(())
 }
 
+/// Format a last used timestamp (in milliseconds) as a human readable string
+fn format_last_used(last_used: i64) -> String {
+    if last_used <= 0 {
+        return "Unknown".to_string();
+    }
+    
+    chrono::DateTime::from_timestamp(last_used / 1000, 0)
+        .map(|dt| {
+            let now = chrono::Utc::now();
+            let duration = now.signed_duration_since(dt);
+            
+            if duration.num_days() > 365 {
+                dt.format("%Y-%m-%d %H:%M:%S").to_string()
+            } else if duration.num_days() > 30 {
+                format!("{} months ago", duration.num_days() / 30)
+            } else if duration.num_days() > 0 {
+                format!("{} days ago", duration.num_days())
+            } else if duration.num_hours() > 0 {
+                format!("{} hours ago", duration.num_hours())
+            } else if duration.num_minutes() > 0 {
+                format!("{} minutes ago", duration.num_minutes())
+            } else {
+                "just now".to_string()
+            }
+        })
+        .unwrap_or_else(|| "Unknown".to_string())
+}
+
 /// Output workspaces as formatted text
 fn output_text(workspaces: &[Workspace]) -> Result<()> {
     let stdout = io::stdout();
     let mut handle = stdout.lock();
     
     if workspaces.is_empty() {
-        writeln!(handle, "No workspaces found.")?;
+        if !is_quiet() {
+            writeln!(handle, "No workspaces found.")?;
+        }
         return Ok(());
     }
     
@@ -32,12 +101,7 @@ fn output_text(workspaces: &[Workspace]) -> Result<()> {
         writeln!(handle, "     Name: {}", workspace.name.as_deref().unwrap_or("N/A"))?;
         
         // Display parsed path if available, otherwise original path
-        let display_path = if let Some(parsed_info) = &workspace.parsed_info {
-            parsed_info.path.clone()
-        } else {
-            workspace.path.clone()
-        };
-        writeln!(handle, "     Path: {}", display_path)?;
+        writeln!(handle, "     Path: {}", workspace.get_display_path())?;
         
         // Display parsed data
         if let Some(parsed_info) = &workspace.parsed_info {
@@ -63,38 +127,25 @@ fn output_text(workspaces: &[Workspace]) -> Result<()> {
             if let Some(remote_port) = &parsed_info.remote_port {
                 writeln!(handle, "     Remote Port: {}", remote_port)?;
             }
+            
+            if let Some(resolved_host) = &parsed_info.resolved_host {
+                writeln!(handle, "     Resolved Host: {}", resolved_host)?;
+            }
+            
+            if let Some(resolved_user) = &parsed_info.resolved_user {
+                writeln!(handle, "     Resolved User: {}", resolved_user)?;
+            }
+            
+            if let Some(resolved_port) = &parsed_info.resolved_port {
+                writeln!(handle, "     Resolved Port: {}", resolved_port)?;
+            }
 
             if !parsed_info.tags.is_empty() {
                 writeln!(handle, "     Tags: {}", parsed_info.tags.join(", "))?;
             }
         }
         
-        if workspace.last_used > 0 {
-            let last_used = chrono::DateTime::from_timestamp(workspace.last_used / 1000, 0)
-                .map(|dt| {
-                    let now = chrono::Utc::now();
-                    let duration = now.signed_duration_since(dt);
-                    
-                    if duration.num_days() > 365 {
-                        dt.format("%Y-%m-%d %H:%M:%S").to_string()
-                    } else if duration.num_days() > 30 {
-                        format!("{} months ago", duration.num_days() / 30)
-                    } else if duration.num_days() > 0 {
-                        format!("{} days ago", duration.num_days())
-                    } else if duration.num_hours() > 0 {
-                        format!("{} hours ago", duration.num_hours())
-                    } else if duration.num_minutes() > 0 {
-                        format!("{} minutes ago", duration.num_minutes())
-                    } else {
-                        "just now".to_string()
-                    }
-                })
-                .unwrap_or_else(|| "Unknown".to_string());
-            
-            writeln!(handle, "     Last Used: {}", last_used)?;
-        } else {
-            writeln!(handle, "     Last Used: Unknown")?;
-        }
+        writeln!(handle, "     Last Used: {}", format_last_used(workspace.last_used))?;
         
         // Display each source with its details
         writeln!(handle, "     Sources:")?;
@@ -109,6 +160,8 @@ fn output_text(workspaces: &[Workspace]) -> Result<()> {
                         writeln!(handle, "       Database: {}", key)?,
                     WorkspaceSource::Zed(channel) =>
                         writeln!(handle, "       Zed({})", channel)?,
+                    WorkspaceSource::Profile(name) =>
+                        writeln!(handle, "       Profile: {}", name)?,
                 }
             }
         }
@@ -119,115 +172,1097 @@ fn output_text(workspaces: &[Workspace]) -> Result<()> {
     Ok(())
 }
 
+/// Build the detailed JSON representation of a workspace, with the original path explicitly included
+fn workspace_to_json(workspace: &Workspace) -> serde_json::Value {
+    let mut json_workspace = serde_json::json!({
+        "id": workspace.id,
+        "name": workspace.name,
+        "path": workspace.get_display_path(),
+        "last_used": workspace.last_used,
+        "last_used_human": format_last_used(workspace.last_used),
+        "sources": workspace.sources,
+        "exists": workspaces::workspace_exists(workspace),
+        "open_count": workspace.open_count,
+    });
+    
+    // Add parsed_info with original_path explicitly
+    if let Some(parsed_info) = &workspace.parsed_info {
+        json_workspace["original_path"] = serde_json::Value::String(parsed_info.original_path.clone());
+        json_workspace["workspace_type"] = serde_json::Value::String(format!("{:?}", parsed_info.workspace_type));
+        
+        if let Some(remote_authority) = &parsed_info.remote_authority {
+            json_workspace["remote_authority"] = serde_json::Value::String(remote_authority.clone());
+        }
+        
+        if let Some(remote_host) = &parsed_info.remote_host {
+            json_workspace["remote_host"] = serde_json::Value::String(remote_host.clone());
+        }
+        
+        if let Some(remote_user) = &parsed_info.remote_user {
+            json_workspace["remote_user"] = serde_json::Value::String(remote_user.clone());
+        }
+        
+        if let Some(remote_port) = &parsed_info.remote_port {
+            json_workspace["remote_port"] = serde_json::Value::Number((*remote_port).into());
+        }
+        
+        if let Some(resolved_host) = &parsed_info.resolved_host {
+            json_workspace["resolved_host"] = serde_json::Value::String(resolved_host.clone());
+        }
+        
+        if let Some(resolved_user) = &parsed_info.resolved_user {
+            json_workspace["resolved_user"] = serde_json::Value::String(resolved_user.clone());
+        }
+        
+        if let Some(resolved_port) = &parsed_info.resolved_port {
+            json_workspace["resolved_port"] = serde_json::Value::Number((*resolved_port).into());
+        }
+        
+        if let Some(container_path) = &parsed_info.container_path {
+            json_workspace["container_path"] = serde_json::Value::String(container_path.clone());
+        }
+        
+        if let Some(label) = &parsed_info.label {
+            json_workspace["label"] = serde_json::Value::String(label.clone());
+        }
+        
+        if !parsed_info.tags.is_empty() {
+            json_workspace["tags"] = serde_json::Value::Array(
+                parsed_info.tags.iter()
+                    .map(|tag| serde_json::Value::String(tag.clone()))
+                    .collect()
+            );
+        }
+    }
+    
+    json_workspace
+}
+
+/// Output workspaces as newline-delimited JSON, one compact object per line
+fn output_ndjson(workspaces: &[Workspace]) -> Result<()> {
+    let stdout = io::stdout();
+    let mut handle = stdout.lock();
+    
+    for workspace in workspaces {
+        writeln!(handle, "{}", serde_json::to_string(&workspace_to_json(workspace))?)?;
+    }
+    
+    Ok(())
+}
+
 /// Output workspaces as JSON
 fn output_json(workspaces: &[Workspace]) -> Result<()> {
-    // Create a more detailed representation with original path explicitly included
-    let workspace_details: Vec<serde_json::Value> = workspaces.iter().map(|workspace| {
-        // Determine the path to display - use parsed path if available, otherwise original path
-        let display_path = if let Some(parsed_info) = &workspace.parsed_info {
-            parsed_info.path.clone()
-        } else {
-            workspace.path.clone()
+    let workspace_details: Vec<serde_json::Value> = workspaces.iter().map(workspace_to_json).collect();
+    
+    let json = serde_json::to_string_pretty(&workspace_details)?;
+    println!("{}", json);
+    Ok(())
+}
+
+/// Escape a value for use inside a Markdown table cell
+fn escape_markdown_cell(value: &str) -> String {
+    value.replace('|', "\\|").replace('\n', " ")
+}
+
+/// Wrap a value in a Markdown code span, using a longer fence if it contains backticks
+fn markdown_code(value: &str) -> String {
+    if value.contains('`') {
+        format!("`` {} ``", value)
+    } else {
+        format!("`{}`", value)
+    }
+}
+
+/// Output workspaces as a Markdown table
+fn output_markdown(workspaces: &[Workspace]) -> Result<()> {
+    let stdout = io::stdout();
+    let mut handle = stdout.lock();
+    
+    let header = ["#", "Name", "Path", "Type", "Last Used", "Remote"];
+    let rows: Vec<[String; 6]> = workspaces.iter().enumerate().map(|(i, workspace)| {
+        let mut workspace = workspace.clone();
+        let name = match workspace.name.as_deref() {
+            Some(name) if !name.is_empty() => name.to_string(),
+            _ => workspaces::extract_folder_basename(&workspace.path),
         };
+        let path = workspace.get_display_path().to_string();
+        let remote = workspace.parse_path()
+            .and_then(|info| info.remote_authority.clone())
+            .unwrap_or_default();
         
-        let mut json_workspace = serde_json::json!({
-            "id": workspace.id,
-            "name": workspace.name,
-            "path": display_path,
-            "last_used": workspace.last_used,
-            "last_used_human": if workspace.last_used > 0 {
-                chrono::DateTime::from_timestamp(workspace.last_used / 1000, 0)
-                    .map(|dt| {
-                        let now = chrono::Utc::now();
-                        let duration = now.signed_duration_since(dt);
-                        
-                        if duration.num_days() > 365 {
-                            dt.format("%Y-%m-%d %H:%M:%S").to_string()
-                        } else if duration.num_days() > 30 {
-                            format!("{} months ago", duration.num_days() / 30)
-                        } else if duration.num_days() > 0 {
-                            format!("{} days ago", duration.num_days())
-                        } else if duration.num_hours() > 0 {
-                            format!("{} hours ago", duration.num_hours())
-                        } else if duration.num_minutes() > 0 {
-                            format!("{} minutes ago", duration.num_minutes())
-                        } else {
-                            "just now".to_string()
-                        }
-                    })
-                    .unwrap_or_else(|| "Unknown".to_string())
-            } else {
-                "Unknown".to_string()
-            },
-            "sources": workspace.sources,
-        });
+        [
+            (i + 1).to_string(),
+            escape_markdown_cell(&name),
+            escape_markdown_cell(&markdown_code(&path)),
+            workspace.get_type(),
+            format_last_used(workspace.last_used),
+            escape_markdown_cell(&remote),
+        ]
+    }).collect();
+    
+    // Pad every column to its widest cell so the table is readable as plain text
+    let mut widths = header.map(|h| h.chars().count());
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    
+    let format_row = |cells: &[String]| {
+        let padded: Vec<String> = cells.iter().zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect();
+        format!("| {} |", padded.join(" | "))
+    };
+    
+    writeln!(handle, "{}", format_row(&header.map(String::from)))?;
+    let separator: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+    writeln!(handle, "|-{}-|", separator.join("-|-"))?;
+    for row in &rows {
+        writeln!(handle, "{}", format_row(row))?;
+    }
+    
+    Ok(())
+}
+
+/// Print the comparison of two profiles as a table or JSON
+pub fn print_comparison(comparison: &WorkspaceComparison, profile_a: &str, profile_b: &str, format: &str) -> Result<()> {
+    if format.eq_ignore_ascii_case("json") {
+        println!("{}", serde_json::to_string_pretty(comparison)?);
+        return Ok(());
+    }
+    
+    let stdout = io::stdout();
+    let mut handle = stdout.lock();
+    
+    let mut rows: Vec<(&str, &Workspace)> = Vec::new();
+    rows.extend(comparison.only_in_a.iter().map(|ws| ("A", ws)));
+    rows.extend(comparison.only_in_b.iter().map(|ws| ("B", ws)));
+    rows.extend(comparison.in_both.iter().map(|(ws, _)| ("A+B", ws)));
+    
+    writeln!(handle, "A: {}", profile_a)?;
+    writeln!(handle, "B: {}", profile_b)?;
+    writeln!(handle)?;
+    writeln!(handle, "{:<5} {:<30} Path", "In", "Name")?;
+    writeln!(handle, "{:-<80}", "")?;
+    for (side, workspace) in rows {
+        let name = match workspace.name.as_deref() {
+            Some(name) if !name.is_empty() => name.to_string(),
+            _ => workspaces::extract_folder_basename(&workspace.path),
+        };
+        writeln!(handle, "{:<5} {:<30} {}", side, name, workspace.path)?;
+    }
+    writeln!(handle, "{:-<80}", "")?;
+    writeln!(handle, "Only in A: {}, only in B: {}, in both: {}",
+        comparison.only_in_a.len(), comparison.only_in_b.len(), comparison.in_both.len())?;
+    
+    Ok(())
+}
+
+/// Print shell variables as `export` statements for `eval $(vscode-workspaces-editor env <id>)`.
+/// Each statement ends with `;` so they still work when the shell joins the lines.
+pub fn print_env_vars(vars: &[(String, String)]) {
+    for (key, value) in vars {
+        println!("export {}='{}';", key, value.replace('\'', "'\\''"));
+    }
+}
+
+/// Print workspace statistics as text or JSON
+pub fn print_stats(stats: &WorkspaceStats, format: &str) -> Result<()> {
+    if format.eq_ignore_ascii_case("json") {
+        println!("{}", serde_json::to_string_pretty(stats)?);
+        return Ok(());
+    }
+    
+    let stdout = io::stdout();
+    let mut handle = stdout.lock();
+    
+    writeln!(handle, "Workspaces: {} ({} local, {} remote, {} missing)",
+        stats.total, stats.local_count, stats.remote_count, stats.missing_count)?;
+    
+    let mut by_type: Vec<_> = stats.by_type.iter().collect();
+    by_type.sort();
+    let by_type: Vec<String> = by_type.iter().map(|(kind, count)| format!("{}: {}", kind, count)).collect();
+    writeln!(handle, "By type: {}", by_type.join(", "))?;
+    
+    if let Some(workspace) = &stats.most_recent {
+        writeln!(handle, "Most recent: {}", workspace.path)?;
+    }
+    if let Some(workspace) = &stats.oldest_used {
+        writeln!(handle, "Oldest: {}", workspace.path)?;
+    }
+    
+    if !stats.by_host.is_empty() {
+        // Busiest hosts first
+        let mut by_host: Vec<_> = stats.by_host.iter().collect();
+        by_host.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
         
-        // Add parsed_info with original_path explicitly
-        if let Some(parsed_info) = &workspace.parsed_info {
-            json_workspace["original_path"] = serde_json::Value::String(parsed_info.original_path.clone());
-            json_workspace["workspace_type"] = serde_json::Value::String(format!("{:?}", parsed_info.workspace_type));
-            
-            if let Some(remote_authority) = &parsed_info.remote_authority {
-                json_workspace["remote_authority"] = serde_json::Value::String(remote_authority.clone());
+        writeln!(handle)?;
+        writeln!(handle, "By host:")?;
+        for (host, count) in by_host {
+            writeln!(handle, "  {:<40} {}", host, count)?;
+        }
+    }
+    
+    Ok(())
+}
+
+/// Parse a `--since` value into a timestamp in milliseconds: a relative age (`30m`, `2h`, `7d`, `4w`),
+/// a date (`2024-05-01`) or an RFC 3339 timestamp
+pub fn parse_since(value: &str) -> Result<i64> {
+    let value = value.trim();
+    
+    if let Some(unit) = value.chars().last().filter(|c| c.is_ascii_alphabetic()) {
+        if let Ok(amount) = value[..value.len() - 1].parse::<i64>() {
+            let duration = match unit {
+                'm' => chrono::Duration::minutes(amount),
+                'h' => chrono::Duration::hours(amount),
+                'd' => chrono::Duration::days(amount),
+                'w' => chrono::Duration::weeks(amount),
+                _ => anyhow::bail!("Unknown time unit '{}' in {} (use m, h, d or w)", unit, value),
+            };
+            return Ok((chrono::Utc::now() - duration).timestamp_millis());
+        }
+    }
+    
+    if let Ok(date) = chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        let start = date.and_hms_opt(0, 0, 0).context("Invalid date")?;
+        return Ok(start.and_utc().timestamp_millis());
+    }
+    
+    chrono::DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.timestamp_millis())
+        .with_context(|| format!("Invalid time: {} (e.g. 2h, 7d, 2024-05-01)", value))
+}
+
+/// Print the most recent workspace opens from the activity log, oldest first
+pub fn print_activity_log(last: usize, since: Option<&str>) -> Result<()> {
+    let since = since.map(parse_since).transpose()?;
+    let entries = workspaces::read_activity_log(since)?;
+    
+    let stdout = io::stdout();
+    let mut handle = stdout.lock();
+    
+    if entries.is_empty() {
+        writeln!(handle, "No activity recorded.")?;
+        return Ok(());
+    }
+    
+    let start = entries.len().saturating_sub(last);
+    for entry in &entries[start..] {
+        let time = chrono::DateTime::from_timestamp_millis(entry.timestamp)
+            .map(|dt| dt.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S").to_string())
+            .unwrap_or_else(|| "Unknown".to_string());
+        let source = format!("{:?}", entry.source).to_lowercase();
+        writeln!(handle, "{}  {:<6} {}", time, source, entry.path)?;
+    }
+    
+    Ok(())
+}
+
+/// Guess which editor a profile path belongs to from its directory name
+fn editor_variant(profile_path: &str) -> &'static str {
+    if profile_path == workspaces::ZED_PROFILE_NAME {
+        return "Zed";
+    }
+    
+    let lower = profile_path.to_lowercase();
+    if lower.contains("insiders") {
+        "Insiders"
+    } else if lower.contains("cursor") {
+        "Cursor"
+    } else if lower.contains("antigravity") {
+        "Antigravity"
+    } else if lower.contains("kiro") {
+        "Kiro"
+    } else {
+        "VSCode"
+    }
+}
+
+/// Print all detected profiles, including named profiles, with their workspace counts
+pub fn list_profiles() -> Result<()> {
+    let stdout = io::stdout();
+    let mut handle = stdout.lock();
+    
+    writeln!(handle, "{:<12} {:<7} {:>10}  Profile", "Editor", "Exists", "Workspaces")?;
+    writeln!(handle, "{:-<80}", "")?;
+    for path in workspaces::get_known_vscode_paths() {
+        let named = workspaces::get_named_profiles(&path).unwrap_or_default();
+        let editor = editor_variant(&path);
+        
+        let exists = path == workspaces::ZED_PROFILE_NAME || Path::new(&path).exists();
+        let count = match workspaces::get_workspaces(&path, None) {
+            Ok(found) => found.len().to_string(),
+            Err(_) => "-".to_string(),
+        };
+        writeln!(handle, "{:<12} {:<7} {:>10}  {}", editor, if exists { "yes" } else { "no" }, count, path)?;
+        
+        // Named profiles keep their storage and databases directly in their directory
+        for profile in named {
+            let count = match workspaces::get_named_profile_workspaces(&path, &profile.path) {
+                Ok(found) => found.len().to_string(),
+                Err(_) => "-".to_string(),
+            };
+            let label = format!("  (Named) {}  {}", profile.name, profile.path);
+            writeln!(handle, "{:<12} {:<7} {:>10}  {}", editor, "yes", count, label)?;
+        }
+    }
+    
+    Ok(())
+}
+
+/// Format a single workspace as a tab-separated fzf line: path, name, type, last used
+fn format_fzf_line(workspace: &Workspace) -> String {
+    let name = match workspace.name.as_deref() {
+        Some(name) if !name.is_empty() => name.to_string(),
+        _ => workspaces::extract_folder_basename(&workspace.path),
+    };
+    let workspace_type = workspace.parsed_info.as_ref()
+        .map(|info| format!("{:?}", info.workspace_type).to_lowercase())
+        .unwrap_or_else(|| "folder".to_string());
+    
+    // Tabs inside fields would break the column layout
+    format!(
+        "{}\t{}\t{}\t{}",
+        workspace.path.replace('\t', " "),
+        name.replace('\t', " "),
+        workspace_type,
+        format_last_used(workspace.last_used)
+    )
+}
+
+/// Output workspaces as tab-separated lines for fzf (`fzf --delimiter '\t' --with-nth 2..`)
+fn output_fzf(workspaces: &[Workspace]) -> Result<()> {
+    let stdout = io::stdout();
+    let mut handle = stdout.lock();
+    
+    for workspace in workspaces {
+        writeln!(handle, "{}", format_fzf_line(workspace))?;
+    }
+    
+    Ok(())
+}
+
+/// Run an external picker, feed it one line per entry and return the selected line
+///
+/// Returns `None` if the user cancelled the selection.
+fn run_picker(program: &str, args: &[&str], lines: &[String]) -> Result<Option<String>> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .map_err(|e| anyhow::anyhow!("Failed to run {} (is it installed and in PATH?): {}", program, e))?;
+    
+    {
+        let mut stdin = child.stdin.take()
+            .ok_or_else(|| anyhow::anyhow!("Failed to open {} stdin", program))?;
+        for line in lines {
+            // The picker may exit before reading everything, ignore broken pipes
+            if writeln!(stdin, "{}", line).is_err() {
+                break;
             }
+        }
+    }
+    
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        // Pickers exit with a non-zero status when cancelled or nothing matched
+        return Ok(None);
+    }
+    
+    let selection = String::from_utf8_lossy(&output.stdout);
+    Ok(selection
+        .lines()
+        .next()
+        .filter(|line| !line.is_empty())
+        .map(|line| line.to_string()))
+}
+
+/// Let the user pick a workspace with fzf and return the selected workspace path
+///
+/// Returns `None` if the user cancelled the selection.
+pub fn pick_with_fzf(workspaces: &[Workspace]) -> Result<Option<String>> {
+    let lines: Vec<String> = workspaces.iter().map(format_fzf_line).collect();
+    let selection = run_picker(
+        "fzf",
+        &["--delimiter", "\t", "--with-nth", "2..", "--prompt", "workspace> "],
+        &lines,
+    )?;
+    
+    Ok(selection
+        .as_deref()
+        .and_then(|line| line.split('\t').next())
+        .filter(|path| !path.is_empty())
+        .map(|path| path.to_string()))
+}
+
+/// Separator between the label and the path in dmenu lines
+const DMENU_SEPARATOR: &str = " | ";
+
+/// Format a single workspace as a dmenu line: `<type-icon> <name> | <path>`
+fn format_dmenu_line(workspace: &Workspace) -> String {
+    let name = match workspace.name.as_deref() {
+        Some(name) if !name.is_empty() => name.to_string(),
+        _ => workspaces::extract_folder_basename(&workspace.path),
+    };
+    let type_icon = match workspace.parsed_info.as_ref().map(|info| &info.workspace_type) {
+        Some(WorkspaceType::Workspace) => "🔨",
+        Some(WorkspaceType::File) => "📄",
+        _ => "📁",
+    };
+    
+    format!("{} {}{}{}", type_icon, name, DMENU_SEPARATOR, workspace.path)
+}
+
+/// Output workspaces as dmenu/rofi lines
+fn output_dmenu(workspaces: &[Workspace]) -> Result<()> {
+    let stdout = io::stdout();
+    let mut handle = stdout.lock();
+    
+    for workspace in workspaces {
+        writeln!(handle, "{}", format_dmenu_line(workspace))?;
+    }
+    
+    Ok(())
+}
+
+/// Extract the workspace path from a line selected in dmenu/rofi
+///
+/// Plain paths (not in the dmenu line format) are returned as-is.
+pub fn parse_dmenu_selection(line: &str) -> Option<String> {
+    let line = line.trim();
+    let path = match line.rfind(DMENU_SEPARATOR) {
+        Some(pos) => &line[(pos + DMENU_SEPARATOR.len())..],
+        None => line,
+    };
+    
+    if path.is_empty() {
+        None
+    } else {
+        Some(path.to_string())
+    }
+}
+
+/// Let the user pick a workspace with dmenu (or a compatible picker such as
+/// `rofi -dmenu`) and return the selected workspace path
+pub fn pick_with_dmenu(workspaces: &[Workspace], picker_command: &str) -> Result<Option<String>> {
+    let mut parts = picker_command.split_whitespace();
+    let program = parts.next()
+        .ok_or_else(|| anyhow::anyhow!("Picker command is empty"))?;
+    let args: Vec<&str> = parts.collect();
+    
+    let lines: Vec<String> = workspaces.iter().map(format_dmenu_line).collect();
+    let selection = run_picker(program, &args, &lines)?;
+    
+    Ok(selection.as_deref().and_then(parse_dmenu_selection))
+}
+
+/// Open a workspace with the given editor command
+pub fn open_workspace(path: &str, editor_command: &str) -> Result<()> {
+    spawn_editor(editor_command, path)?;
+    status!("Opening workspace with {}: {}", editor_command, path);
+    
+    if let Err(e) = workspaces::record_workspace_open(path, workspaces::ActivitySource::Cli) {
+        log::warn!("Failed to record workspace open: {}", e);
+    }
+    
+    if UPDATE_ZOXIDE.load(Ordering::Relaxed) {
+        if let Err(e) = add_to_zoxide(path) {
+            log::warn!("Failed to add workspace to zoxide: {}", e);
+        }
+    }
+    Ok(())
+}
+
+/// Add the folder of a local workspace to the zoxide database, so `z` can jump to it.
+/// Remote workspaces are skipped.
+fn add_to_zoxide(path: &str) -> Result<()> {
+    if path.contains("://") && !path.starts_with("file://") {
+        log::debug!("Not adding remote workspace to zoxide: {}", path);
+        return Ok(());
+    }
+    
+    let folder = local_folder(path);
+    if !Path::new(&folder).is_dir() {
+        log::debug!("Not adding missing folder to zoxide: {}", folder);
+        return Ok(());
+    }
+    
+    let status = Command::new("zoxide")
+        .args(["add", &folder])
+        .stdout(Stdio::null())
+        .status()
+        .context("Failed to run zoxide")?;
+    if !status.success() {
+        anyhow::bail!("zoxide exited with {}", status);
+    }
+    
+    log::debug!("Added {} to zoxide", folder);
+    Ok(())
+}
+
+/// Spawn the editor command (e.g. "code", "cursor", "code-insiders") with the given path.
+/// The command may include extra arguments, e.g. "code --new-window".
+pub fn spawn_editor(editor_command: &str, path: &str) -> Result<()> {
+    let mut parts = editor_command.split_whitespace();
+    let program = parts.next()
+        .ok_or_else(|| anyhow::anyhow!("Editor command is empty"))?;
+    
+    match Command::new(program)
+        .args(parts)
+        .arg(path)
+        .spawn() {
+            Ok(_) => Ok(()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Err(anyhow::anyhow!(
+                "Editor command '{}' not found in PATH. Install it or choose another editor \
+                 with --editor or `editor_command` in the config (e.g. code, cursor, code-insiders, windsurf, codium)",
+                program
+            )),
+            Err(e) => Err(anyhow::anyhow!("Failed to open workspace with '{}': {}", program, e)),
+        }
+}
+
+/// Terminal multiplexer (or terminal) used by the `session` subcommand
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SessionTool {
+    #[default]
+    Tmux,
+    Zellij,
+    Kitty,
+}
+
+impl std::str::FromStr for SessionTool {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "tmux" => Ok(SessionTool::Tmux),
+            "zellij" => Ok(SessionTool::Zellij),
+            "kitty" => Ok(SessionTool::Kitty),
+            _ => Err(format!("Unknown session tool '{}' (expected tmux, zellij or kitty)", s)),
+        }
+    }
+}
+
+/// Get the local folder of a workspace path, decoding file:// URIs.
+/// Workspace files (.code-workspace) resolve to the folder containing them.
+pub fn local_folder(path: &str) -> String {
+    // Remote URIs are kept as they are
+    let path = workspaces::path_from_uri(path).unwrap_or_else(|_| path.to_string());
+    
+    if path.ends_with(".code-workspace") {
+        if let Some(parent) = Path::new(&path).parent() {
+            return parent.to_string_lossy().to_string();
+        }
+    }
+    path
+}
+
+/// Get the session name for a folder: its basename, without the characters tmux doesn't
+/// allow in session names ('.' and ':')
+pub fn session_name(folder: &str) -> String {
+    let basename = Path::new(folder.trim_end_matches(['/', '\\']))
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "workspace".to_string());
+    basename.replace(['.', ':'], "_")
+}
+
+/// Attach to the terminal session named `name`, creating it in `folder` if it doesn't exist yet
+pub fn open_session(tool: SessionTool, name: &str, folder: &str) -> Result<()> {
+    if !Path::new(folder).is_dir() {
+        anyhow::bail!("Workspace folder does not exist: {}", folder);
+    }
+    
+    let program = match tool {
+        SessionTool::Tmux => "tmux",
+        SessionTool::Zellij => "zellij",
+        SessionTool::Kitty => "kitty",
+    };
+    if !workspaces::is_command_available(program) {
+        anyhow::bail!("'{}' not found in PATH", program);
+    }
+    
+    match tool {
+        SessionTool::Tmux => {
+            let exists = Command::new("tmux")
+                .args(["has-session", "-t", &format!("={}", name)])
+                .stderr(Stdio::null())
+                .status()
+                .map(|status| status.success())
+                .unwrap_or(false);
             
-            if let Some(remote_host) = &parsed_info.remote_host {
-                json_workspace["remote_host"] = serde_json::Value::String(remote_host.clone());
+            if !exists {
+                status!("Creating tmux session '{}' in {}", name, folder);
+                run_session_command(Command::new("tmux").args(["new-session", "-d", "-s", name, "-c", folder]))?;
             }
             
-            if let Some(remote_user) = &parsed_info.remote_user {
-                json_workspace["remote_user"] = serde_json::Value::String(remote_user.clone());
+            // Inside tmux, attaching would nest sessions, so switch to it instead
+            if std::env::var_os("TMUX").is_some() {
+                run_session_command(Command::new("tmux").args(["switch-client", "-t", name]))
+            } else {
+                status!("Attaching to tmux session '{}'", name);
+                run_session_command(Command::new("tmux").args(["attach-session", "-t", name]))
             }
-            
-            if let Some(remote_port) = &parsed_info.remote_port {
-                json_workspace["remote_port"] = serde_json::Value::Number((*remote_port).into());
+        }
+        SessionTool::Zellij => {
+            // --create attaches to an existing session or starts one in the current directory
+            status!("Attaching to zellij session '{}' in {}", name, folder);
+            run_session_command(Command::new("zellij").args(["attach", "--create", name]).current_dir(folder))
+        }
+        SessionTool::Kitty => {
+            status!("Opening kitty window '{}' in {}", name, folder);
+            run_session_command(Command::new("kitty").args(["--detach", "--title", name, "--directory", folder]))
+        }
+    }
+}
+
+/// Run a session command, failing if it exits unsuccessfully
+fn run_session_command(command: &mut Command) -> Result<()> {
+    let status = command.status()
+        .with_context(|| format!("Failed to run {:?}", command.get_program()))?;
+    if !status.success() {
+        anyhow::bail!("{:?} exited with {}", command.get_program(), status);
+    }
+    Ok(())
+}
+
+/// Delete workspaces, printing each one along with its sources.
+/// In dry-run mode nothing is removed and the planned actions are printed instead.
+pub fn delete_workspaces(profile_path: &str, workspaces: &[Workspace], dry_run: bool) -> Result<bool> {
+    if workspaces.is_empty() {
+        status!("No workspaces to delete.");
+        return Ok(!dry_run);
+    }
+    
+    let action = if dry_run { "Would delete" } else { "Deleting" };
+    for workspace in workspaces {
+        status!("{} workspace {} ({})", action, workspace.id, workspace.path);
+        for source in &workspace.sources {
+            match source {
+                WorkspaceSource::Storage(path) =>
+                    status!("  - remove storage directory for {}", path),
+                WorkspaceSource::Database(key) =>
+                    status!("  - remove entry from database {}", key),
+                WorkspaceSource::Zed(channel) =>
+                    status!("  - remove entry from Zed({})", channel),
+                // Named profile entries are removed through their storage and database sources
+                WorkspaceSource::Profile(_) => {},
             }
-            
-            if let Some(container_path) = &parsed_info.container_path {
-                json_workspace["container_path"] = serde_json::Value::String(container_path.clone());
+        }
+    }
+    
+    let result = workspaces::delete_workspace(profile_path, workspaces, dry_run)?;
+    
+    if dry_run {
+        status!("Dry run: {} workspaces would be deleted, no changes were made.", workspaces.len());
+    } else if result {
+        status!("Deleted {} workspaces.", workspaces.len());
+    } else {
+        eprintln!("Some workspaces could not be deleted, run with RUST_LOG=warn for details.");
+    }
+    
+    Ok(result)
+}
+
+/// Delete the workspaces matching each ID or path, one at a time, and print a summary.
+/// Returns the number of deleted and failed entries; IDs that match nothing count as failed.
+pub fn batch_delete_workspaces(profile_path: &str, workspaces: &[Workspace], ids: &[&str], dry_run: bool) -> Result<(usize, usize)> {
+    let mut deleted = 0;
+    let mut failed = 0;
+    
+    for id in ids {
+        let Some(workspace) = workspaces.iter().find(|ws| ws.id == *id || ws.path == *id) else {
+            eprintln!("Not found: {}", id);
+            failed += 1;
+            continue;
+        };
+        
+        if dry_run {
+            status!("Would delete workspace {} ({})", workspace.id, workspace.path);
+            deleted += 1;
+            continue;
+        }
+        
+        match workspaces::delete_workspace(profile_path, std::slice::from_ref(workspace), false) {
+            Ok(true) => {
+                status!("Deleted workspace {} ({})", workspace.id, workspace.path);
+                deleted += 1;
             }
-            
-            if let Some(label) = &parsed_info.label {
-                json_workspace["label"] = serde_json::Value::String(label.clone());
+            Ok(false) => {
+                eprintln!("Failed to delete workspace {} ({})", workspace.id, workspace.path);
+                failed += 1;
             }
-            
-            if !parsed_info.tags.is_empty() {
-                json_workspace["tags"] = serde_json::Value::Array(
-                    parsed_info.tags.iter()
-                        .map(|tag| serde_json::Value::String(tag.clone()))
-                        .collect()
-                );
+            Err(e) => {
+                eprintln!("Failed to delete workspace {} ({}): {}", workspace.id, workspace.path, e);
+                failed += 1;
             }
         }
+    }
+    
+    if dry_run {
+        status!("Dry run: would delete {} workspaces, failed {}", deleted, failed);
+    } else {
+        status!("Deleted {} workspaces, failed {}", deleted, failed);
+    }
+    
+    Ok((deleted, failed))
+}
+
+/// Delete all workspaces whose path no longer exists.
+/// Returns the number of missing workspaces found and whether all of them were deleted.
+pub fn clean_missing_workspaces(profile_path: &str, workspaces: &[Workspace], dry_run: bool, interactive: bool) -> Result<(usize, bool)> {
+    let mut missing: Vec<Workspace> = workspaces.iter()
+        .filter(|ws| !workspaces::workspace_exists(ws))
+        .cloned()
+        .collect();
+    
+    if missing.is_empty() {
+        status!("No missing workspaces found.");
+        return Ok((0, true));
+    }
+    
+    status!("Found {} missing workspaces.", missing.len());
+    if interactive {
+        // A path can look missing because a network share is not mounted, let the user decide
+        missing = confirm_each(missing, &mut io::stdin().lock(), &mut io::stdout())?;
+        if missing.is_empty() {
+            status!("Nothing deleted.");
+            return Ok((0, true));
+        }
+    }
+    
+    let deleted = delete_workspaces(profile_path, &missing, dry_run)?;
+    Ok((missing.len(), deleted))
+}
+
+/// Ask `Delete '<path>'? [y/N/a/q]` for each workspace and return the confirmed ones.
+/// `a` confirms the remaining workspaces, `q` (or the end of input) skips them.
+fn confirm_each(workspaces: Vec<Workspace>, input: &mut impl io::BufRead, output: &mut impl Write) -> Result<Vec<Workspace>> {
+    let mut confirmed = Vec::new();
+    let mut remaining = workspaces.into_iter();
+    
+    while let Some(workspace) = remaining.next() {
+        write!(output, "Delete '{}'? [y/N/a/q] ", workspace.path)?;
+        output.flush()?;
         
-        json_workspace
-    }).collect();
+        let mut answer = String::new();
+        if input.read_line(&mut answer)? == 0 {
+            writeln!(output)?;
+            break;
+        }
+        match answer.trim().to_lowercase().as_str() {
+            "y" | "yes" => confirmed.push(workspace),
+            "a" | "all" => {
+                confirmed.push(workspace);
+                confirmed.extend(remaining);
+                break;
+            }
+            "q" | "quit" => break,
+            _ => {}
+        }
+    }
+    
+    Ok(confirmed)
+}
+
+/// Outcome of a single health check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CheckStatus {
+    /// The check does not apply to this workspace
+    Skip,
+    /// The check passed
+    Pass,
+    /// Something looks off but the workspace is usable
+    Warn,
+    /// The check failed
+    Fail,
+}
+
+impl CheckStatus {
+    fn label(self) -> &'static str {
+        match self {
+            CheckStatus::Skip => "SKIP",
+            CheckStatus::Pass => "PASS",
+            CheckStatus::Warn => "WARN",
+            CheckStatus::Fail => "FAIL",
+        }
+    }
+}
+
+/// Result of a single health check
+#[derive(Debug, Clone)]
+pub struct CheckResult {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+impl CheckResult {
+    fn new(name: &'static str, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self { name, status, detail: detail.into() }
+    }
+}
+
+/// Run all health checks on a workspace: existence, workspace.json, database entry,
+/// SSH reachability for remotes and missing metadata
+pub async fn health_check(profile_path: &str, workspace: &Workspace) -> Vec<CheckResult> {
+    let mut workspace = workspace.clone();
+    let mut results = Vec::new();
+    let is_remote = workspace.is_remote();
+    
+    // 1. The workspace exists on disk
+    if is_remote {
+        results.push(CheckResult::new("exists", CheckStatus::Skip, "remote workspace, see the SSH check"));
+    } else if workspaces::workspace_exists(&workspace) {
+        results.push(CheckResult::new("exists", CheckStatus::Pass, "path exists"));
+    } else {
+        results.push(CheckResult::new("exists", CheckStatus::Fail, format!("path does not exist: {}", workspace.path)));
+    }
+    
+    // 2. workspace.json can be read and parsed
+    results.push(match &workspace.storage_path {
+        Some(storage_path) => {
+            let file = workspaces::expand_tilde(profile_path)
+                .map(|profile| Path::new(&profile).join("User").join(storage_path));
+            match file.and_then(|file| Ok(serde_json::from_str::<serde_json::Value>(&fs::read_to_string(file)?)?)) {
+                Ok(json) if json.get("folder").is_some() || json.get("workspace").is_some() =>
+                    CheckResult::new("workspace.json", CheckStatus::Pass, storage_path.clone()),
+                Ok(_) => CheckResult::new("workspace.json", CheckStatus::Warn, format!("{} has no folder or workspace entry", storage_path)),
+                Err(e) => CheckResult::new("workspace.json", CheckStatus::Fail, format!("{}: {}", storage_path, e)),
+            }
+        }
+        None => CheckResult::new("workspace.json", CheckStatus::Skip, "no workspace storage entry"),
+    });
+    
+    // 3. The state database entry is consistent with workspace storage
+    let in_database = workspace.sources.iter().any(|s| matches!(s, WorkspaceSource::Database(_)));
+    let in_storage = workspace.sources.iter().any(|s| matches!(s, WorkspaceSource::Storage(_)));
+    let is_zed = workspace.sources.iter().any(|s| matches!(s, WorkspaceSource::Zed(_)));
+    results.push(if is_zed {
+        CheckResult::new("state.vscdb", CheckStatus::Skip, "Zed workspace")
+    } else if in_database {
+        CheckResult::new("state.vscdb", CheckStatus::Pass, "listed in recently opened")
+    } else if in_storage {
+        CheckResult::new("state.vscdb", CheckStatus::Warn, "not listed in recently opened (storage only)")
+    } else {
+        CheckResult::new("state.vscdb", CheckStatus::Fail, "not found in any source")
+    });
+    
+    // 4. Remote workspaces are reachable over SSH
+    if is_remote {
+        results.push(match workspaces::check_remote_workspace_exists(&workspace).await {
+            Some(true) => CheckResult::new("ssh", CheckStatus::Pass, "remote path exists"),
+            Some(false) => CheckResult::new("ssh", CheckStatus::Fail, "remote path does not exist"),
+            None => CheckResult::new("ssh", CheckStatus::Warn, "not an SSH remote, or the host could not be reached"),
+        });
+    } else {
+        results.push(CheckResult::new("ssh", CheckStatus::Skip, "local workspace"));
+    }
+    
+    // 5. Metadata fields are present
+    let mut missing = Vec::new();
+    if workspace.last_used <= 0 {
+        missing.push("last_used");
+    }
+    if workspace.name.as_deref().unwrap_or_default().is_empty() {
+        missing.push("name");
+    }
+    results.push(if missing.is_empty() {
+        CheckResult::new("metadata", CheckStatus::Pass, "all fields present")
+    } else {
+        CheckResult::new("metadata", CheckStatus::Warn, format!("missing: {}", missing.join(", ")))
+    });
+    
+    results
+}
+
+/// Print a health check report and return the worst status of its checks
+pub fn print_health_report(workspace: &Workspace, results: &[CheckResult]) -> CheckStatus {
+    println!("Workspace: {}", workspace.path);
+    println!("ID: {}", workspace.id);
+    println!("{:-<80}", "");
+    for result in results {
+        println!("[{}] {:<15} {}", result.status.label(), result.name, result.detail);
+    }
+    println!("{:-<80}", "");
+    
+    let worst = results.iter().map(|r| r.status).max().unwrap_or(CheckStatus::Pass);
+    match worst {
+        CheckStatus::Fail => println!("Result: failed"),
+        CheckStatus::Warn => println!("Result: passed with warnings"),
+        _ => println!("Result: healthy"),
+    }
+    worst
+}
+
+/// Check the existence of all workspaces and report missing ones and remote reachability
+pub async fn check_all_workspaces(workspaces: &mut [Workspace], timeout: std::time::Duration) -> Result<()> {
+    status!("Checking {} workspaces...", workspaces.len());
+    workspaces::batch_check_workspace_existence(workspaces, timeout).await?;
+    
+    let mut local_missing = 0;
+    let mut remote_total = 0;
+    let mut remote_reachable = 0;
+    for workspace in workspaces.iter_mut() {
+        let is_remote = workspace.is_remote();
+        if is_remote {
+            remote_total += 1;
+            if workspace.exists_checked {
+                remote_reachable += 1;
+            }
+        }
+        
+        if workspace.exists_checked && !workspace.exists {
+            if !is_remote {
+                local_missing += 1;
+            }
+            println!("Missing: {}", workspace.path);
+        } else if is_remote && !workspace.exists_checked {
+            println!("Unreachable: {}", workspace.path);
+        }
+    }
+    
+    println!("Local workspaces missing: {}", local_missing);
+    println!("Remote workspaces reachable: {}/{}", remote_reachable, remote_total);
     
-    let json = serde_json::to_string_pretty(&workspace_details)?;
-    println!("{}", json);
     Ok(())
 }
 
-/// Open a workspace with VSCode
-pub fn open_workspace(path: &str) -> Result<()> {
-    // Determine the appropriate command to use based on the platform
-    #[cfg(target_os = "windows")]
-    let code_command = "code";
+/// Prefix used for generated desktop launcher file names
+const DESKTOP_FILE_PREFIX: &str = "vscode-workspace-";
+
+/// Get the default output directory for generated desktop launcher files
+pub fn default_desktop_files_dir() -> Result<PathBuf> {
+    let base_dirs = directories::BaseDirs::new()
+        .ok_or_else(|| anyhow::anyhow!("Failed to determine home directory"))?;
+    Ok(base_dirs.data_dir().join("applications/vscode-workspaces"))
+}
+
+/// Quote an argument for the Exec key of a desktop entry
+fn quote_desktop_exec_arg(arg: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in arg.chars() {
+        match c {
+            '"' | '`' | '$' | '\\' => {
+                quoted.push('\\');
+                quoted.push(c);
+            }
+            // Field codes start with '%', a literal one must be doubled
+            '%' => quoted.push_str("%%"),
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Generate a `.desktop` launcher file for each existing workspace
+///
+/// Files for the same workspace ID are overwritten, and launcher files for
+/// workspaces that no longer exist are removed. Returns the number of files written.
+pub fn generate_desktop_files(workspaces: &[Workspace], output_dir: &str) -> Result<usize> {
+    let output_dir = Path::new(output_dir);
+    fs::create_dir_all(output_dir)
+        .with_context(|| format!("Failed to create directory: {}", output_dir.display()))?;
     
-    #[cfg(target_os = "macos")]
-    let code_command = "code";
+    let mut generated = HashSet::new();
     
-    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
-    let code_command = "code";
+    for workspace in workspaces {
+        if !workspaces::workspace_exists(workspace) {
+            continue;
+        }
+        
+        // Clone for methods that require mutability
+        let mut workspace_clone = workspace.clone();
+        let label = workspace_clone.get_label().replace(['\n', '\r'], " ");
+        
+        let file_name = format!("{}{}.desktop", DESKTOP_FILE_PREFIX, workspace.id);
+        let content = format!(
+            "[Desktop Entry]\n\
+             Type=Application\n\
+             Name={}\n\
+             Comment={}\n\
+             Exec=code {}\n\
+             Icon=vscode\n\
+             Categories=Development;\n",
+            label,
+            workspace.path.replace(['\n', '\r'], " "),
+            quote_desktop_exec_arg(&workspace.path)
+        );
+        
+        let file_path = output_dir.join(&file_name);
+        fs::write(&file_path, content)
+            .with_context(|| format!("Failed to write desktop file: {}", file_path.display()))?;
+        generated.insert(file_name);
+    }
     
-    // Open the workspace with VSCode
-    match Command::new(code_command)
-        .arg(path)
-        .spawn() {
-            Ok(_) => {
-                println!("Opening workspace in VSCode: {}", path);
-                Ok(())
-            },
-            Err(e) => Err(anyhow::anyhow!("Failed to open workspace: {}", e)),
+    // Remove launcher files for workspaces that no longer exist
+    for entry in fs::read_dir(output_dir)?.flatten() {
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        if file_name.starts_with(DESKTOP_FILE_PREFIX)
+            && file_name.ends_with(".desktop")
+            && !generated.contains(&file_name)
+        {
+            if let Err(e) = fs::remove_file(entry.path()) {
+                log::warn!("Failed to remove stale desktop file {}: {}", file_name, e);
+            }
         }
-} 
\ No newline at end of file
+    }
+    
+    Ok(generated.len())
+}
+
+/// Refresh the desktop entry cache for the given directory
+pub fn update_desktop_database(output_dir: &str) -> Result<()> {
+    let status = Command::new("update-desktop-database")
+        .arg(output_dir)
+        .status()
+        .map_err(|e| anyhow::anyhow!("Failed to run update-desktop-database: {}", e))?;
+    
+    if !status.success() {
+        return Err(anyhow::anyhow!("update-desktop-database exited with {}", status));
+    }
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn workspace(path: &str) -> Workspace {
+        Workspace::test_fixture(path.trim_start_matches('/'), path)
+    }
+
+    /// Run `confirm_each` on five workspaces with the given answers and return the confirmed paths
+    fn confirm(answers: &str) -> Vec<String> {
+        let workspaces = ["/a", "/b", "/c", "/d", "/e"].into_iter().map(workspace).collect();
+        let mut output = Vec::new();
+        confirm_each(workspaces, &mut answers.as_bytes(), &mut output).unwrap()
+            .into_iter()
+            .map(|ws| ws.path)
+            .collect()
+    }
+
+    #[test]
+    fn test_local_folder() {
+        assert_eq!(local_folder("file:///home/alice/my%20api"), "/home/alice/my api");
+        assert_eq!(local_folder("file:///home/alice/team.code-workspace"), "/home/alice");
+        assert_eq!(local_folder("/home/alice/web"), "/home/alice/web");
+        assert_eq!(local_folder("file://server/share/docs"), "//server/share/docs");
+        assert_eq!(local_folder("vscode-remote://ssh-remote%2Bdevbox/srv"), "vscode-remote://ssh-remote%2Bdevbox/srv");
+    }
+
+    #[test]
+    fn test_confirm_each() {
+        assert_eq!(confirm("y\nn\nY\n\nyes\n"), ["/a", "/c", "/e"]);
+        assert_eq!(confirm("n\ny\na\n"), ["/b", "/c", "/d", "/e"]);
+        assert_eq!(confirm("y\nq\ny\n"), ["/a"]);
+        // The end of input skips the remaining workspaces
+        assert_eq!(confirm("y\n"), ["/a"]);
+        assert!(confirm("").is_empty());
+    }
+}
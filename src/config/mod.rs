@@ -0,0 +1,191 @@
+use crate::workspaces::SortOrder;
+use anyhow::{Context, Result};
+use directories::BaseDirs;
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directory name used for the application's own files
+const APP_DIR_NAME: &str = "vscode-workspaces-editor";
+
+/// Name of the configuration file
+const CONFIG_FILE_NAME: &str = "config.toml";
+
+/// Commented default configuration written by `config init`
+const DEFAULT_CONFIG: &str = r#"# VSCode Workspaces Editor configuration
+#
+# Command line arguments always take priority over the values below.
+
+# Profile path used when --profile is not given
+# (defaults to the platform's VSCode profile)
+# default_profile = "~/.config/Code"
+
+# Reload workspaces in the TUI every N seconds (disabled when unset)
+# auto_reload_interval = 30
+
+# Command used to open workspaces
+editor_command = "code"
+
+# Disable colored output
+no_color = false
+
+# Default sort order: "last_used", "name" or "path"
+default_sort = "last_used"
+
+# Default output format for the list subcommand: "text", "json", "ndjson", "markdown", "fzf" or "dmenu"
+default_list_format = "text"
+
+# Additional workspaceStorage directories to scan, e.g. for custom installations
+# extra_storage_dirs = ["/opt/vscode/data/user-data/User/workspaceStorage"]
+
+# Key bindings of the TUI: "default" or "emacs" (Ctrl+F/B/N/P/G/K, Alt+F/B;
+# Ctrl+P then moves up instead of opening quick open)
+keybinding_mode = "default"
+
+# Indicators shown in the TUI workspace list. The defaults are emoji, use plain
+# letters for terminals that don't render them well.
+# [icons]
+# folder = "D"
+# workspace = "W"
+# file = "F"
+# worktree = "G"
+# remote = "R"
+# local = "L"
+# exists = "+"
+# missing = "-"
+# marked = "X"
+"#;
+
+/// User configuration loaded from `config.toml`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Profile path used when no profile is given on the command line
+    pub default_profile: Option<String>,
+    /// Interval in seconds between automatic workspace reloads in the TUI
+    pub auto_reload_interval: Option<u64>,
+    /// Command used to open workspaces
+    pub editor_command: String,
+    /// Disable colored output
+    pub no_color: bool,
+    /// Default sort order for workspace lists
+    pub default_sort: SortOrder,
+    /// Default output format for the `list` subcommand
+    pub default_list_format: String,
+    /// Additional workspace storage directories to scan
+    pub extra_storage_dirs: Vec<String>,
+    /// Key bindings of the TUI
+    pub keybinding_mode: KeybindingMode,
+    /// Indicators shown in the TUI workspace list
+    pub icons: IconConfig,
+}
+
+/// Key bindings of the TUI
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum KeybindingMode {
+    /// Arrow keys and Esc only
+    #[default]
+    Default,
+    /// Emacs-style bindings in addition to the default keys
+    Emacs,
+}
+
+/// Indicators of the TUI workspace list, from the `[icons]` section
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct IconConfig {
+    /// Type indicator of folders
+    pub folder: String,
+    /// Type indicator of multi-root workspaces
+    pub workspace: String,
+    /// Type indicator of single files
+    pub file: String,
+    /// Type indicator of folders that are linked Git worktrees
+    pub worktree: String,
+    /// Indicator of remote workspaces
+    pub remote: String,
+    /// Indicator of local workspaces
+    pub local: String,
+    /// Indicator of workspaces that exist
+    pub exists: String,
+    /// Indicator of workspaces that are missing
+    pub missing: String,
+    /// Indicator of workspaces marked for deletion
+    pub marked: String,
+}
+
+impl Default for IconConfig {
+    fn default() -> Self {
+        Self {
+            folder: "📁".to_string(),
+            workspace: "🔨".to_string(),
+            file: "📄".to_string(),
+            worktree: "🌿".to_string(),
+            remote: "🌐".to_string(),
+            local: "🏠".to_string(),
+            exists: "✓".to_string(),
+            missing: "✗".to_string(),
+            marked: "X".to_string(),
+        }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            default_profile: None,
+            auto_reload_interval: None,
+            editor_command: "code".to_string(),
+            no_color: false,
+            default_sort: SortOrder::default(),
+            default_list_format: "text".to_string(),
+            extra_storage_dirs: Vec::new(),
+            keybinding_mode: KeybindingMode::default(),
+            icons: IconConfig::default(),
+        }
+    }
+}
+
+impl Config {
+    /// Load the configuration from the default location, falling back to defaults if the file is missing
+    pub fn load() -> Result<Self> {
+        Self::load_from(&config_path()?)
+    }
+
+    /// Load the configuration from the given file, falling back to defaults if the file is missing
+    pub fn load_from(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+
+        toml::from_str(&content)
+            .with_context(|| format!("Failed to parse config file: {}", path.display()))
+    }
+}
+
+/// Get the path of the configuration file (e.g. ~/.config/vscode-workspaces-editor/config.toml)
+pub fn config_path() -> Result<PathBuf> {
+    let base_dirs = BaseDirs::new().context("Could not determine config directory")?;
+    Ok(base_dirs.config_dir().join(APP_DIR_NAME).join(CONFIG_FILE_NAME))
+}
+
+/// Write the commented default configuration to the given path
+pub fn write_default_config(path: &Path, force: bool) -> Result<()> {
+    if path.exists() && !force {
+        anyhow::bail!("Config file already exists: {} (use --force to overwrite)", path.display());
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create config directory: {}", parent.display()))?;
+    }
+
+    fs::write(path, DEFAULT_CONFIG)
+        .with_context(|| format!("Failed to write config file: {}", path.display()))?;
+
+    Ok(())
+}
@@ -1,3 +1,4 @@
 pub mod workspaces;
 pub mod cli;
-pub mod tui; 
\ No newline at end of file
+pub mod tui; 
+pub mod config;
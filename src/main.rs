@@ -1,21 +1,81 @@
 mod workspaces;
 mod tui;
+#[macro_use]
 mod cli;
+mod config;
 
 use clap::{Parser, Subcommand};
-use anyhow::Result;
+use anyhow::{Context, Result};
+use config::Config;
+use std::process::{ExitCode, Termination};
+use workspaces::WorkspaceError;
+
+/// Exit codes, listed at the end of `--help`
+const EXIT_CODES_HELP: &str = "\
+Exit codes:
+  0   Success
+  1   General error
+  2   Workspace not found
+  3   Profile not found
+  4   Permission denied
+  5   Database locked
+  10  Dry run, changes would have been made
+  11  Health check passed with warnings
+  12  Health check failed";
+
+/// Build metadata shown by `--version`, embedded by build.rs
+const LONG_VERSION: &str = concat!(
+    env!("CARGO_PKG_VERSION"), "\n",
+    "commit:   ", env!("BUILD_GIT_HASH"), "\n",
+    "built:    ", env!("BUILD_TIMESTAMP"), "\n",
+    "target:   ", env!("BUILD_TARGET"), "\n",
+    "ratatui:  ", env!("BUILD_DEP_RATATUI"), "\n",
+    "rusqlite: ", env!("BUILD_DEP_RUSQLITE"),
+);
 
 /// VSCode Workspaces Editor
 #[derive(Parser, Debug)]
-#[clap(version, about, long_about = None)]
+#[clap(version, long_version = LONG_VERSION, about, long_about = None, after_help = EXIT_CODES_HELP)]
 struct Args {
     /// Path to the workspaces storage profile (if not provided, default profile will be used)
-    #[clap(short, long)]
+    #[clap(short, long, env = "VSCODE_WE_PROFILE")]
     profile: Option<String>,
     
     /// Disable colored output (alternatively, set NO_COLOR environment variable)
     #[clap(long)]
     no_color: bool,
+    
+    /// Don't back up databases before modifying them
+    #[clap(long, global = true)]
+    no_backup: bool,
+    
+    /// Editor command used to open workspaces (e.g. code, cursor, code-insiders, windsurf, codium)
+    #[clap(long, global = true)]
+    editor: Option<String>,
+    
+    /// Additional workspaceStorage directory to scan (can be repeated)
+    #[clap(long = "extra-storage", value_name = "DIR", global = true)]
+    extra_storage: Vec<String>,
+
+    /// Include recent projects of JetBrains IDEs in the workspace list
+    #[clap(long, global = true)]
+    include_jetbrains: bool,
+
+    /// Add opened local workspaces to zoxide (defaults to true if zoxide is installed)
+    #[clap(long, global = true, num_args = 0..=1, default_missing_value = "true", value_name = "BOOL")]
+    update_zoxide: Option<bool>,
+
+    /// Suppress status messages on stdout (errors are still printed to stderr)
+    #[clap(short, long, global = true)]
+    quiet: bool,
+
+    /// Log each step of loading workspaces, with timings, to stderr (like RUST_LOG=debug)
+    #[clap(short, long, global = true)]
+    verbose: bool,
+
+    /// Print errors to stderr as JSON ({"error": "...", "code": "..."}) for scripts
+    #[clap(long, global = true)]
+    json_errors: bool,
 
     /// CLI Subcommands
     #[clap(subcommand)]
@@ -27,16 +87,44 @@ struct Args {
 enum Commands {
     /// List all workspaces
     List {
-        /// Output format (text or json)
-        #[clap(short, long, default_value = "text")]
-        format: String,
+        /// Output format (text, json, ndjson, markdown, fzf or dmenu; defaults to the configured format)
+        #[clap(short, long)]
+        format: Option<String>,
+        
+        /// Sort order (last_used, name or path; defaults to the configured order)
+        #[clap(short, long)]
+        sort: Option<workspaces::SortOrder>,
+        
+        /// Pick a workspace with fzf and open it in the editor
+        #[clap(long)]
+        fzf: bool,
+        
+        /// Pick a workspace with dmenu/rofi and open it in the editor
+        #[clap(long, conflicts_with = "fzf")]
+        dmenu: bool,
+        
+        /// Picker command used with --dmenu (e.g. "rofi -dmenu -i")
+        #[clap(long, default_value = "dmenu -i -l 20")]
+        picker: String,
+        
+        /// List archived workspaces instead of the profile's workspaces
+        #[clap(long)]
+        archived: bool,
+        
+        /// Only list workspaces matching a search query (e.g. "api :existing:no :type:folder")
+        #[clap(long)]
+        filter: Option<String>,
+        
+        /// Print only the number of matching workspaces
+        #[clap(long, conflicts_with_all = ["fzf", "dmenu", "format"])]
+        count: bool,
     },
     /// Parse a specific workspace path (for testing)
     Parse {
         /// The workspace path to parse
         path: String,
     },
-    /// Diagnose a specific workspace by ID or path
+    /// Diagnose a specific workspace by ID or path (superseded by `check`)
     Diagnose {
         /// The workspace ID or full path to diagnose
         #[clap(name = "id-or-path")]
@@ -45,12 +133,20 @@ enum Commands {
         /// Profile path (uses default if not specified)
         #[clap(short, long)]
         profile: Option<String>,
+        
+        /// Also list the keys stored in the profile's state databases, with their value sizes
+        #[clap(long)]
+        show_db_keys: bool,
     },
-    /// Open a workspace with VSCode
+    /// Open a workspace with the configured editor
     Open {
         /// The workspace ID or full path to open
-        #[clap(name = "id-or-path")]
-        id_or_path: String,
+        #[clap(name = "id-or-path", required_unless_present = "stdin")]
+        id_or_path: Option<String>,
+        
+        /// Read the workspace ID or path from the first line of standard input
+        #[clap(long, conflicts_with = "id-or-path")]
+        stdin: bool,
         
         /// Profile path (uses default if not specified)
         #[clap(short, long)]
@@ -60,42 +156,435 @@ enum Commands {
         #[clap(long)]
         use_parsed: bool,
     },
+    /// Create or attach to a terminal session (tmux, zellij or kitty) in a workspace folder
+    Session {
+        /// The workspace ID or full path
+        #[clap(name = "id-or-path")]
+        id_or_path: String,
+        
+        /// Profile path (uses default if not specified)
+        #[clap(short, long)]
+        profile: Option<String>,
+        
+        /// Session tool (tmux, zellij or kitty)
+        #[clap(long, default_value = "tmux")]
+        tool: cli::SessionTool,
+    },
+    /// Delete a workspace by ID or path
+    Delete {
+        /// The workspace ID or full path to delete
+        #[clap(name = "id-or-path")]
+        id_or_path: String,
+        
+        /// Profile path (uses default if not specified)
+        #[clap(short, long)]
+        profile: Option<String>,
+        
+        /// Show what would be removed without changing anything
+        #[clap(long)]
+        dry_run: bool,
+    },
+    /// Set the display name of a workspace (an empty name removes it)
+    Rename {
+        /// The workspace ID or full path to rename
+        #[clap(name = "id-or-path")]
+        id_or_path: String,
+        
+        /// The new name
+        name: String,
+        
+        /// Profile path (uses default if not specified)
+        #[clap(short, long)]
+        profile: Option<String>,
+    },
+    /// Archive a workspace: remove it from the profile but keep it in the local archive
+    Archive {
+        /// The workspace ID or full path to archive
+        #[clap(name = "id-or-path")]
+        id_or_path: String,
+        
+        /// Profile path (uses default if not specified)
+        #[clap(short, long)]
+        profile: Option<String>,
+    },
+    /// Restore archived workspaces into the profile
+    Restore {
+        /// IDs or paths of the archived workspaces (see `list --archived`)
+        #[clap(name = "id-or-path", required = true)]
+        ids_or_paths: Vec<String>,
+        
+        /// Profile path (uses default if not specified)
+        #[clap(short, long)]
+        profile: Option<String>,
+    },
+    /// Save all workspaces to a timestamped JSON file, e.g. before cleaning up
+    Snapshot {
+        /// Directory to write the snapshot to
+        #[clap(name = "snapshot-dir")]
+        snapshot_path: String,
+        
+        /// Profile path (uses default if not specified)
+        #[clap(short, long)]
+        profile: Option<String>,
+    },
+    /// Add the workspaces of a snapshot that are missing from the profile back to it
+    RestoreSnapshot {
+        /// Snapshot file written by the snapshot command
+        #[clap(name = "snapshot-file")]
+        snapshot_file: String,
+        
+        /// Profile path (uses default if not specified)
+        #[clap(short, long)]
+        profile: Option<String>,
+    },
+    /// Print workspace properties as shell variables: eval $(vscode-workspaces-editor env <id>)
+    Env {
+        /// The workspace ID or full path
+        #[clap(name = "id-or-path")]
+        id_or_path: String,
+        
+        /// Profile path (uses default if not specified)
+        #[clap(short, long)]
+        profile: Option<String>,
+    },
+    /// Delete workspaces listed by ID or path, one per line, from a file or stdin
+    BatchDelete {
+        /// Read workspace IDs from this file
+        #[clap(long, required_unless_present = "stdin")]
+        from_file: Option<String>,
+        
+        /// Read workspace IDs from standard input
+        #[clap(long, conflicts_with = "from_file")]
+        stdin: bool,
+        
+        /// Profile path (uses default if not specified)
+        #[clap(short, long)]
+        profile: Option<String>,
+        
+        /// Show what would be removed without changing anything
+        #[clap(long)]
+        dry_run: bool,
+    },
+    /// Run a health check on a workspace: existence, workspace.json, database entry, SSH and metadata.
+    /// Exits with 0 if healthy, 11 on warnings and 12 on failures
+    Check {
+        /// The workspace ID or full path to check
+        #[clap(name = "id-or-path")]
+        id_or_path: String,
+        
+        /// Profile path (uses default if not specified)
+        #[clap(short, long)]
+        profile: Option<String>,
+    },
+    /// Check the existence of all workspaces, including SSH remotes
+    CheckAll {
+        /// Profile path (uses default if not specified)
+        #[clap(short, long)]
+        profile: Option<String>,
+        
+        /// Timeout in seconds for the checks against each remote host
+        #[clap(long, default_value = "10")]
+        timeout: u64,
+    },
+    /// Generate .desktop launcher files for each workspace (Linux)
+    DesktopFiles {
+        /// Output directory (defaults to ~/.local/share/applications/vscode-workspaces/)
+        #[clap(short, long)]
+        output_dir: Option<String>,
+        
+        /// Run update-desktop-database after generating the files
+        #[clap(long)]
+        update_database: bool,
+    },
+    /// Delete all workspaces whose path no longer exists
+    Clean {
+        /// Profile path (uses default if not specified)
+        #[clap(short, long)]
+        profile: Option<String>,
+        
+        /// Show what would be removed without changing anything
+        #[clap(long)]
+        dry_run: bool,
+        
+        /// Ask before deleting each missing workspace (y: yes, n: no, a: yes to all, q: quit)
+        #[clap(short, long, conflicts_with = "dry_run")]
+        interactive: bool,
+    },
+    /// Show statistics about the workspaces, including remote workspaces per host
+    Stats {
+        /// Profile path (uses default if not specified)
+        #[clap(short, long)]
+        profile: Option<String>,
+        
+        /// Output format (text or json)
+        #[clap(short, long, default_value = "text")]
+        format: String,
+    },
+    /// Compare the workspaces of two profiles
+    Diff {
+        /// First profile path
+        profile_a: String,
+        
+        /// Second profile path
+        profile_b: String,
+        
+        /// Output format (table or json)
+        #[clap(short, long, default_value = "table")]
+        format: String,
+    },
+    /// Print the JSON Schema of exported workspaces
+    Schema,
+    /// Show workspaces recently opened through this tool
+    Log {
+        /// Number of most recent entries to show
+        #[clap(long, default_value = "20")]
+        last: usize,
+        
+        /// Only show entries since this time (e.g. 2h, 7d, 2024-05-01 or an RFC 3339 timestamp)
+        #[clap(long)]
+        since: Option<String>,
+    },
+    /// List detected VSCode installations and named profiles with their workspace counts
+    ProfileList,
+    /// Manage the configuration file
+    Config {
+        #[clap(subcommand)]
+        action: ConfigAction,
+    },
+    /// Show the version with build metadata, for bug reports
+    Version {
+        /// Output as JSON
+        #[clap(long)]
+        json: bool,
+    },
 }
 
-#[tokio::main]
-async fn main() -> Result<()> {
-    // Initialize logger
-    env_logger::init();
+/// Configuration subcommands
+#[derive(Subcommand, Debug)]
+enum ConfigAction {
+    /// Write a commented default config file
+    Init {
+        /// Overwrite an existing config file
+        #[clap(long)]
+        force: bool,
+    },
+}
+
+/// Find a workspace by ID, falling back to a lookup by path
+fn find_workspace(profile_path: &str, id_or_path: &str) -> Result<Option<workspaces::Workspace>> {
+    // Paths can't be storage IDs, so skip straight to the full scan for them
+    let looks_like_path = id_or_path.contains(['/', '\\', ':']);
+    if !looks_like_path {
+        if let Some(workspace) = workspaces::get_workspace_by_id(profile_path, id_or_path)? {
+            return Ok(Some(workspace));
+        }
+    }
     
+    Ok(workspaces::get_workspaces(profile_path, None)?
+        .into_iter()
+        .find(|ws| ws.path == id_or_path))
+}
+
+/// Resolve the profile path: subcommand flag, then global flag or VSCODE_WE_PROFILE, then config, then the default profile
+fn resolve_profile_path(profile: Option<&String>, args: &Args, config: &Config) -> Result<String> {
+    let path = match profile.or(args.profile.as_ref()).or(config.default_profile.as_ref()) {
+        // Paths from the config file and quoted arguments may still start with `~`
+        Some(path) => workspaces::expand_tilde(path)?,
+        None => workspaces::get_default_profile_path()?,
+    };
+    
+    if path != workspaces::ZED_PROFILE_NAME && !std::path::Path::new(&path).exists() {
+        return Err(WorkspaceError::ProfileNotFound(path).into());
+    }
+    Ok(path)
+}
+
+/// Process exit code reported to the shell, see `EXIT_CODES_HELP`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct WorkspaceExitCode(u8);
+
+impl WorkspaceExitCode {
+    const SUCCESS: Self = Self(0);
+    const GENERAL_ERROR: Self = Self(1);
+    const WORKSPACE_NOT_FOUND: Self = Self(2);
+    const PROFILE_NOT_FOUND: Self = Self(3);
+    const PERMISSION_DENIED: Self = Self(4);
+    const DATABASE_LOCKED: Self = Self(5);
+    const DRY_RUN: Self = Self(10);
+    const CHECK_WARNINGS: Self = Self(11);
+    const CHECK_FAILED: Self = Self(12);
+    
+    /// Map an error to the exit code of its failure reason
+    fn from_error(error: &anyhow::Error) -> Self {
+        match error_code(error) {
+            "NOT_FOUND" => Self::WORKSPACE_NOT_FOUND,
+            "PROFILE_NOT_FOUND" => Self::PROFILE_NOT_FOUND,
+            "PERMISSION_DENIED" => Self::PERMISSION_DENIED,
+            "DATABASE_LOCKED" => Self::DATABASE_LOCKED,
+            _ => Self::GENERAL_ERROR,
+        }
+    }
+    
+    /// Exit code for a dry run, depending on whether anything would have changed
+    fn dry_run(would_change: bool) -> Self {
+        if would_change { Self::DRY_RUN } else { Self::SUCCESS }
+    }
+}
+
+impl Termination for WorkspaceExitCode {
+    fn report(self) -> ExitCode {
+        ExitCode::from(self.0)
+    }
+}
+
+/// Classify an error into a stable code for scripts, matching `WorkspaceError::code`
+fn error_code(error: &anyhow::Error) -> &'static str {
+    for cause in error.chain() {
+        if let Some(e) = cause.downcast_ref::<WorkspaceError>() {
+            return e.code();
+        }
+        if let Some(e) = cause.downcast_ref::<rusqlite::Error>() {
+            return match e.sqlite_error_code() {
+                Some(rusqlite::ErrorCode::DatabaseBusy | rusqlite::ErrorCode::DatabaseLocked) => "DATABASE_LOCKED",
+                Some(rusqlite::ErrorCode::PermissionDenied | rusqlite::ErrorCode::ReadOnly) => "PERMISSION_DENIED",
+                _ => "DATABASE_ERROR",
+            };
+        }
+        if let Some(e) = cause.downcast_ref::<std::io::Error>() {
+            return match e.kind() {
+                std::io::ErrorKind::NotFound => "FILE_NOT_FOUND",
+                std::io::ErrorKind::PermissionDenied => "PERMISSION_DENIED",
+                _ => "IO_ERROR",
+            };
+        }
+    }
+    "ERROR"
+}
+
+/// Print a terminal error to stderr, as JSON if requested
+fn report_error(error: &anyhow::Error, json: bool) {
+    if json {
+        let value = serde_json::json!({
+            "error": format!("{:#}", error),
+            "code": error_code(error),
+        });
+        eprintln!("{}", value);
+    } else {
+        eprintln!("Error: {:?}", error);
+    }
+}
+
+#[tokio::main]
+async fn main() -> WorkspaceExitCode {
     // Parse command line arguments
     let args = Args::parse();
     
-    // Set NO_COLOR environment variable if --no-color flag is used
-    if args.no_color {
+    // Initialize logger, --verbose enables debug output of the workspace loading unless RUST_LOG is set
+    let mut logger = env_logger::Builder::from_default_env();
+    if args.verbose && std::env::var_os("RUST_LOG").is_none() {
+        logger.filter_module("vscode_workspaces_editor::workspaces", log::LevelFilter::Debug);
+    }
+    logger.init();
+    let json_errors = args.json_errors;
+    
+    match run(args).await {
+        Ok(code) => code,
+        Err(e) => {
+            report_error(&e, json_errors);
+            WorkspaceExitCode::from_error(&e)
+        }
+    }
+}
+
+async fn run(args: Args) -> Result<WorkspaceExitCode> {
+    // Load the config file (CLI arguments take priority)
+    let mut config = Config::load().unwrap_or_else(|e| {
+        eprintln!("Warning: {:#}. Using default configuration.", e);
+        Config::default()
+    });
+    
+    // Merge JetBrains recent projects into the loaded workspaces
+    workspaces::set_include_jetbrains(args.include_jetbrains);
+    
+    // Let zoxide know about opened workspaces when it's installed
+    cli::set_update_zoxide(args.update_zoxide.unwrap_or_else(|| workspaces::is_command_available("zoxide")));
+    
+    // Only print the requested output for scripted use
+    cli::set_quiet(args.quiet);
+    
+    // Skip database backups for scripted use
+    if args.no_backup {
+        workspaces::set_backups_enabled(false);
+    }
+    
+    // Scan extra storage directories from the config and the command line
+    let mut extra_storage_dirs = config.extra_storage_dirs.clone();
+    extra_storage_dirs.extend(args.extra_storage.iter().cloned());
+    workspaces::set_extra_storage_dirs(extra_storage_dirs);
+    
+    // The --editor flag overrides the configured editor command
+    if let Some(editor) = &args.editor {
+        config.editor_command = editor.clone();
+    }
+    
+    // Set NO_COLOR environment variable if --no-color flag or config option is used
+    if args.no_color || config.no_color {
         std::env::set_var("NO_COLOR", "1");
     }
 
     // Handle subcommands if present
     if let Some(cmd) = &args.command {
         match cmd {
-            Commands::List { format } => {
-                // Get profile path (default or user-provided)
-                let profile_path = match &args.profile {
-                    Some(path) => path.clone(),
-                    None => workspaces::get_default_profile_path()?,
+            Commands::List { format, sort, fzf, dmenu, picker, archived, filter, count } => {
+                // Load workspaces, from the archive store if requested
+                let mut workspaces = if *archived {
+                    workspaces::list_archived_workspaces()?
+                } else {
+                    let profile_path = resolve_profile_path(None, &args, &config)?;
+                    workspaces::get_workspaces(&profile_path, None)?
                 };
                 
-                // Load workspaces
-                let mut workspaces = workspaces::get_workspaces(&profile_path)?;
+                if let Some(query) = filter {
+                    workspaces = workspaces::filter_workspaces(&mut workspaces, query)
+                        .into_iter()
+                        .cloned()
+                        .collect();
+                }
+                
+                // Just the number, for scripts
+                if *count {
+                    println!("{}", workspaces.len());
+                    return Ok(WorkspaceExitCode::SUCCESS);
+                }
                 
                 // Parse workspace paths for all workspaces
                 for workspace in &mut workspaces {
                     let _ = workspace.parse_path();
                 }
                 
+                // Apply the requested sort order
+                sort.unwrap_or(config.default_sort).sort(&mut workspaces);
+                
+                // Let the user pick a workspace with fzf and open it
+                if *fzf {
+                    if let Some(path) = cli::pick_with_fzf(&workspaces)? {
+                        cli::open_workspace(&path, &config.editor_command)?;
+                    }
+                    return Ok(WorkspaceExitCode::SUCCESS);
+                }
+                
+                // Let the user pick a workspace with dmenu/rofi and open it
+                if *dmenu {
+                    if let Some(path) = cli::pick_with_dmenu(&workspaces, picker)? {
+                        cli::open_workspace(&path, &config.editor_command)?;
+                    }
+                    return Ok(WorkspaceExitCode::SUCCESS);
+                }
+                
                 // Output the list
+                let format = format.as_deref().unwrap_or(&config.default_list_format);
                 cli::list_workspaces(&workspaces, format)?;
-                return Ok(());
+                return Ok(WorkspaceExitCode::SUCCESS);
             },
             Commands::Parse { path } => {
                 // Parse the given workspace path
@@ -118,31 +607,21 @@ async fn main() -> Result<()> {
                         println!("Failed to parse workspace path: {}", e);
                     }
                 }
-                return Ok(());
+                return Ok(WorkspaceExitCode::SUCCESS);
             },
-            Commands::Diagnose { id_or_path, profile } => {
+            Commands::Diagnose { id_or_path, profile, show_db_keys } => {
                 // Get profile path (default or user-provided)
-                let profile_path = match profile {
-                    Some(path) => path.clone(),
-                    None => match &args.profile {
-                        Some(path) => path.clone(),
-                        None => workspaces::get_default_profile_path()?,
-                    },
-                };
+                let profile_path = resolve_profile_path(profile.as_ref(), &args, &config)?;
                 
                 println!("Diagnosing workspace with profile: {}", profile_path);
                 println!("Looking for workspace by ID or path: {}", id_or_path);
                 
-                // Load workspaces
-                let mut workspaces = workspaces::get_workspaces(&profile_path)?;
-                
-                // Try to find the workspace by ID or path
-                let id_or_path_str = id_or_path.as_str();
-                let matching_workspace = workspaces.iter_mut().find(|ws| 
-                    ws.id == id_or_path_str || ws.path == id_or_path_str
-                );
+                // Try to find the workspace by ID or path, keeping the entries that were skipped while loading
+                let (all_workspaces, warnings) = workspaces::get_workspaces_with_warnings(&profile_path, None)?;
+                let mut matching_workspace = all_workspaces.into_iter()
+                    .find(|ws| ws.id == *id_or_path || ws.path == *id_or_path);
                 
-                if let Some(workspace) = matching_workspace {
+                if let Some(workspace) = matching_workspace.as_mut() {
                     println!("\nFound workspace:");
                     println!("ID: {}", workspace.id);
                     println!("Path: {}", workspace.path);
@@ -184,6 +663,8 @@ async fn main() -> Result<()> {
                                 println!("Database: {}", key),
                             workspaces::WorkspaceSource::Zed(channel) =>
                                 println!("Zed({})", channel),
+                            workspaces::WorkspaceSource::Profile(name) =>
+                                println!("Profile: {}", name),
                         }
                     }
                 } else {
@@ -215,67 +696,361 @@ async fn main() -> Result<()> {
                     }
                 }
                 
-                return Ok(());
+                // Entries of the profile's databases that were skipped while loading
+                if !warnings.is_empty() {
+                    println!("\n{} workspace entries could not be parsed:", warnings.len());
+                    for warning in &warnings {
+                        println!("{} entry {}: {}", warning.source, warning.entry_index, warning.reason);
+                        println!("  {}", warning.entry_preview);
+                    }
+                }
+                
+                if *show_db_keys {
+                    for db in ["User/state.vscdb", "User/globalStorage/state.vscdb"] {
+                        let db_path = format!("{}/{}", profile_path, db);
+                        if !std::path::Path::new(&db_path).exists() {
+                            continue;
+                        }
+                        match workspaces::list_all_database_keys(&db_path) {
+                            Ok(keys) => {
+                                println!("\nKeys in {} ({}):", db, keys.len());
+                                for (key, size) in keys {
+                                    println!("{:>10}  {}", size, key);
+                                }
+                            },
+                            Err(e) => println!("\nFailed to read keys of {}: {}", db, e),
+                        }
+                    }
+                }
+                
+                return Ok(WorkspaceExitCode::SUCCESS);
             },
-            Commands::Open { id_or_path, profile, use_parsed } => {
+            Commands::Open { id_or_path, stdin, profile, use_parsed } => {
                 // Get profile path (default or user-provided)
-                let profile_path = match profile {
-                    Some(path) => path.clone(),
-                    None => match &args.profile {
-                        Some(path) => path.clone(),
-                        None => workspaces::get_default_profile_path()?,
+                let profile_path = resolve_profile_path(profile.as_ref(), &args, &config)?;
+                
+                let id_or_path = match id_or_path {
+                    Some(id_or_path) => id_or_path.clone(),
+                    None if *stdin => {
+                        let mut line = String::new();
+                        std::io::stdin().read_line(&mut line)
+                            .context("Failed to read workspace path from stdin")?;
+                        let line = line.trim().to_string();
+                        if line.is_empty() {
+                            anyhow::bail!("No workspace path given on stdin");
+                        }
+                        line
                     },
+                    None => anyhow::bail!("Either an ID/path or --stdin is required"),
                 };
-                
-                // Load workspaces
-                let mut workspaces = workspaces::get_workspaces(&profile_path)?;
+                let id_or_path = &id_or_path;
                 
                 // Try to find the workspace by ID or path
-                let id_or_path_str = id_or_path.as_str();
-                let matching_workspace = workspaces.iter_mut().find(|ws| 
-                    ws.id == id_or_path_str || ws.path == id_or_path_str
-                );
+                let mut matching_workspace = find_workspace(&profile_path, id_or_path)?;
                 
-                if let Some(workspace) = matching_workspace {
-                    println!("Found workspace: {} ({})", 
+                if let Some(workspace) = matching_workspace.as_mut() {
+                    status!("Found workspace: {} ({})", 
                         workspace.name.as_deref().unwrap_or(&workspace.id), 
                         workspace.path
                     );
                     
                     // Parse the workspace path to get the original path
-                    let parsed_info = workspace.parse_path();
-                    
-                    if let Some(info) = parsed_info {
-                        // Determine which path to use
-                        let path_to_use = if *use_parsed {
-                            &workspace.path
-                        } else {
-                            &info.original_path
-                        };
-                        
-                        println!("Opening workspace with {}path: {}", 
-                            if *use_parsed { "parsed " } else { "original " },
-                            path_to_use
-                        );
-                        
-                        // Open the workspace
-                        cli::open_workspace(path_to_use)?;
-                    } else {
+                    if workspace.parse_path().is_none() {
                         println!("Failed to parse workspace path. Using provided path.");
-                        cli::open_workspace(&workspace.path)?;
                     }
+                    
+                    // Determine which path to use. The parsed path of a remote workspace
+                    // lacks the remote authority, so remote workspaces keep their URI.
+                    let use_parsed = *use_parsed && workspace.is_local_parsed();
+                    let path_to_use = if use_parsed {
+                        workspace.get_display_path()
+                    } else {
+                        workspace.get_original_uri()
+                    };
+                    
+                    status!("Opening workspace with {}path: {}", 
+                        if use_parsed { "parsed " } else { "original " },
+                        path_to_use
+                    );
+                    
+                    // Open the workspace
+                    cli::open_workspace(path_to_use, &config.editor_command)?;
                 } else {
                     // If not found in stored workspaces, try to use the path directly
-                    println!("No workspace found with ID/path: {}. Trying to open directly.", id_or_path);
-                    cli::open_workspace(id_or_path)?;
+                    status!("No workspace found with ID/path: {}. Trying to open directly.", id_or_path);
+                    cli::open_workspace(id_or_path, &config.editor_command)?;
+                }
+                
+                return Ok(WorkspaceExitCode::SUCCESS);
+            },
+            Commands::Session { id_or_path, profile, tool } => {
+                // Get profile path (default or user-provided)
+                let profile_path = resolve_profile_path(profile.as_ref(), &args, &config)?;
+                
+                // Fall back to the given path if it's a folder that isn't a known workspace
+                let folder = match find_workspace(&profile_path, id_or_path)? {
+                    Some(mut workspace) => {
+                        if workspace.is_remote() {
+                            anyhow::bail!("Sessions are only supported for local workspaces: {}", workspace.path);
+                        }
+                        cli::local_folder(workspace.get_display_path())
+                    },
+                    None if std::path::Path::new(id_or_path).is_dir() => id_or_path.clone(),
+                    None => return Err(WorkspaceError::NotFound(id_or_path.clone()).into()),
+                };
+                
+                cli::open_session(*tool, &cli::session_name(&folder), &folder)?;
+                return Ok(WorkspaceExitCode::SUCCESS);
+            },
+            Commands::Delete { id_or_path, profile, dry_run } => {
+                // Get profile path (default or user-provided)
+                let profile_path = resolve_profile_path(profile.as_ref(), &args, &config)?;
+                
+                // Load workspaces
+                let workspaces = workspaces::get_workspaces(&profile_path, None)?;
+                
+                // Try to find the workspace by ID or path
+                let id_or_path_str = id_or_path.as_str();
+                let matching: Vec<workspaces::Workspace> = workspaces.into_iter()
+                    .filter(|ws| ws.id == id_or_path_str || ws.path == id_or_path_str)
+                    .collect();
+                
+                if matching.is_empty() {
+                    return Err(WorkspaceError::NotFound(id_or_path.clone()).into());
                 }
                 
-                return Ok(());
+                let deleted = cli::delete_workspaces(&profile_path, &matching, *dry_run)?;
+                if *dry_run {
+                    return Ok(WorkspaceExitCode::DRY_RUN);
+                }
+                return Ok(if deleted {
+                    WorkspaceExitCode::SUCCESS
+                } else {
+                    WorkspaceExitCode::GENERAL_ERROR
+                });
+            },
+            Commands::Rename { id_or_path, name, profile } => {
+                // Get profile path (default or user-provided)
+                let profile_path = resolve_profile_path(profile.as_ref(), &args, &config)?;
+                
+                workspaces::rename_workspace(&profile_path, id_or_path, name)?;
+                if name.is_empty() {
+                    status!("Removed the name of workspace {}", id_or_path);
+                } else {
+                    status!("Renamed workspace {} to {}", id_or_path, name);
+                }
+                
+                return Ok(WorkspaceExitCode::SUCCESS);
+            },
+            Commands::Archive { id_or_path, profile } => {
+                // Get profile path (default or user-provided)
+                let profile_path = resolve_profile_path(profile.as_ref(), &args, &config)?;
+                
+                let workspace = find_workspace(&profile_path, id_or_path)?
+                    .ok_or_else(|| WorkspaceError::NotFound(id_or_path.clone()))?;
+                workspaces::archive_workspaces(&profile_path, std::slice::from_ref(&workspace))?;
+                status!("Archived workspace: {}", workspace.path);
+                
+                return Ok(WorkspaceExitCode::SUCCESS);
+            },
+            Commands::Restore { ids_or_paths, profile } => {
+                // Get profile path (default or user-provided)
+                let profile_path = resolve_profile_path(profile.as_ref(), &args, &config)?;
+                
+                let restored = workspaces::restore_workspaces(&profile_path, ids_or_paths)?;
+                status!("Restored {} workspaces to {}", restored, profile_path);
+                
+                return Ok(WorkspaceExitCode::SUCCESS);
+            },
+            Commands::Snapshot { snapshot_path, profile } => {
+                // Get profile path (default or user-provided)
+                let profile_path = resolve_profile_path(profile.as_ref(), &args, &config)?;
+                
+                workspaces::snapshot_workspaces(&profile_path, snapshot_path)?;
+                status!("Saved snapshot of {} to {}", profile_path, snapshot_path);
+                
+                return Ok(WorkspaceExitCode::SUCCESS);
+            },
+            Commands::RestoreSnapshot { snapshot_file, profile } => {
+                // Get profile path (default or user-provided)
+                let profile_path = resolve_profile_path(profile.as_ref(), &args, &config)?;
+                
+                let report = workspaces::restore_snapshot(&profile_path, snapshot_file)?;
+                for (path, reason) in &report.failed {
+                    eprintln!("Could not restore {}: {}", path, reason);
+                }
+                status!(
+                    "Restored {} workspaces ({} already present, {} failed)",
+                    report.restored.len(), report.skipped.len(), report.failed.len()
+                );
+                
+                return Ok(if report.failed.is_empty() {
+                    WorkspaceExitCode::SUCCESS
+                } else {
+                    WorkspaceExitCode::GENERAL_ERROR
+                });
+            },
+            Commands::Env { id_or_path, profile } => {
+                // Get profile path (default or user-provided)
+                let profile_path = resolve_profile_path(profile.as_ref(), &args, &config)?;
+                
+                let mut workspace = find_workspace(&profile_path, id_or_path)?
+                    .ok_or_else(|| WorkspaceError::NotFound(id_or_path.clone()))?;
+                cli::print_env_vars(&workspace.to_shell_env_vars());
+                
+                return Ok(WorkspaceExitCode::SUCCESS);
+            },
+            Commands::BatchDelete { from_file, stdin, profile, dry_run } => {
+                // Get profile path (default or user-provided)
+                let profile_path = resolve_profile_path(profile.as_ref(), &args, &config)?;
+                
+                let input = match from_file {
+                    Some(file) => std::fs::read_to_string(file)
+                        .with_context(|| format!("Failed to read IDs from {}", file))?,
+                    None if *stdin => std::io::read_to_string(std::io::stdin())
+                        .context("Failed to read IDs from stdin")?,
+                    None => anyhow::bail!("Either --from-file or --stdin is required"),
+                };
+                let ids: Vec<&str> = input.lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty())
+                    .collect();
+                
+                // Load workspaces
+                let workspaces = workspaces::get_workspaces(&profile_path, None)?;
+                
+                let (deleted, failed) = cli::batch_delete_workspaces(&profile_path, &workspaces, &ids, *dry_run)?;
+                if *dry_run {
+                    return Ok(WorkspaceExitCode::dry_run(deleted > 0));
+                }
+                return Ok(if failed == 0 {
+                    WorkspaceExitCode::SUCCESS
+                } else {
+                    WorkspaceExitCode::GENERAL_ERROR
+                });
+            },
+            Commands::Check { id_or_path, profile } => {
+                // Get profile path (default or user-provided)
+                let profile_path = resolve_profile_path(profile.as_ref(), &args, &config)?;
+                
+                // Try to find the workspace by ID or path
+                let workspace = find_workspace(&profile_path, id_or_path)?
+                    .ok_or_else(|| WorkspaceError::NotFound(id_or_path.clone()))?;
+                let results = cli::health_check(&profile_path, &workspace).await;
+                return Ok(match cli::print_health_report(&workspace, &results) {
+                    cli::CheckStatus::Fail => WorkspaceExitCode::CHECK_FAILED,
+                    cli::CheckStatus::Warn => WorkspaceExitCode::CHECK_WARNINGS,
+                    _ => WorkspaceExitCode::SUCCESS,
+                });
+            },
+            Commands::CheckAll { profile, timeout } => {
+                // Get profile path (default or user-provided)
+                let profile_path = resolve_profile_path(profile.as_ref(), &args, &config)?;
+                
+                // Load workspaces
+                let mut workspaces = workspaces::get_workspaces(&profile_path, None)?;
+                
+                cli::check_all_workspaces(&mut workspaces, std::time::Duration::from_secs(*timeout)).await?;
+                return Ok(WorkspaceExitCode::SUCCESS);
+            },
+            Commands::DesktopFiles { output_dir, update_database } => {
+                // Get profile path (default or user-provided)
+                let profile_path = resolve_profile_path(None, &args, &config)?;
+                
+                let output_dir = match output_dir {
+                    Some(dir) => dir.clone(),
+                    None => cli::default_desktop_files_dir()?.to_string_lossy().to_string(),
+                };
+                
+                // Load workspaces
+                let workspaces = workspaces::get_workspaces(&profile_path, None)?;
+                
+                let count = cli::generate_desktop_files(&workspaces, &output_dir)?;
+                status!("Generated {} desktop files in {}", count, output_dir);
+                
+                if *update_database {
+                    cli::update_desktop_database(&output_dir)?;
+                }
+                
+                return Ok(WorkspaceExitCode::SUCCESS);
+            },
+            Commands::Clean { profile, dry_run, interactive } => {
+                // Get profile path (default or user-provided)
+                let profile_path = resolve_profile_path(profile.as_ref(), &args, &config)?;
+                
+                // Load workspaces
+                let workspaces = workspaces::get_workspaces(&profile_path, None)?;
+                
+                let (missing, deleted) = cli::clean_missing_workspaces(&profile_path, &workspaces, *dry_run, *interactive)?;
+                if *dry_run {
+                    return Ok(WorkspaceExitCode::dry_run(missing > 0));
+                }
+                return Ok(if deleted {
+                    WorkspaceExitCode::SUCCESS
+                } else {
+                    WorkspaceExitCode::GENERAL_ERROR
+                });
+            },
+            Commands::Stats { profile, format } => {
+                // Get profile path (default or user-provided)
+                let profile_path = resolve_profile_path(profile.as_ref(), &args, &config)?;
+                
+                let mut workspaces = workspaces::get_workspaces(&profile_path, None)?;
+                let stats = workspaces::workspace_statistics(&mut workspaces);
+                cli::print_stats(&stats, format)?;
+                return Ok(WorkspaceExitCode::SUCCESS);
+            },
+            Commands::Diff { profile_a, profile_b, format } => {
+                let comparison = workspaces::diff_profiles(profile_a, profile_b)?;
+                cli::print_comparison(&comparison, profile_a, profile_b, format)?;
+                return Ok(WorkspaceExitCode::SUCCESS);
+            },
+            Commands::Log { last, since } => {
+                cli::print_activity_log(*last, since.as_deref())?;
+                return Ok(WorkspaceExitCode::SUCCESS);
+            },
+            Commands::ProfileList => {
+                cli::list_profiles()?;
+                return Ok(WorkspaceExitCode::SUCCESS);
+            },
+            Commands::Schema => {
+                let schema = schemars::schema_for!(Vec<workspaces::Workspace>);
+                println!("{}", serde_json::to_string_pretty(&schema)?);
+                return Ok(WorkspaceExitCode::SUCCESS);
+            },
+            Commands::Config { action } => {
+                match action {
+                    ConfigAction::Init { force } => {
+                        let path = config::config_path()?;
+                        config::write_default_config(&path, *force)?;
+                        status!("Wrote default config to {}", path.display());
+                    }
+                }
+                return Ok(WorkspaceExitCode::SUCCESS);
+            }
+            Commands::Version { json } => {
+                if *json {
+                    let info = serde_json::json!({
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "commit": env!("BUILD_GIT_HASH"),
+                        "build_timestamp": env!("BUILD_TIMESTAMP"),
+                        "target": env!("BUILD_TARGET"),
+                        "dependencies": {
+                            "ratatui": env!("BUILD_DEP_RATATUI"),
+                            "rusqlite": env!("BUILD_DEP_RUSQLITE"),
+                        },
+                    });
+                    println!("{}", serde_json::to_string_pretty(&info)?);
+                } else {
+                    println!("{} {}", env!("CARGO_PKG_NAME"), LONG_VERSION);
+                }
+                return Ok(WorkspaceExitCode::SUCCESS);
             }
         }
     }
     
-    tui::run(args.profile.as_deref())?;
+    let profile_path = args.profile.as_deref().or(config.default_profile.as_deref());
+    tui::run(profile_path, &config)?;
     
-    Ok(())
+    Ok(WorkspaceExitCode::SUCCESS)
 }
@@ -1,9 +1,15 @@
-use crate::workspaces::{self, Workspace, workspace_exists};
-use crate::tui::models::{InputMode, UiConfig};
+use crate::workspaces::{self, Workspace, SortOrder, NamedProfile, WorkspaceGroup, WorkspaceParseWarning, ProgressUpdate};
+use crate::tui::models::{InputMode, ListRow, UiConfig, ViewMode};
 use anyhow::Result;
-use std::collections::HashSet;
+use rayon::prelude::*;
+use std::cell::Cell;
+use std::collections::{HashMap, HashSet};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
 use std::time::{Duration, Instant};
 
+/// Workspaces loaded in the background, with the database entries that could not be read
+type LoadResult = Result<(Vec<Workspace>, Vec<WorkspaceParseWarning>)>;
+
 /// Main application state
 pub struct App {
     /// VSCode profile path
@@ -36,12 +42,77 @@ pub struct App {
     pub autocomplete_suggestion: Option<String>,
     /// Position where the autocomplete suggestion starts
     pub autocomplete_start_position: usize,
+    /// Directory entries cycled through by Tab in the profile path input
+    pub path_completions: Vec<String>,
     /// UI configuration settings
     pub ui_config: UiConfig,
-    /// Known VSCode profile paths
+    /// Known VSCode profile paths, including named profiles
     pub known_profile_paths: Vec<String>,
+    /// Named profiles found in the known profiles, used to label them
+    pub named_profiles: Vec<NamedProfile>,
     /// Selected profile path index
     pub selected_profile_index: Option<usize>,
+    /// Order in which workspaces are listed
+    pub sort_order: SortOrder,
+    /// Interval between automatic reloads (disabled when None)
+    pub auto_reload_interval: Option<Duration>,
+    /// Time of the last workspace load
+    pub last_reload: Instant,
+    /// Editor command used to open workspaces
+    pub editor_command: String,
+    /// Whether each workspace exists, indexed like `workspaces` (empty until computed)
+    pub existence_cache: Vec<bool>,
+    /// Real path of each local workspace opened through a symlink, indexed like `workspaces`
+    /// (empty until the background check finishes)
+    pub symlink_targets: Vec<Option<String>>,
+    /// Whether workspaces are being loaded in the background
+    pub loading: bool,
+    /// Progress message shown while loading
+    pub loading_message: String,
+    /// Fraction of the loading stages completed (0.0 to 1.0)
+    pub loading_progress: f64,
+    /// Layout of the workspace list
+    pub view_mode: ViewMode,
+    /// Hosts whose group is collapsed in the grouped view
+    pub collapsed_hosts: HashSet<String>,
+    /// Filtered workspaces grouped by host (only in the grouped view)
+    pub host_groups: Vec<(String, Vec<usize>)>,
+    /// Host whose group header is selected; no workspace is selected meanwhile
+    pub selected_group: Option<String>,
+    /// Whether the search was opened with Ctrl+P, so Enter opens the selected workspace
+    pub quick_open: bool,
+    /// Workspace notes, keyed by normalized workspace path
+    pub notes: HashMap<String, String>,
+    /// Lines of the note being edited
+    pub notes_buffer: Vec<String>,
+    /// Lines of the workspace dump shown in inspect mode
+    pub inspect_lines: Vec<String>,
+    /// First line shown in inspect mode
+    pub inspect_scroll: usize,
+    /// Whether Tab moved the focus to the details pane, so Shift+↑/↓ scroll it
+    pub details_focused: bool,
+    /// First row shown in the details pane
+    pub details_scroll_offset: usize,
+    /// Largest useful details scroll offset, updated when the details pane is drawn
+    pub details_max_scroll: Cell<usize>,
+    /// User-defined workspace groups
+    pub groups: Vec<WorkspaceGroup>,
+    /// Number of workspaces per remote host
+    pub host_counts: HashMap<String, usize>,
+    /// Number of workspaces per source kind (storage, database, zed)
+    pub source_breakdown: HashMap<String, usize>,
+    /// Database entries that could not be read during the last load
+    pub parse_warnings: Vec<WorkspaceParseWarning>,
+    /// Whether the parse warnings are shown instead of the details pane
+    pub show_parse_warnings: bool,
+    /// Number of ticks since startup, used to animate the loading spinner
+    pub tick_count: usize,
+    /// Receiver for the result of the background loader
+    load_receiver: Option<Receiver<LoadResult>>,
+    /// Receiver for the loading stages reported by the background loader
+    progress_receiver: Option<Receiver<ProgressUpdate>>,
+    /// Receiver for the background existence check of the loaded workspaces
+    existence_receiver: Option<Receiver<Vec<BackgroundCheck>>>,
 }
 
 impl App {
@@ -52,8 +123,25 @@ impl App {
             None => workspaces::get_default_profile_path()?
         };
         
-        // Get known VSCode paths
-        let known_profile_paths = workspaces::get_known_vscode_paths();
+        // Get known VSCode paths, each followed by its named profiles
+        let mut known_profile_paths = Vec::new();
+        let mut named_profiles = Vec::new();
+        for path in workspaces::get_known_vscode_paths() {
+            let named = workspaces::get_named_profiles(&path).unwrap_or_default();
+            known_profile_paths.push(path);
+            known_profile_paths.extend(named.iter().map(|profile| profile.path.clone()));
+            named_profiles.extend(named);
+        }
+        
+        let notes = workspaces::load_notes().unwrap_or_else(|e| {
+            log::warn!("Failed to load workspace notes: {}", e);
+            HashMap::new()
+        });
+        
+        let groups = workspaces::list_groups().unwrap_or_else(|e| {
+            log::warn!("Failed to load workspace groups: {}", e);
+            Vec::new()
+        });
         
         Ok(Self {
             profile_path,
@@ -71,15 +159,152 @@ impl App {
             is_autocomplete_active: false,
             autocomplete_suggestion: None,
             autocomplete_start_position: 0,
+            path_completions: Vec::new(),
             ui_config: UiConfig::default(),
             known_profile_paths,
+            named_profiles,
             selected_profile_index: None,
+            sort_order: SortOrder::default(),
+            auto_reload_interval: None,
+            last_reload: Instant::now(),
+            editor_command: "code".to_string(),
+            existence_cache: Vec::new(),
+            symlink_targets: Vec::new(),
+            loading: false,
+            loading_message: String::new(),
+            loading_progress: 0.0,
+            view_mode: ViewMode::default(),
+            collapsed_hosts: HashSet::new(),
+            host_groups: Vec::new(),
+            selected_group: None,
+            quick_open: false,
+            notes,
+            notes_buffer: Vec::new(),
+            inspect_lines: Vec::new(),
+            inspect_scroll: 0,
+            details_focused: false,
+            details_scroll_offset: 0,
+            details_max_scroll: Cell::new(0),
+            groups,
+            host_counts: HashMap::new(),
+            source_breakdown: HashMap::new(),
+            parse_warnings: Vec::new(),
+            show_parse_warnings: false,
+            tick_count: 0,
+            load_receiver: None,
+            progress_receiver: None,
+            existence_receiver: None,
         })
     }
 
     /// Load workspaces from the profile
     pub fn load_workspaces(&mut self) -> Result<()> {
-        self.workspaces = workspaces::get_workspaces(&self.profile_path)?;
+        // Drop any background load in progress, it may be for another profile
+        self.load_receiver = None;
+        self.progress_receiver = None;
+        self.loading = false;
+        
+        let (workspaces, warnings) = workspaces::get_workspaces_with_warnings(&self.profile_path, None)?;
+        self.set_workspaces(workspaces);
+        self.report_parse_warnings(warnings);
+        Ok(())
+    }
+
+    /// Start loading workspaces in the background; results are picked up by `poll_loading`.
+    /// Falls back to loading synchronously when no tokio runtime is available.
+    pub fn start_loading(&mut self) -> Result<()> {
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            return self.load_workspaces();
+        };
+        
+        let (sender, receiver) = mpsc::channel();
+        let (progress_sender, progress_receiver) = mpsc::channel();
+        let profile_path = self.profile_path.clone();
+        handle.spawn(async move {
+            let result = tokio::task::spawn_blocking(move || {
+                workspaces::get_workspaces_with_warnings(&profile_path, Some(progress_sender))
+            })
+            .await
+            .unwrap_or_else(|e| Err(anyhow::anyhow!("Workspace loader failed: {}", e)));
+            let _ = sender.send(result);
+        });
+        
+        self.loading = true;
+        self.loading_message = "Loading workspaces...".to_string();
+        self.loading_progress = 0.0;
+        self.load_receiver = Some(receiver);
+        self.progress_receiver = Some(progress_receiver);
+        Ok(())
+    }
+
+    /// Handle pending events from the background loader
+    pub fn poll_loading(&mut self) {
+        if let Some(progress_receiver) = &self.progress_receiver {
+            while let Ok(update) = progress_receiver.try_recv() {
+                let (progress, message) = match update {
+                    ProgressUpdate::StartingStorage => (0.0, "Reading workspace storage...".to_string()),
+                    ProgressUpdate::StorageLoaded(count) => (0.3, format!("Read workspace storage, {} found", count)),
+                    ProgressUpdate::StartingDatabase => (0.3, "Reading state databases...".to_string()),
+                    ProgressUpdate::DatabaseLoaded(count) => (0.7, format!("Read state databases, {} found", count)),
+                    ProgressUpdate::Processing(count) => (0.8, format!("Processing {} workspaces...", count)),
+                    ProgressUpdate::Done(count) => (1.0, format!("Loaded {} workspaces", count)),
+                };
+                self.loading_progress = progress;
+                self.loading_message = message;
+            }
+        }
+        
+        let Some(receiver) = &self.load_receiver else {
+            return;
+        };
+        
+        let result = match receiver.try_recv() {
+            Ok(result) => result,
+            Err(TryRecvError::Empty) => return,
+            Err(TryRecvError::Disconnected) => Err(anyhow::anyhow!("Workspace loader stopped unexpectedly")),
+        };
+        
+        self.loading = false;
+        self.load_receiver = None;
+        self.progress_receiver = None;
+        match result {
+            Ok((workspaces, warnings)) => {
+                self.set_workspaces(workspaces);
+                self.set_status(&format!("Loaded {} workspaces", self.workspaces.len()), Duration::from_secs(3));
+                self.report_parse_warnings(warnings);
+            }
+            Err(e) => self.set_status(&format!("Error: {}", e), Duration::from_secs(5)),
+        }
+    }
+
+    /// Keep the database entries skipped by the last load and tell the user about them
+    fn report_parse_warnings(&mut self, warnings: Vec<WorkspaceParseWarning>) {
+        self.parse_warnings = warnings;
+        if self.parse_warnings.is_empty() {
+            self.show_parse_warnings = false;
+        } else {
+            self.set_status(
+                &format!("{} workspace entries could not be parsed (press W for details)", self.parse_warnings.len()),
+                Duration::from_secs(10),
+            );
+        }
+    }
+
+    /// Show or hide the parse warnings in place of the details pane
+    pub fn toggle_parse_warnings(&mut self) {
+        if self.parse_warnings.is_empty() {
+            self.show_parse_warnings = false;
+            self.set_status("All workspace entries were parsed", Duration::from_secs(2));
+        } else {
+            self.show_parse_warnings = !self.show_parse_warnings;
+        }
+    }
+
+    /// Replace the workspace list with freshly loaded workspaces
+    fn set_workspaces(&mut self, workspaces: Vec<Workspace>) {
+        self.workspaces = workspaces;
+        self.sort_order.sort(&mut self.workspaces);
+        self.last_reload = Instant::now();
         
         // Parse workspace paths to extract additional info
         for workspace in &mut self.workspaces {
@@ -87,12 +312,381 @@ impl App {
                 let _ = workspace.parse_path();
             }
         }
+        self.existence_cache.clear();
+        workspaces::ExistenceCache::shared().clear();
+        self.symlink_targets.clear();
+        self.start_existence_check();
+        self.host_counts = workspaces::workspace_count_by_host(&self.workspaces);
+        self.source_breakdown = self.count_sources();
         
         self.apply_filter();
         if !self.filtered_workspaces.is_empty() && self.selected_workspace_index.is_none() {
             self.selected_workspace_index = Some(0);
         }
-        Ok(())
+    }
+
+    /// Check which workspaces exist, resolve symlinks and read the Git branch and extension
+    /// recommendations of local folders in the background; results are picked up by `poll_existence`.
+    /// Without a tokio runtime the existence check is left to `ensure_existence_cache`.
+    fn start_existence_check(&mut self) {
+        self.existence_receiver = None;
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        
+        let (sender, receiver) = mpsc::channel();
+        let workspaces = self.workspaces.clone();
+        handle.spawn_blocking(move || {
+            let _ = sender.send(background_check(&workspaces));
+        });
+        self.existence_receiver = Some(receiver);
+    }
+
+    /// Pick up the result of the background existence check
+    pub fn poll_existence(&mut self) {
+        let Some(receiver) = &self.existence_receiver else {
+            return;
+        };
+        
+        match receiver.try_recv() {
+            Ok(checks) => {
+                self.existence_receiver = None;
+                // Ignore results for a list that has been replaced since
+                if checks.len() == self.workspaces.len() {
+                    self.existence_cache = checks.iter().map(|check| check.exists).collect();
+                    self.symlink_targets = checks.iter().map(|check| check.symlink_target.clone()).collect();
+                    for (workspace, check) in self.workspaces.iter_mut().zip(checks) {
+                        workspace.git_branch = check.git_branch;
+                        workspace.recommended_extensions = check.recommended_extensions;
+                    }
+                    if [":existing:", ":branch:", ":extension:"].iter().any(|filter| self.search_query.contains(filter)) {
+                        self.refilter_keeping_selection();
+                    }
+                }
+            }
+            Err(TryRecvError::Empty) => {}
+            Err(TryRecvError::Disconnected) => self.existence_receiver = None,
+        }
+    }
+
+    /// Compute the existence cache now if the background check hasn't delivered it yet
+    fn ensure_existence_cache(&mut self) {
+        if self.existence_cache.len() != self.workspaces.len() {
+            self.existence_cache = check_existence(&self.workspaces);
+            self.existence_receiver = None;
+        }
+    }
+
+    /// Whether the workspace at the given index exists, using the existence cache when available
+    pub fn workspace_exists_at(&self, index: usize) -> bool {
+        match (self.existence_cache.get(index), self.workspaces.get(index)) {
+            (Some(&exists), Some(workspace)) if !workspace.exists_checked => exists,
+            (_, Some(workspace)) => workspaces::workspace_exists(workspace),
+            _ => false,
+        }
+    }
+
+    /// Real path of the workspace at the given index if it was opened through a symlink
+    pub fn symlink_target_at(&self, index: usize) -> Option<&str> {
+        self.symlink_targets.get(index).and_then(|target| target.as_deref())
+    }
+
+    /// Re-apply the filter, keeping the selected workspace selected if it is still listed
+    fn refilter_keeping_selection(&mut self) {
+        let selected = self.selected_workspace_index
+            .and_then(|idx| self.filtered_workspaces.get(idx))
+            .copied();
+        self.apply_filter();
+        if let Some(pos) = selected.and_then(|selected| self.filtered_workspaces.iter().position(|&idx| idx == selected)) {
+            self.selected_workspace_index = Some(pos);
+        }
+    }
+
+    /// Reload workspaces if the auto-reload interval has elapsed, keeping the current selection
+    pub fn auto_reload_if_due(&mut self) {
+        let Some(interval) = self.auto_reload_interval else {
+            return;
+        };
+        
+        // Don't reload while a dialog is using the filtered list
+        if !matches!(self.input_mode, InputMode::Normal | InputMode::Searching) {
+            return;
+        }
+        
+        if self.loading || self.last_reload.elapsed() < interval {
+            return;
+        }
+        
+        let selected_id = self.selected_workspace_index
+            .and_then(|idx| self.filtered_workspaces.get(idx))
+            .map(|&idx| self.workspaces[idx].id.clone());
+        
+        if let Err(e) = self.load_workspaces() {
+            self.set_status(&format!("Auto-reload failed: {}", e), Duration::from_secs(3));
+            return;
+        }
+        
+        // Restore the previous selection if the workspace is still listed
+        if let Some(id) = selected_id {
+            if let Some(pos) = self.filtered_workspaces.iter()
+                .position(|&idx| self.workspaces[idx].id == id)
+            {
+                self.selected_workspace_index = Some(pos);
+            }
+        }
+    }
+
+    /// Check whether the selected remote workspace exists over SSH and report it in the status line
+    pub fn check_selected_remote(&mut self) {
+        let Some(workspace_idx) = self.selected_workspace_index
+            .and_then(|idx| self.filtered_workspaces.get(idx))
+            .copied()
+        else {
+            return;
+        };
+        let Some(workspace) = self.workspaces.get(workspace_idx).cloned() else {
+            return;
+        };
+        
+        if !workspace.clone().is_remote() {
+            self.set_status("Selected workspace is not remote", Duration::from_secs(2));
+            return;
+        }
+        
+        // The TUI is synchronous, so block on the check (bounded by the SSH timeout)
+        let check = workspaces::check_remote_workspace_exists(&workspace);
+        let result = match tokio::runtime::Handle::try_current() {
+            Ok(handle) => tokio::task::block_in_place(|| handle.block_on(check)),
+            Err(_) => match tokio::runtime::Builder::new_current_thread().enable_all().build() {
+                Ok(runtime) => runtime.block_on(check),
+                Err(_) => None,
+            },
+        };
+        
+        // Remember the result so the list shows the remote as missing or present
+        if let (Some(exists), Some(checked)) = (result, self.workspaces.get_mut(workspace_idx)) {
+            checked.exists = exists;
+            checked.exists_checked = true;
+        }
+        
+        let message = match result {
+            Some(true) => format!("Remote workspace exists: {}", workspace.path),
+            Some(false) => format!("Remote workspace is missing: {}", workspace.path),
+            None => format!("Could not check remote workspace over SSH: {}", workspace.path),
+        };
+        self.set_status(&message, Duration::from_secs(5));
+    }
+
+    /// Open the selected workspace with the editor command
+    pub fn open_selected_workspace(&mut self) {
+        let Some(workspace) = self.selected_workspace_index
+            .and_then(|idx| self.filtered_workspaces.get(idx))
+            .and_then(|&idx| self.workspaces.get(idx))
+        else {
+            self.set_status("No workspace selected", Duration::from_secs(2));
+            return;
+        };
+        
+        let path = workspace.get_original_uri().to_string();
+        match crate::cli::spawn_editor(&self.editor_command, &path) {
+            Ok(()) => {
+                if let Err(e) = workspaces::record_workspace_open(&path, workspaces::ActivitySource::Tui) {
+                    log::warn!("Failed to record workspace open: {}", e);
+                }
+                self.set_status(&format!("Opening {} with {}", path, self.editor_command), Duration::from_secs(3));
+            }
+            Err(e) => self.set_status(&format!("Error: {}", e), Duration::from_secs(5)),
+        }
+    }
+
+    /// Get the selected workspace, if any
+    pub fn selected_workspace(&self) -> Option<&Workspace> {
+        self.selected_workspace_index
+            .and_then(|idx| self.filtered_workspaces.get(idx))
+            .and_then(|&idx| self.workspaces.get(idx))
+    }
+
+    /// Get the note attached to a workspace
+    pub fn workspace_note(&self, workspace: &Workspace) -> Option<&str> {
+        self.notes.get(&workspaces::note_key(&workspace.path)).map(String::as_str)
+    }
+
+    /// Start editing the note of the selected workspace
+    pub fn start_edit_notes(&mut self) {
+        let Some(workspace) = self.selected_workspace() else {
+            self.set_status("No workspace selected", Duration::from_secs(2));
+            return;
+        };
+        
+        let mut lines: Vec<String> = self.workspace_note(workspace)
+            .map(|note| note.lines().map(String::from).collect())
+            .unwrap_or_default();
+        if lines.is_empty() {
+            lines.push(String::new());
+        }
+        
+        self.notes_buffer = lines;
+        self.input_mode = InputMode::EditNotes;
+    }
+
+    /// Show all fields of the selected workspace as pretty JSON in a full-screen view
+    pub fn start_inspect(&mut self) {
+        let Some(workspace) = self.selected_workspace() else {
+            self.set_status("No workspace selected", Duration::from_secs(2));
+            return;
+        };
+        
+        let mut json = match serde_json::to_value(workspace) {
+            Ok(json) => json,
+            Err(e) => {
+                self.set_status(&format!("Error: {}", e), Duration::from_secs(5));
+                return;
+            }
+        };
+        if let Some(object) = json.as_object_mut() {
+            let last_used = chrono::DateTime::<chrono::Utc>::from_timestamp(workspace.last_used / 1000, 0)
+                .filter(|_| workspace.last_used > 0)
+                .map(|dt| dt.format("%Y-%m-%d %H:%M:%S UTC").to_string());
+            object.insert("last_used_human".to_string(), serde_json::json!(last_used));
+            // The complete parsed path, including fields the export leaves out
+            if let Some(info) = &workspace.parsed_info {
+                object.insert("parsed_info".to_string(), serde_json::to_value(info).unwrap_or_default());
+            }
+        }
+        
+        let text = serde_json::to_string_pretty(&json).unwrap_or_default();
+        self.inspect_lines = text.lines().map(String::from).collect();
+        self.inspect_scroll = 0;
+        self.input_mode = InputMode::Inspect;
+    }
+
+    /// Scroll the inspect view by the given number of lines
+    pub fn scroll_inspect(&mut self, delta: isize) {
+        let max = self.inspect_lines.len().saturating_sub(1);
+        self.inspect_scroll = self.inspect_scroll.saturating_add_signed(delta).min(max);
+    }
+
+    /// Move the focus between the workspace list and the details pane
+    pub fn toggle_details_focus(&mut self) {
+        self.details_focused = !self.details_focused;
+    }
+
+    /// Scroll the details pane by the given number of rows, stopping at the ends
+    pub fn scroll_details(&mut self, delta: isize) {
+        self.details_scroll_offset = self.details_scroll_offset
+            .saturating_add_signed(delta)
+            .min(self.details_max_scroll.get());
+    }
+
+    /// Save the edited note of the selected workspace and return to normal mode
+    pub fn save_notes(&mut self) {
+        self.input_mode = InputMode::Normal;
+        let Some(path) = self.selected_workspace().map(|workspace| workspace.path.clone()) else {
+            return;
+        };
+        
+        let note = self.notes_buffer.join("\n").trim_end().to_string();
+        match workspaces::set_workspace_note(&path, &note) {
+            Ok(()) => {
+                if note.is_empty() {
+                    self.notes.remove(&workspaces::note_key(&path));
+                    self.set_status("Removed note", Duration::from_secs(2));
+                } else {
+                    self.notes.insert(workspaces::note_key(&path), note);
+                    self.set_status("Saved note", Duration::from_secs(2));
+                }
+            }
+            Err(e) => self.set_status(&format!("Error: {}", e), Duration::from_secs(5)),
+        }
+    }
+
+    /// Start entering a new name for the selected workspace, starting from its current name
+    pub fn start_rename(&mut self) {
+        let Some(name) = self.selected_workspace().map(|workspace| workspace.name.clone().unwrap_or_default()) else {
+            self.set_status("No workspace selected", Duration::from_secs(2));
+            return;
+        };
+        
+        self.cursor_position = name.len();
+        self.input_buffer = name;
+        self.input_mode = InputMode::Rename;
+    }
+
+    /// Rename the selected workspace in the profile; an empty name removes its name
+    pub fn rename_selected(&mut self, name: &str) {
+        self.input_mode = InputMode::Normal;
+        let Some(index) = self.selected_workspace_index.and_then(|idx| self.filtered_workspaces.get(idx).copied()) else {
+            self.set_status("No workspace selected", Duration::from_secs(2));
+            return;
+        };
+        
+        let name = name.trim();
+        let path = self.workspaces[index].path.clone();
+        match workspaces::rename_workspace(&self.profile_path, &path, name) {
+            Ok(()) => {
+                self.workspaces[index].name = (!name.is_empty()).then(|| name.to_string());
+                self.refilter_keeping_selection();
+                if name.is_empty() {
+                    self.set_status(&format!("Removed the name of {}", path), Duration::from_secs(3));
+                } else {
+                    self.set_status(&format!("Renamed {} to {}", path, name), Duration::from_secs(3));
+                }
+            }
+            Err(e) => self.set_status(&format!("Error: {}", e), Duration::from_secs(5)),
+        }
+    }
+
+    /// Get the remote host of a workspace and how many other workspaces are on it
+    pub fn workspaces_on_same_host(&self, workspace: &Workspace) -> Option<(String, usize)> {
+        let host = workspace.parsed_info.as_ref()?.remote_host.clone()?;
+        let count = self.host_counts.get(&host).copied().unwrap_or(1);
+        Some((host, count.saturating_sub(1)))
+    }
+
+    /// Show only the workspaces on the remote host of the selected workspace
+    pub fn filter_by_selected_host(&mut self) {
+        let Some(host) = self.selected_workspace()
+            .and_then(|workspace| workspace.parsed_info.as_ref())
+            .and_then(|info| info.remote_host.clone()) else {
+            self.set_status("Selected workspace is not remote", Duration::from_secs(2));
+            return;
+        };
+        
+        self.search_query = format!(":host:{}", host);
+        self.apply_filter();
+        self.set_status(&format!("Showing workspaces on {}", host), Duration::from_secs(2));
+    }
+
+    /// Get the names of the groups a workspace belongs to
+    pub fn workspace_groups(&self, workspace: &Workspace) -> Vec<&str> {
+        self.groups.iter()
+            .filter(|group| group.contains(&workspace.path))
+            .map(|group| group.name.as_str())
+            .collect()
+    }
+
+    /// Add the selected workspace to a group, creating the group if needed
+    pub fn add_selected_to_group(&mut self, name: &str) {
+        self.input_mode = InputMode::Normal;
+        let Some(path) = self.selected_workspace().map(|workspace| workspace.path.clone()) else {
+            self.set_status("No workspace selected", Duration::from_secs(2));
+            return;
+        };
+        
+        let name = name.trim();
+        let result = match self.groups.iter().find(|group| group.name.eq_ignore_ascii_case(name)) {
+            Some(group) => workspaces::add_to_group(&group.name, &path),
+            None => workspaces::create_group(name, None)
+                .and_then(|_| workspaces::add_to_group(name, &path)),
+        };
+        
+        match result.and_then(|_| workspaces::list_groups()) {
+            Ok(groups) => {
+                self.groups = groups;
+                self.set_status(&format!("Added {} to group {}", path, name), Duration::from_secs(3));
+            }
+            Err(e) => self.set_status(&format!("Error: {}", e), Duration::from_secs(5)),
+        }
     }
 
     /// Set a status message with an expiration time
@@ -121,6 +715,13 @@ impl App {
         let mut type_filter: Option<&str> = None;
         let mut tag_filter: Option<&str> = None;
         let mut existence_filter: Option<bool> = None;
+        let mut id_filter: Option<&str> = None;
+        let mut min_files_filter: Option<usize> = None;
+        let mut group_filter: Option<&str> = None;
+        let mut host_filter: Option<&str> = None;
+        let mut branch_filter: Option<&str> = None;
+        let mut source_filter: Option<&str> = None;
+        let mut extension_filter: Option<&str> = None;
         let mut regular_keywords: Vec<&str> = Vec::new();
 
         for word in words {
@@ -150,12 +751,45 @@ impl App {
                     existence_filter = Some(false);
                 }
             }
+            // Check for :id: filter
+            else if word.starts_with(":id:") {
+                id_filter = Some(word.trim_start_matches(":id:"));
+            }
+            // Check for :host: filter
+            else if word.starts_with(":host:") {
+                host_filter = Some(word.trim_start_matches(":host:"));
+            }
+            // Check for :branch: filter
+            else if word.starts_with(":branch:") {
+                branch_filter = Some(word.trim_start_matches(":branch:"));
+            }
+            // Check for :source: filter
+            else if word.starts_with(":source:") {
+                source_filter = Some(word.trim_start_matches(":source:"));
+            }
+            // Check for :extension: filter
+            else if word.starts_with(":extension:") {
+                extension_filter = Some(word.trim_start_matches(":extension:"));
+            }
+            // Check for :group: filter
+            else if word.starts_with(":group:") {
+                group_filter = Some(word.trim_start_matches(":group:"));
+            }
+            // Check for :minfiles: filter
+            else if word.starts_with(":minfiles:") {
+                min_files_filter = word.trim_start_matches(":minfiles:").parse().ok();
+            }
             // Regular keyword search
             else if !word.is_empty() {
                 regular_keywords.push(word);
             }
         }
 
+        // Existence is checked for all workspaces at once, in parallel
+        if existence_filter.is_some() {
+            self.ensure_existence_cache();
+        }
+
         // Apply filters to create indices of matching workspaces
         for (i, workspace) in self.workspaces.iter_mut().enumerate() {
             let mut include = true;
@@ -205,10 +839,86 @@ impl App {
                 }
             }
 
+            // ID prefix filter (the query is already lowercase)
+            if include {
+                if let Some(prefix) = id_filter {
+                    if !workspace.id.to_lowercase().starts_with(prefix) {
+                        include = false;
+                    }
+                }
+            }
+
+            // Remote host filter (the query is already lowercase)
+            if include {
+                if let Some(host) = host_filter {
+                    let on_host = workspace.parse_path()
+                        .and_then(|info| info.remote_host.as_ref())
+                        .is_some_and(|remote_host| remote_host.to_lowercase().contains(host));
+                    if !on_host {
+                        include = false;
+                    }
+                }
+            }
+
+            // Git branch filter (the query is already lowercase)
+            if include {
+                if let Some(branch) = branch_filter {
+                    let on_branch = workspace.git_branch.as_ref()
+                        .is_some_and(|git_branch| git_branch.to_lowercase().contains(branch));
+                    if !on_branch {
+                        include = false;
+                    }
+                }
+            }
+
+            // Recommended extension filter (the query is already lowercase)
+            if include {
+                if let Some(extension) = extension_filter {
+                    let recommends = workspace.recommended_extensions.iter()
+                        .any(|id| id.to_lowercase().contains(extension));
+                    if !recommends {
+                        include = false;
+                    }
+                }
+            }
+
+            // Source filter, e.g. :source:profile:work (the query is already lowercase)
+            if include {
+                if let Some(source) = source_filter {
+                    if !source.is_empty() && !workspace.sources.iter().any(|s| s.matches_filter(source)) {
+                        include = false;
+                    }
+                }
+            }
+
+            // Group filter (the query is already lowercase)
+            if include {
+                if let Some(name) = group_filter {
+                    let in_group = self.groups.iter()
+                        .any(|group| group.name.to_lowercase() == name && group.contains(&workspace.path));
+                    if !in_group {
+                        include = false;
+                    }
+                }
+            }
+
+            // Minimum number of files opened from the editor history
+            if include {
+                if let Some(min_files) = min_files_filter {
+                    if workspace.recent_file_count < min_files {
+                        include = false;
+                    }
+                }
+            }
+
             // Existence filter
             if include && existence_filter.is_some() {
                 if let Some(exists) = existence_filter {
-                    let path_exists = workspace_exists(workspace);
+                    let path_exists = if workspace.exists_checked {
+                        workspace.exists
+                    } else {
+                        self.existence_cache[i]
+                    };
                     if path_exists != exists {
                         include = false;
                     }
@@ -223,7 +933,11 @@ impl App {
                     .map(|info| info.tags.join(" ").to_lowercase())
                     .unwrap_or_default();
                 
-                let combined_info = format!("{} {} {}", label, path, tags);
+                let note = self.notes.get(&workspaces::note_key(&workspace.path))
+                    .map(|note| note.to_lowercase())
+                    .unwrap_or_default();
+                
+                let combined_info = format!("{} {} {} {}", label, path, tags, note);
                 
                 if !regular_keywords.iter().all(|keyword| combined_info.contains(keyword)) {
                     include = false;
@@ -235,10 +949,150 @@ impl App {
             }
         }
 
-        self.filtered_workspaces = filtered_workspaces;
+        self.filtered_workspaces = self.group_by_host(filtered_workspaces);
+        self.selected_group = None;
         self.selected_workspace_index = self.filtered_workspaces.first().map(|_| 0);
     }
 
+    /// Group the filtered workspaces by host in the grouped view, local workspaces first.
+    /// Returns the workspaces to list, leaving out those in collapsed groups.
+    fn group_by_host(&mut self, filtered_workspaces: Vec<usize>) -> Vec<usize> {
+        self.host_groups.clear();
+        if self.view_mode == ViewMode::Flat {
+            return filtered_workspaces;
+        }
+        
+        for idx in filtered_workspaces {
+            let host = host_group_name(&self.workspaces[idx]);
+            match self.host_groups.iter_mut().find(|(name, _)| *name == host) {
+                Some((_, members)) => members.push(idx),
+                None => self.host_groups.push((host, vec![idx])),
+            }
+        }
+        self.host_groups.sort_by(|(a, _), (b, _)| {
+            (a != LOCAL_GROUP).cmp(&(b != LOCAL_GROUP)).then_with(|| a.to_lowercase().cmp(&b.to_lowercase()))
+        });
+        
+        self.host_groups.iter()
+            .filter(|(host, _)| !self.collapsed_hosts.contains(host))
+            .flat_map(|(_, members)| members.iter().copied())
+            .collect()
+    }
+
+    /// Rows of the workspace list: group headers and workspaces in the grouped view,
+    /// only workspaces otherwise
+    pub fn list_rows(&self) -> Vec<ListRow> {
+        if self.host_groups.is_empty() || self.input_mode == InputMode::ConfirmDelete {
+            return (0..self.filtered_workspaces.len()).map(ListRow::Workspace).collect();
+        }
+        
+        let mut rows = Vec::new();
+        let mut position = 0;
+        for (host, members) in &self.host_groups {
+            let collapsed = self.collapsed_hosts.contains(host);
+            rows.push(ListRow::Group { host: host.clone(), count: members.len(), collapsed });
+            if !collapsed {
+                rows.extend((position..position + members.len()).map(ListRow::Workspace));
+                position += members.len();
+            }
+        }
+        rows
+    }
+
+    /// Position of the selected group header or workspace in `list_rows`
+    pub fn selected_row(&self, rows: &[ListRow]) -> Option<usize> {
+        rows.iter().position(|row| match (row, &self.selected_group) {
+            (ListRow::Group { host, .. }, Some(selected)) => host == selected,
+            (ListRow::Workspace(pos), None) => Some(*pos) == self.selected_workspace_index,
+            _ => false,
+        })
+    }
+
+    /// Move the selection up or down by the given number of rows, stopping at the ends
+    pub fn move_selection(&mut self, delta: isize) {
+        let rows = self.list_rows();
+        if rows.is_empty() {
+            return;
+        }
+        
+        let target = match self.selected_row(&rows) {
+            Some(row) => (row as isize + delta).clamp(0, rows.len() as isize - 1) as usize,
+            None => 0,
+        };
+        self.select_row(&rows, target);
+    }
+
+    /// Count the workspaces found in each kind of source.
+    ///
+    /// A workspace found in several sources is counted once for each of them.
+    fn count_sources(&self) -> HashMap<String, usize> {
+        let mut counts = HashMap::new();
+        for workspace in &self.workspaces {
+            let kinds: HashSet<&str> = workspace.sources.iter().map(|source| source.kind()).collect();
+            for kind in kinds {
+                *counts.entry(kind.to_string()).or_insert(0) += 1;
+            }
+        }
+        counts
+    }
+
+    /// Select the first item of the list
+    pub fn select_first(&mut self) {
+        let rows = self.list_rows();
+        if !rows.is_empty() {
+            self.select_row(&rows, 0);
+        }
+    }
+
+    /// Select the last item of the list
+    pub fn select_last(&mut self) {
+        let rows = self.list_rows();
+        if !rows.is_empty() {
+            self.select_row(&rows, rows.len() - 1);
+        }
+    }
+
+    /// Select a row of the list, either a host group or a workspace
+    fn select_row(&mut self, rows: &[ListRow], target: usize) {
+        self.details_scroll_offset = 0;
+        match &rows[target] {
+            ListRow::Group { host, .. } => {
+                self.selected_group = Some(host.clone());
+                self.selected_workspace_index = None;
+            }
+            ListRow::Workspace(pos) => {
+                self.selected_group = None;
+                self.selected_workspace_index = Some(*pos);
+            }
+        }
+    }
+
+    /// Switch between the flat and the grouped by host view
+    pub fn toggle_view_mode(&mut self) {
+        self.view_mode = match self.view_mode {
+            ViewMode::Flat => ViewMode::GroupedByHost,
+            ViewMode::GroupedByHost => ViewMode::Flat,
+        };
+        self.refilter_keeping_selection();
+    }
+
+    /// Expand or collapse the group whose header is selected; returns false if no header is selected
+    pub fn toggle_selected_group(&mut self) -> bool {
+        let Some(host) = self.selected_group.clone() else {
+            return false;
+        };
+        
+        if !self.collapsed_hosts.remove(&host) {
+            self.collapsed_hosts.insert(host.clone());
+        }
+        self.apply_filter();
+        
+        // Keep the header selected
+        self.selected_group = Some(host);
+        self.selected_workspace_index = None;
+        true
+    }
+
     /// Toggle mark/unmark the currently selected workspace
     pub fn toggle_mark_selected(&mut self) {
         if let Some(selected_idx) = self.selected_workspace_index {
@@ -271,7 +1125,7 @@ impl App {
             .collect();
             
         // Delete the workspaces
-        let result = workspaces::delete_workspace(&self.profile_path, &workspaces_to_delete);
+        let result = workspaces::delete_workspace(&self.profile_path, &workspaces_to_delete, false);
         
         // Clear the marked set
         self.marked_for_deletion.clear();
@@ -303,6 +1157,41 @@ impl App {
         Ok(())
     }
 
+    /// Preview the deletion of marked workspaces without changing anything
+    pub fn preview_marked_deletion(&mut self) {
+        if self.marked_for_deletion.is_empty() {
+            self.set_status("No workspaces marked for deletion", Duration::from_secs(2));
+            return;
+        }
+
+        let workspaces_to_delete: Vec<Workspace> = self.workspaces.iter()
+            .filter(|w| self.marked_for_deletion.contains(&w.id))
+            .cloned()
+            .collect();
+        let source_count: usize = workspaces_to_delete.iter()
+            .map(|w| w.sources.len())
+            .sum();
+
+        match workspaces::delete_workspace(&self.profile_path, &workspaces_to_delete, true) {
+            Ok(_) => {
+                self.set_status(
+                    &format!(
+                        "Dry run: {} workspaces ({} sources) would be deleted, nothing was changed",
+                        workspaces_to_delete.len(),
+                        source_count
+                    ),
+                    Duration::from_secs(5),
+                );
+            },
+            Err(e) => {
+                self.set_status(
+                    &format!("Error previewing deletion: {}", e),
+                    Duration::from_secs(5),
+                );
+            }
+        }
+    }
+
     /// Cancel the deletion of marked workspaces
     #[allow(dead_code)]
     pub fn cancel_deletion(&mut self) {
@@ -405,4 +1294,53 @@ impl App {
         // Return the current word up to the cursor
         (&self.input_buffer[word_start..self.cursor_position], word_start)
     }
-} 
\ No newline at end of file
+} 
+
+/// Check whether each workspace exists, in parallel
+fn check_existence(workspaces: &[Workspace]) -> Vec<bool> {
+    workspaces.par_iter().map(workspaces::workspace_exists).collect()
+}
+
+/// Result of the background filesystem checks of one workspace
+struct BackgroundCheck {
+    exists: bool,
+    symlink_target: Option<String>,
+    git_branch: Option<String>,
+    recommended_extensions: Vec<String>,
+}
+
+/// Check whether each workspace exists, resolve symlinks of local workspaces and read the
+/// Git branch and extension recommendations of local folders, in parallel
+fn background_check(workspaces: &[Workspace]) -> Vec<BackgroundCheck> {
+    workspaces.par_iter().map(|workspace| {
+        let exists = workspaces::workspace_exists(workspace);
+        let symlink_target = if workspace.is_local_parsed() {
+            workspaces::resolve_symlinks(&workspace.path).ok()
+                .filter(|resolved| *resolved != workspaces::normalize_path(&workspace.path))
+        } else {
+            None
+        };
+        // Only local folders, reading .git/HEAD on a remote would be too slow
+        let local_folder = workspace.is_local_parsed()
+            && workspace.parsed_info.as_ref().is_some_and(|info| info.workspace_type == workspaces::parser::WorkspaceType::Folder);
+        if !local_folder {
+            return BackgroundCheck { exists, symlink_target, git_branch: None, recommended_extensions: Vec::new() };
+        }
+        BackgroundCheck {
+            exists,
+            symlink_target,
+            git_branch: workspaces::extract_git_branch(&workspace.path),
+            recommended_extensions: workspaces::read_workspace_extension_recommendations(&workspace.path),
+        }
+    }).collect()
+}
+
+/// Group name used for local workspaces in the grouped view
+const LOCAL_GROUP: &str = "Local";
+
+/// Name of the host group a workspace belongs to in the grouped view
+fn host_group_name(workspace: &Workspace) -> String {
+    workspace.parsed_info.as_ref()
+        .and_then(|info| info.remote_host.clone().or_else(|| info.remote_authority.clone()))
+        .unwrap_or_else(|| LOCAL_GROUP.to_string())
+}
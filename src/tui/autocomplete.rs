@@ -1,8 +1,9 @@
 use crate::tui::app::App;
+use crate::workspaces::expand_tilde;
 use std::time::Duration;
 
 /// Available filter modifiers
-pub const FILTER_MODIFIERS: [&str; 5] = [":existing:", ":remote:", ":type:", ":path:", ":tag:"];
+pub const FILTER_MODIFIERS: [&str; 12] = [":existing:", ":remote:", ":type:", ":path:", ":tag:", ":id:", ":minfiles:", ":group:", ":host:", ":branch:", ":source:", ":extension:"];
 
 /// Available values for the :existing: filter
 pub const EXISTING_VALUES: [&str; 2] = ["yes", "no"];
@@ -13,6 +14,9 @@ pub const REMOTE_VALUES: [&str; 2] = ["yes", "no"];
 /// Available values for the :type: filter
 pub const TYPE_VALUES: [&str; 3] = ["folder", "file", "workspace"];
 
+/// Available values for the :source: filter (`profile:<name>` narrows down to a named profile)
+pub const SOURCE_VALUES: [&str; 4] = ["storage", "database", "zed", "profile:"];
+
 /// Process Tab key press for autocomplete
 pub fn process_tab_key(app: &mut App) {
     let (current_word, position_before_word) = app.get_current_word();
@@ -107,7 +111,8 @@ fn process_value_autocomplete(app: &mut App, modifier: &str) {
         ":existing:" => &EXISTING_VALUES[..],
         ":remote:" => &REMOTE_VALUES[..],
         ":type:" => &TYPE_VALUES[..],
-        ":path:" | ":tag:" => {
+        ":source:" => &SOURCE_VALUES[..],
+        ":path:" | ":tag:" | ":id:" | ":minfiles:" | ":group:" | ":host:" | ":branch:" | ":extension:" => {
             // These don't have predetermined values
             app.set_status(
                 &format!("Type a value for {}", modifier),
@@ -246,12 +251,85 @@ fn process_value_autocomplete(app: &mut App, modifier: &str) {
     app.apply_filter();
 }
 
+/// Process Tab key press in the profile path input: complete the directory name before
+/// the cursor, cycling through the matches on repeated presses
+pub fn process_path_tab_key(app: &mut App) {
+    if app.is_autocomplete_active && !app.path_completions.is_empty() {
+        app.current_autocomplete_index = (app.current_autocomplete_index + 1) % app.path_completions.len();
+        let current_match = app.path_completions[app.current_autocomplete_index].clone();
+        app.input_buffer
+            .replace_range(app.autocomplete_start_position..app.cursor_position, &current_match);
+        app.cursor_position = app.autocomplete_start_position + current_match.len();
+        app.set_status(
+            &format!(
+                "Selected {} ({}/{}) - Press Tab again to cycle",
+                current_match,
+                app.current_autocomplete_index + 1,
+                app.path_completions.len()
+            ),
+            Duration::from_secs(3),
+        );
+        return;
+    }
+
+    // Split the input before the cursor into the directory to list and the typed prefix
+    let before_cursor = &app.input_buffer[..app.cursor_position];
+    let name_start = before_cursor.rfind('/').map(|pos| pos + 1).unwrap_or(0);
+    let (dir, prefix) = before_cursor.split_at(name_start);
+    let prefix = prefix.to_string();
+    let dir = match dir {
+        "" => ".".to_string(),
+        dir => expand_tilde(dir).unwrap_or_else(|_| dir.to_string()),
+    };
+
+    let mut matches: Vec<String> = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries
+            .flatten()
+            .filter(|entry| entry.path().is_dir())
+            .filter_map(|entry| entry.file_name().to_str().map(|name| name.to_string()))
+            // Hidden directories are only offered once the user types the dot
+            .filter(|name| name.starts_with(&prefix) && (prefix.starts_with('.') || !name.starts_with('.')))
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    matches.sort();
+
+    app.autocomplete_start_position = name_start;
+    app.current_autocomplete_index = 0;
+    match matches.len() {
+        0 => {
+            app.is_autocomplete_active = false;
+            app.path_completions.clear();
+            app.set_status("No matching directory", Duration::from_secs(2));
+        }
+        1 => {
+            // A single match is completed with its slash so Tab continues inside it
+            let completed = format!("{}/", matches[0]);
+            app.input_buffer.replace_range(name_start..app.cursor_position, &completed);
+            app.cursor_position = name_start + completed.len();
+            app.is_autocomplete_active = false;
+            app.path_completions.clear();
+        }
+        count => {
+            app.input_buffer.replace_range(name_start..app.cursor_position, &matches[0]);
+            app.cursor_position = name_start + matches[0].len();
+            app.set_status(
+                &format!("Selected {} (1/{}) - Press Tab again to cycle", matches[0], count),
+                Duration::from_secs(3),
+            );
+            app.path_completions = matches;
+            app.is_autocomplete_active = true;
+        }
+    }
+}
+
 /// Commit the current autocomplete selection
 pub fn commit_autocomplete(app: &mut App) {
     // Mark autocomplete as no longer active and clear suggestion
     app.is_autocomplete_active = false;
     app.autocomplete_suggestion = None;
     app.current_autocomplete_index = 0;
+    app.path_completions.clear();
 }
 
 /// Show help text for the selected filter
@@ -281,6 +359,43 @@ fn show_filter_help(app: &mut App, filter: &str) {
         ":tag:" => {
             app.set_status("Filter by tag - :tag:value", Duration::from_secs(3));
         }
+        ":id:" => {
+            app.set_status("Filter by workspace ID prefix - :id:prefix", Duration::from_secs(3));
+        }
+        ":group:" => {
+            let names: Vec<&str> = app.groups.iter().map(|group| group.name.as_str()).collect();
+            let message = if names.is_empty() {
+                "Filter by group - :group:name (no groups yet, press g to add a workspace to one)".to_string()
+            } else {
+                format!("Filter values for :group: - {}", names.join(", "))
+            };
+            app.set_status(&message, Duration::from_secs(3));
+        }
+        ":host:" => {
+            let mut hosts: Vec<&str> = app.host_counts.keys().map(|host| host.as_str()).collect();
+            hosts.sort();
+            let message = if hosts.is_empty() {
+                "Filter by remote host - :host:name (no remote workspaces)".to_string()
+            } else {
+                format!("Filter values for :host: - {}", hosts.join(", "))
+            };
+            app.set_status(&message, Duration::from_secs(3));
+        }
+        ":branch:" => {
+            app.set_status("Filter by Git branch - :branch:name", Duration::from_secs(3));
+        }
+        ":extension:" => {
+            app.set_status("Filter by recommended extension - :extension:publisher.name", Duration::from_secs(3));
+        }
+        ":source:" => {
+            app.set_status(
+                "Filter values for :source: - storage, database, zed, profile:name",
+                Duration::from_secs(3),
+            );
+        }
+        ":minfiles:" => {
+            app.set_status("Filter by minimum number of files opened - :minfiles:n", Duration::from_secs(3));
+        }
         _ => {
             app.set_status(
                 &format!("Type a value for {}", filter),
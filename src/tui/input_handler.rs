@@ -1,6 +1,7 @@
 use crate::tui::app::App;
 use crate::tui::autocomplete;
-use crate::tui::models::InputMode;
+use crate::config::KeybindingMode;
+use crate::tui::models::{InputMode, ViewMode};
 use anyhow::Result;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use std::time::Duration;
@@ -14,24 +15,121 @@ pub fn handle_key_event(app: &mut App, key: KeyEvent) -> Result<bool> {
         }
     }
 
+    // Emacs bindings are translated to the default keys or handled right away
+    let key = if app.ui_config.keybinding_mode == KeybindingMode::Emacs {
+        match handle_emacs_key_event(app, key) {
+            Some(key) => key,
+            None => return Ok(false),
+        }
+    } else {
+        key
+    };
+
     match app.input_mode {
         InputMode::Normal => handle_normal_mode(app, key),
         InputMode::ProfilePath => handle_profile_path_mode(app, key),
         InputMode::SelectProfile => handle_select_profile_mode(app, key),
         InputMode::Searching => handle_search_mode(app, key),
         InputMode::ConfirmDelete => handle_confirm_delete_mode(app, key),
+        InputMode::EditNotes => handle_edit_notes_mode(app, key),
+        InputMode::AddToGroup => handle_add_to_group_mode(app, key),
+        InputMode::Rename => handle_rename_mode(app, key),
+        InputMode::Inspect => handle_inspect_mode(app, key),
+    }
+}
+
+/// Handle Emacs-style key bindings.
+///
+/// Cursor movement, navigation and cancel are translated to the matching default key so
+/// every mode handles them as usual. Killing to the end of the line and word movement are
+/// applied to the input line directly, in which case `None` is returned.
+fn handle_emacs_key_event(app: &mut App, key: KeyEvent) -> Option<KeyEvent> {
+    let has_input_line = matches!(app.input_mode, InputMode::ProfilePath | InputMode::Searching | InputMode::AddToGroup | InputMode::Rename);
+    let translated = |code: KeyCode| Some(KeyEvent::new(code, KeyModifiers::NONE));
+
+    if key.modifiers.contains(KeyModifiers::CONTROL) {
+        match key.code {
+            KeyCode::Char('f') => return translated(KeyCode::Right),
+            KeyCode::Char('b') => return translated(KeyCode::Left),
+            KeyCode::Char('n') => return translated(KeyCode::Down),
+            KeyCode::Char('p') => return translated(KeyCode::Up),
+            KeyCode::Char('g') => return translated(KeyCode::Esc),
+            KeyCode::Char('k') if has_input_line => {
+                commit_pending_autocomplete(app);
+                app.input_buffer.truncate(app.cursor_position);
+                input_line_changed(app);
+                return None;
+            }
+            _ => {}
+        }
+    } else if key.modifiers.contains(KeyModifiers::ALT) && has_input_line {
+        match key.code {
+            KeyCode::Char('f') => {
+                commit_pending_autocomplete(app);
+                app.cursor_position = next_word_end(&app.input_buffer, app.cursor_position);
+                return None;
+            }
+            KeyCode::Char('b') => {
+                commit_pending_autocomplete(app);
+                app.cursor_position = previous_word_start(&app.input_buffer, app.cursor_position);
+                return None;
+            }
+            _ => {}
+        }
+    }
+
+    Some(key)
+}
+
+/// Accept the autocomplete suggestion shown in the input line before editing it
+fn commit_pending_autocomplete(app: &mut App) {
+    if app.is_autocomplete_active {
+        autocomplete::commit_autocomplete(app);
     }
 }
 
+/// Update the mode after the input line was edited outside of its key handler
+fn input_line_changed(app: &mut App) {
+    if app.input_mode == InputMode::Searching {
+        app.current_autocomplete_index = 0;
+        update_search_results(app);
+    }
+}
+
+/// Get the position after the end of the word at or after the cursor
+fn next_word_end(text: &str, cursor: usize) -> usize {
+    let rest = &text[cursor..];
+    let word_start = rest.find(|c: char| !c.is_whitespace()).unwrap_or(rest.len());
+    let word_len = rest[word_start..].find(char::is_whitespace).unwrap_or(rest.len() - word_start);
+    cursor + word_start + word_len
+}
+
+/// Get the position of the start of the word before the cursor
+fn previous_word_start(text: &str, cursor: usize) -> usize {
+    let before = text[..cursor].trim_end();
+    before.rfind(char::is_whitespace).map_or(0, |pos| pos + 1)
+}
+
 /// Handle keyboard events in normal mode
 fn handle_normal_mode(app: &mut App, key: KeyEvent) -> Result<bool> {
     match key.code {
         KeyCode::Char('q') => Ok(true), // quit
         KeyCode::Char('r') => {
-            app.load_workspaces().unwrap_or_else(|e| {
-                app.set_status(&format!("Error: {}", e), Duration::from_secs(5));
-            });
-            app.set_status("Workspaces reloaded", Duration::from_secs(2));
+            if !app.loading {
+                app.start_loading().unwrap_or_else(|e| {
+                    app.set_status(&format!("Error: {}", e), Duration::from_secs(5));
+                });
+            }
+            Ok(false)
+        }
+        // Ctrl+P: Quick open, search and open the selected workspace with Enter
+        KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.input_mode = InputMode::Searching;
+            app.quick_open = true;
+            app.input_buffer.clear();
+            app.cursor_position = 0;
+            update_search_results(app);
+            app.set_status("Quick open: type to search, Enter to open", Duration::from_secs(3));
             Ok(false)
         }
         KeyCode::Char('p') => {
@@ -42,13 +140,36 @@ fn handle_normal_mode(app: &mut App, key: KeyEvent) -> Result<bool> {
             app.set_status("Select VSCode profile or press 'c' to enter custom path", Duration::from_secs(3));
             Ok(false)
         }
+        KeyCode::Char('g') => {
+            if app.selected_workspace().is_some() {
+                app.input_mode = InputMode::AddToGroup;
+                app.input_buffer.clear();
+                app.cursor_position = 0;
+            } else {
+                app.set_status("No workspace selected", Duration::from_secs(2));
+            }
+            Ok(false)
+        }
+        KeyCode::Char('R') => {
+            app.start_rename();
+            Ok(false)
+        }
+        KeyCode::Char('N') => {
+            app.start_edit_notes();
+            Ok(false)
+        }
+        KeyCode::Char('i') => {
+            app.start_inspect();
+            Ok(false)
+        }
         KeyCode::Char('f') | KeyCode::Char('/') => {
             app.input_mode = InputMode::Searching;
             app.input_buffer = app.search_query.clone();
             app.cursor_position = app.input_buffer.len();
             Ok(false)
         }
-        // Enter: Toggle mark/unmark for selected item
+        // Enter: Expand/collapse the selected group, or toggle mark/unmark for selected item
+        KeyCode::Enter if app.toggle_selected_group() => Ok(false),
         KeyCode::Enter => {
             app.toggle_mark_selected();
             app.set_status("Toggled current workspace", Duration::from_secs(1));
@@ -110,30 +231,196 @@ fn handle_normal_mode(app: &mut App, key: KeyEvent) -> Result<bool> {
             }
             Ok(false)
         }
+        // W: Show the database entries that could not be parsed
+        KeyCode::Char('W') => {
+            app.toggle_parse_warnings();
+            Ok(false)
+        }
+        // H: Show the workspaces on the host of the selected remote workspace
+        KeyCode::Char('H') => {
+            app.filter_by_selected_host();
+            Ok(false)
+        }
+        // G: Switch between the flat and the grouped by host view
+        KeyCode::Char('G') => {
+            app.toggle_view_mode();
+            let message = match app.view_mode {
+                ViewMode::Flat => "Showing all workspaces",
+                ViewMode::GroupedByHost => "Grouped by host, Enter on a header to expand/collapse",
+            };
+            app.set_status(message, Duration::from_secs(2));
+            Ok(false)
+        }
+        // c: Check whether the selected remote workspace exists over SSH
+        KeyCode::Char('c') => {
+            app.check_selected_remote();
+            Ok(false)
+        }
+        // D: Preview deletion of marked workspaces (dry run)
+        KeyCode::Char('D') => {
+            app.preview_marked_deletion();
+            Ok(false)
+        }
+        // Tab: Move the focus between the list and the details pane
+        KeyCode::Tab => {
+            app.toggle_details_focus();
+            let message = if app.details_focused {
+                "Details focused, Shift+↑/↓ to scroll, Tab to go back"
+            } else {
+                "Workspace list focused"
+            };
+            app.set_status(message, Duration::from_secs(2));
+            Ok(false)
+        }
+        KeyCode::Up if app.details_focused && key.modifiers.contains(KeyModifiers::SHIFT) => {
+            app.scroll_details(-1);
+            Ok(false)
+        }
+        KeyCode::Down if app.details_focused && key.modifiers.contains(KeyModifiers::SHIFT) => {
+            app.scroll_details(1);
+            Ok(false)
+        }
         KeyCode::Up => {
-            if let Some(index) = app.selected_workspace_index {
-                if index > 0 {
-                    app.selected_workspace_index = Some(index - 1);
-                }
-            }
+            app.move_selection(-1);
             Ok(false)
         }
         KeyCode::Down => {
-            if let Some(index) = app.selected_workspace_index {
-                if index < app.filtered_workspaces.len() - 1 {
-                    app.selected_workspace_index = Some(index + 1);
-                }
-            } else if !app.filtered_workspaces.is_empty() {
-                app.selected_workspace_index = Some(0);
-            }
+            app.move_selection(1);
+            Ok(false)
+        }
+        KeyCode::Home => {
+            app.select_first();
+            Ok(false)
+        }
+        KeyCode::End => {
+            app.select_last();
             Ok(false)
         }
         _ => Ok(false),
     }
 }
 
+/// Handle keyboard events while editing a workspace note
+fn handle_edit_notes_mode(app: &mut App, key: KeyEvent) -> Result<bool> {
+    match key.code {
+        // Ctrl+Enter: Save, Ctrl+S for terminals that don't report Ctrl+Enter
+        KeyCode::Enter if key.modifiers.contains(KeyModifiers::CONTROL) => app.save_notes(),
+        KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => app.save_notes(),
+        KeyCode::Enter => app.notes_buffer.push(String::new()),
+        KeyCode::Char(c) => {
+            if let Some(line) = app.notes_buffer.last_mut() {
+                line.push(c);
+            }
+        }
+        KeyCode::Backspace => {
+            // Join with the previous line at the start of a line
+            let line_empty = app.notes_buffer.last().is_some_and(|line| line.is_empty());
+            if line_empty && app.notes_buffer.len() > 1 {
+                app.notes_buffer.pop();
+            } else if let Some(line) = app.notes_buffer.last_mut() {
+                line.pop();
+            }
+        }
+        KeyCode::Esc => {
+            app.input_mode = InputMode::Normal;
+            app.set_status("Discarded note changes", Duration::from_secs(2));
+        }
+        _ => {}
+    }
+    Ok(false)
+}
+
+/// Handle keyboard events while entering the group for the selected workspace
+fn handle_add_to_group_mode(app: &mut App, key: KeyEvent) -> Result<bool> {
+    match key.code {
+        KeyCode::Enter => {
+            let name = app.input_buffer.clone();
+            if name.trim().is_empty() {
+                app.set_status("Enter a group name", Duration::from_secs(2));
+            } else {
+                app.add_selected_to_group(&name);
+            }
+        }
+        // Group names are single words so they can be used with :group:
+        KeyCode::Char(c) if !c.is_whitespace() => {
+            app.input_buffer.insert(app.cursor_position, c);
+            app.cursor_position += 1;
+        }
+        KeyCode::Backspace if app.cursor_position > 0 => {
+            app.input_buffer.remove(app.cursor_position - 1);
+            app.cursor_position -= 1;
+        }
+        KeyCode::Left if app.cursor_position > 0 => {
+            app.cursor_position -= 1;
+        }
+        KeyCode::Right if app.cursor_position < app.input_buffer.len() => {
+            app.cursor_position += 1;
+        }
+        KeyCode::Esc => {
+            app.input_mode = InputMode::Normal;
+        }
+        _ => {}
+    }
+    Ok(false)
+}
+
+/// Handle keyboard events while entering a new name for the selected workspace
+fn handle_rename_mode(app: &mut App, key: KeyEvent) -> Result<bool> {
+    match key.code {
+        KeyCode::Enter => {
+            let name = app.input_buffer.clone();
+            app.rename_selected(&name);
+        }
+        KeyCode::Char(c) => {
+            app.input_buffer.insert(app.cursor_position, c);
+            app.cursor_position += c.len_utf8();
+        }
+        KeyCode::Backspace if app.cursor_position > 0 => {
+            let previous = app.input_buffer[..app.cursor_position].chars().next_back().map_or(1, char::len_utf8);
+            app.cursor_position -= previous;
+            app.input_buffer.remove(app.cursor_position);
+        }
+        KeyCode::Left if app.cursor_position > 0 => {
+            let previous = app.input_buffer[..app.cursor_position].chars().next_back().map_or(1, char::len_utf8);
+            app.cursor_position -= previous;
+        }
+        KeyCode::Right if app.cursor_position < app.input_buffer.len() => {
+            let next = app.input_buffer[app.cursor_position..].chars().next().map_or(1, char::len_utf8);
+            app.cursor_position += next;
+        }
+        KeyCode::Esc => {
+            app.input_mode = InputMode::Normal;
+        }
+        _ => {}
+    }
+    Ok(false)
+}
+
+/// Handle keyboard events in the full-screen inspect view
+fn handle_inspect_mode(app: &mut App, key: KeyEvent) -> Result<bool> {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('i') => {
+            app.input_mode = InputMode::Normal;
+            app.inspect_lines.clear();
+        }
+        KeyCode::Up | KeyCode::Char('k') => app.scroll_inspect(-1),
+        KeyCode::Down | KeyCode::Char('j') => app.scroll_inspect(1),
+        KeyCode::PageUp => app.scroll_inspect(-10),
+        KeyCode::PageDown => app.scroll_inspect(10),
+        KeyCode::Home => app.inspect_scroll = 0,
+        KeyCode::End => app.scroll_inspect(isize::MAX),
+        _ => {}
+    }
+    Ok(false)
+}
+
 /// Handle keyboard events in profile path editing mode
 fn handle_profile_path_mode(app: &mut App, key: KeyEvent) -> Result<bool> {
+    // Any key other than Tab keeps the completed directory
+    if app.is_autocomplete_active && key.code != KeyCode::Tab {
+        autocomplete::commit_autocomplete(app);
+    }
+
     match key.code {
         KeyCode::Enter => {
             app.profile_path = app.input_buffer.clone();
@@ -148,6 +435,10 @@ fn handle_profile_path_mode(app: &mut App, key: KeyEvent) -> Result<bool> {
             app.cursor_position += 1;
             Ok(false)
         }
+        KeyCode::Tab => {
+            autocomplete::process_path_tab_key(app);
+            Ok(false)
+        }
         KeyCode::Backspace => {
             if app.cursor_position > 0 {
                 app.input_buffer.remove(app.cursor_position - 1);
@@ -167,6 +458,14 @@ fn handle_profile_path_mode(app: &mut App, key: KeyEvent) -> Result<bool> {
             }
             Ok(false)
         }
+        KeyCode::Home => {
+            app.cursor_position = 0;
+            Ok(false)
+        }
+        KeyCode::End => {
+            app.cursor_position = app.input_buffer.len();
+            Ok(false)
+        }
         KeyCode::Esc => {
             app.input_mode = InputMode::Normal;
             Ok(false)
@@ -233,6 +532,14 @@ fn handle_search_mode(app: &mut App, key: KeyEvent) -> Result<bool> {
     }
 
     match key.code {
+        // In quick open, Enter opens the selected workspace and leaves the search
+        KeyCode::Enter if app.quick_open => {
+            app.open_selected_workspace();
+            app.quick_open = false;
+            app.input_mode = InputMode::Normal;
+            Ok(false)
+        }
+        KeyCode::Enter if app.toggle_selected_group() => Ok(false),
         KeyCode::Enter => {
             // Toggle the selected item
             app.toggle_mark_selected();
@@ -265,27 +572,33 @@ fn handle_search_mode(app: &mut App, key: KeyEvent) -> Result<bool> {
             Ok(false)
         }
         KeyCode::Up => {
-            if let Some(index) = app.selected_workspace_index {
-                if index > 0 {
-                    app.selected_workspace_index = Some(index - 1);
-                }
-            } else if !app.filtered_workspaces.is_empty() {
-                app.selected_workspace_index = Some(0);
-            }
+            app.move_selection(-1);
             Ok(false)
         }
         KeyCode::Down => {
-            if let Some(index) = app.selected_workspace_index {
-                if index < app.filtered_workspaces.len() - 1 {
-                    app.selected_workspace_index = Some(index + 1);
-                }
-            } else if !app.filtered_workspaces.is_empty() {
-                app.selected_workspace_index = Some(0);
-            }
+            app.move_selection(1);
+            Ok(false)
+        }
+        // Home/End edit the query, with Ctrl they jump to the first/last workspace
+        KeyCode::Home if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.select_first();
+            Ok(false)
+        }
+        KeyCode::End if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.select_last();
+            Ok(false)
+        }
+        KeyCode::Home => {
+            app.cursor_position = 0;
+            Ok(false)
+        }
+        KeyCode::End => {
+            app.cursor_position = app.input_buffer.len();
             Ok(false)
         }
         KeyCode::Esc => {
             app.input_mode = InputMode::Normal;
+            app.quick_open = false;
 
             // Reset the autocomplete index when exiting search mode
             app.current_autocomplete_index = 0;
@@ -450,9 +763,19 @@ fn update_search_results(app: &mut App) {
     app.search_query = app.input_buffer.clone();
     app.apply_filter();
 
+    // IDs are unique, so point out when an :id: prefix identifies a single workspace
+    let has_id_filter = app.search_query.split_whitespace()
+        .any(|word| word.len() > ":id:".len() && word.to_lowercase().starts_with(":id:"));
+
     let count = app.filtered_workspaces.len();
     if count == 0 {
         app.set_status("No matches found", Duration::from_secs(1));
+    } else if count == 1 && has_id_filter {
+        let path = app.filtered_workspaces.first()
+            .and_then(|&i| app.workspaces.get(i))
+            .map(|workspace| workspace.path.clone())
+            .unwrap_or_default();
+        app.set_status(&format!("Unique ID match: {}", path), Duration::from_secs(3));
     } else {
         app.set_status(&format!("Found {} matches", count), Duration::from_secs(1));
     }
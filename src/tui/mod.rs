@@ -7,6 +7,7 @@ mod autocomplete;
 use std::io;
 use std::time::{Duration, Instant};
 use anyhow::Result;
+use crate::config::Config;
 use crossterm::{
     event::{self, Event},
     execute,
@@ -20,7 +21,7 @@ use ratatui::{
 pub use app::App;
 
 /// Run the TUI application
-pub fn run(profile_path: Option<&str>) -> Result<()> {
+pub fn run(profile_path: Option<&str>, config: &Config) -> Result<()> {
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -30,15 +31,16 @@ pub fn run(profile_path: Option<&str>) -> Result<()> {
 
     // Create app state
     let mut app = App::new(profile_path)?;
+    app.sort_order = config.default_sort;
+    app.editor_command = config.editor_command.clone();
+    app.ui_config.icons = config.icons.clone();
+    app.ui_config.keybinding_mode = config.keybinding_mode;
+    app.auto_reload_interval = config.auto_reload_interval
+        .filter(|secs| *secs > 0)
+        .map(Duration::from_secs);
     
-    // Load workspaces on startup
-    app.load_workspaces()?;
-
-    // Set status message
-    app.set_status(
-        &format!("Loaded {} workspaces", app.workspaces.len()),
-        Duration::from_secs(3),
-    );
+    // Load workspaces in the background so the UI shows up immediately
+    app.start_loading()?;
 
     // Main event loop
     let tick_rate = Duration::from_millis(100);
@@ -62,9 +64,15 @@ pub fn run(profile_path: Option<&str>) -> Result<()> {
             }
         }
         
+        // Pick up results from the background loader and existence check
+        app.poll_loading();
+        app.poll_existence();
+        
         // Tick update
         if last_tick.elapsed() >= tick_rate {
+            app.tick_count = app.tick_count.wrapping_add(1);
             app.update_status();
+            app.auto_reload_if_due();
             last_tick = Instant::now();
         }
     }
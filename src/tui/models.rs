@@ -1,3 +1,5 @@
+use crate::config::{IconConfig, KeybindingMode};
+
 /// Input modes for the TUI
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum InputMode {
@@ -15,6 +17,18 @@ pub enum InputMode {
     
     /// Confirming workspace deletion
     ConfirmDelete,
+    
+    /// Editing the note of the selected workspace
+    EditNotes,
+    
+    /// Entering the group to add the selected workspace to
+    AddToGroup,
+    
+    /// Entering a new name for the selected workspace
+    Rename,
+    
+    /// Full-screen dump of the selected workspace
+    Inspect,
 }
 
 /// Simplified workspace info for the TUI
@@ -50,6 +64,12 @@ pub struct WorkspaceInfo {
     /// Tags associated with the workspace
     #[allow(dead_code)]
     pub tags: Vec<String>,
+    
+    /// Last used timestamp in milliseconds (0 if never used)
+    pub last_used: i64,
+    
+    /// Color assigned to the workspace in the editor, as a readable name
+    pub color: Option<String>,
 }
 
 /// UI configuration settings
@@ -57,6 +77,10 @@ pub struct WorkspaceInfo {
 pub struct UiConfig {
     /// Whether to use colors in the UI
     pub use_colors: bool,
+    /// Indicators shown in the workspace list
+    pub icons: IconConfig,
+    /// Default or Emacs-style key bindings
+    pub keybinding_mode: KeybindingMode,
 }
 
 impl Default for UiConfig {
@@ -67,6 +91,33 @@ impl Default for UiConfig {
         
         Self {
             use_colors: !no_color,
+            icons: IconConfig::default(),
+            keybinding_mode: KeybindingMode::default(),
         }
     }
-} 
\ No newline at end of file
+}
+
+/// Layout of the workspace list
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ViewMode {
+    /// One row per workspace
+    #[default]
+    Flat,
+    
+    /// Workspaces grouped under a header row per remote host
+    GroupedByHost,
+}
+
+/// A row of the workspace list
+#[derive(Debug, Clone, PartialEq)]
+pub enum ListRow {
+    /// Header of a host group, with the number of workspaces in it
+    Group {
+        host: String,
+        count: usize,
+        collapsed: bool,
+    },
+    
+    /// Workspace at the given position in `filtered_workspaces`
+    Workspace(usize),
+}
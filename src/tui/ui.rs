@@ -1,16 +1,25 @@
+use crate::config::IconConfig;
 use crate::tui::app::App;
-use crate::tui::models::{InputMode, WorkspaceInfo};
+use crate::tui::autocomplete::FILTER_MODIFIERS;
+use crate::workspaces::SortOrder;
+use crate::tui::models::{InputMode, ListRow, WorkspaceInfo};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Span, Text, Line},
-    widgets::{Block, Borders, List, ListItem, Paragraph},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
     Frame,
 };
 use crate::workspaces;
 
 /// Render the TUI interface
 pub fn render(f: &mut Frame, app: &App) {
+    // The inspect view replaces the whole screen
+    if app.input_mode == InputMode::Inspect {
+        render_inspect_view(f, app, f.size());
+        return;
+    }
+    
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .margin(1)
@@ -42,6 +51,14 @@ pub fn render(f: &mut Frame, app: &App) {
     
     match app.input_mode {
         InputMode::SelectProfile => render_profile_selection(f, app, chunks[2]),
+        InputMode::EditNotes => {
+            render_workspaces(f, app, content_chunks[0]);
+            render_notes_editor(f, app, content_chunks[1]);
+        }
+        _ if app.show_parse_warnings => {
+            render_workspaces(f, app, content_chunks[0]);
+            render_parse_warnings(f, app, content_chunks[1]);
+        }
         _ => {
             render_workspaces(f, app, content_chunks[0]);
             render_details_pane(f, app, content_chunks[1]);
@@ -49,24 +66,143 @@ pub fn render(f: &mut Frame, app: &App) {
     }
     
     render_help_text(f, app, chunks[3]);
+    
+    if app.loading {
+        render_loading_overlay(f, app, f.size());
+    }
 }
 
-/// Render the status line
-fn render_status_line(f: &mut Frame, app: &App, area: Rect) {
-    // Use a default message with the profile path when status is empty
-    let status_text = match app.status_message.as_deref() {
-        Some(msg) if !msg.is_empty() => msg.to_string(),
-        _ => format!("VSCode WS Editor: {}", app.profile_path)
+/// Render the full-screen dump of the selected workspace
+fn render_inspect_view(f: &mut Frame, app: &App, area: Rect) {
+    let border_color = if app.ui_config.use_colors { Color::Cyan } else { Color::White };
+    let title = format!(
+        "Inspect ({}/{}) - ↑/↓/PgUp/PgDn: scroll, Esc: back",
+        (app.inspect_scroll + 1).min(app.inspect_lines.len()),
+        app.inspect_lines.len()
+    );
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .border_style(Style::default().fg(border_color));
+    
+    let key_style = Style::default().fg(if app.ui_config.use_colors { Color::Yellow } else { Color::White });
+    let lines: Vec<Line> = app.inspect_lines.iter()
+        .skip(app.inspect_scroll)
+        .map(|line| {
+            // Highlight the keys of the JSON dump
+            let indent = line.len() - line.trim_start().len();
+            match line.trim_start().split_once("\": ") {
+                Some((key, value)) if key.starts_with('"') => Line::from(vec![
+                    Span::raw(line[..indent].to_string()),
+                    Span::styled(format!("{}\":", key), key_style),
+                    Span::raw(format!(" {}", value)),
+                ]),
+                _ => Line::from(line.as_str()),
+            }
+        })
+        .collect();
+    
+    f.render_widget(Paragraph::new(Text::from(lines)).block(block), area);
+}
+
+/// Render a popup in the middle of the screen while workspaces are being loaded
+fn render_loading_overlay(f: &mut Frame, app: &App, area: Rect) {
+    let popup = centered_rect(48, 5, area);
+    let style = if app.ui_config.use_colors {
+        Style::default().fg(Color::Yellow)
+    } else {
+        Style::default()
     };
     
+    let lines = vec![
+        Line::from(format!("{} Loading workspaces…", loading_spinner(app))),
+        Line::from(progress_bar(app.loading_progress, 40)),
+        Line::from(Span::styled(app.loading_message.clone(), Style::default().fg(Color::DarkGray))),
+    ];
+    let overlay = Paragraph::new(lines)
+        .style(style)
+        .block(Block::default().borders(Borders::ALL).title("Loading"));
+    
+    // Clear the content below the popup so the list doesn't show through
+    f.render_widget(Clear, popup);
+    f.render_widget(overlay, popup);
+}
+
+/// Get a rectangle of the given size centered in `area`, shrunk to fit if needed
+fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    )
+}
+
+/// Current frame of the loading spinner
+fn loading_spinner(app: &App) -> char {
+    const FRAMES: [char; 4] = ['|', '/', '-', '\\'];
+    FRAMES[app.tick_count % FRAMES.len()]
+}
+
+/// Draw a text progress bar such as `[#######-------------]`
+fn progress_bar(fraction: f64, width: usize) -> String {
+    let filled = ((fraction.clamp(0.0, 1.0) * width as f64).round() as usize).min(width);
+    format!("[{}{}]", "#".repeat(filled), "-".repeat(width - filled))
+}
+
+/// Render the status line
+fn render_status_line(f: &mut Frame, app: &App, area: Rect) {
     let status_style = if app.ui_config.use_colors {
         Style::default().fg(Color::Yellow)
     } else {
         Style::default()
     };
-    
-    let status = Paragraph::new(status_text).style(status_style);
-    f.render_widget(status, area);
+
+    // Use a default message with the profile path, editor and source breakdown when status is empty
+    let status_line = match app.status_message.as_deref() {
+        _ if app.loading => Line::from(Span::styled(
+            format!("{} {} {}", loading_spinner(app), progress_bar(app.loading_progress, 20), app.loading_message),
+            status_style,
+        )),
+        Some(msg) if !msg.is_empty() => Line::from(Span::styled(msg.to_string(), status_style)),
+        _ => {
+            let mut spans = vec![Span::styled(
+                format!("VSCode WS Editor: {} | Editor: {} | ", app.profile_path, app.editor_command),
+                status_style,
+            )];
+            spans.extend(source_breakdown_spans(app, status_style));
+            Line::from(spans)
+        }
+    };
+
+    f.render_widget(Paragraph::new(status_line), area);
+}
+
+/// Spans for "N workspaces (S storage / D db / Z zed)", with each source tag in its own color
+fn source_breakdown_spans(app: &App, style: Style) -> Vec<Span<'static>> {
+    let count = |kind: &str| app.source_breakdown.get(kind).copied().unwrap_or(0);
+    let tags = [
+        (count("storage"), "storage", Color::Cyan),
+        (count("database"), "db", Color::Yellow),
+        (count("zed"), "zed", Color::Green),
+    ];
+
+    let mut spans = vec![Span::styled(format!("{} workspaces (", app.workspaces.len()), style)];
+    for (i, (count, label, color)) in tags.into_iter().enumerate() {
+        if i > 0 {
+            spans.push(Span::styled(" / ", style));
+        }
+        let tag_style = if app.ui_config.use_colors {
+            Style::default().fg(color)
+        } else {
+            style
+        };
+        spans.push(Span::styled(format!("{} {}", count, label), tag_style));
+    }
+    spans.push(Span::styled(")", style));
+    spans
 }
 
 /// Render the input area
@@ -144,6 +280,22 @@ fn render_input(f: &mut Frame, app: &App, area: Rect) {
             
             text = Text::styled(&delete_msg, style);
             title = "Confirm Deletion";
+        },
+        InputMode::EditNotes => {
+            text = Text::raw("Write a note for the selected workspace");
+            title = "Edit Notes";
+        },
+        InputMode::AddToGroup => {
+            text = Text::raw(&app.input_buffer);
+            title = "Add to Group (new or existing group name)";
+        },
+        InputMode::Rename => {
+            text = Text::raw(&app.input_buffer);
+            title = "Rename (empty to remove the name)";
+        },
+        InputMode::Inspect => {
+            text = Text::raw("");
+            title = "Inspect";
         }
     };
 
@@ -152,7 +304,7 @@ fn render_input(f: &mut Frame, app: &App, area: Rect) {
 
     // Set cursor position for input modes
     match app.input_mode {
-        InputMode::ProfilePath | InputMode::Searching => {
+        InputMode::ProfilePath | InputMode::Searching | InputMode::AddToGroup | InputMode::Rename => {
             f.set_cursor(
                 area.x + app.cursor_position as u16 + 1,
                 area.y + 1,
@@ -169,22 +321,33 @@ fn render_input(f: &mut Frame, app: &App, area: Rect) {
     f.render_widget(paragraph, area);
 }
 
+/// Width of the last used column at the right of the workspace list
+const LAST_USED_WIDTH: usize = 16;
+
 /// Render the workspaces list
 fn render_workspaces(f: &mut Frame, app: &App, area: Rect) {
     // Calculate visible count and offset for scrolling
     let height = area.height as usize;
-    let list_height = height.saturating_sub(2); // Subtract 2 for borders
+    let filters = if app.input_mode == InputMode::Searching {
+        active_filters(&app.search_query)
+    } else {
+        Vec::new()
+    };
+    let pill_rows = usize::from(!filters.is_empty());
+    let list_height = height.saturating_sub(3 + pill_rows); // Subtract 2 for borders, 1 for the header and the filter pills
     
-    // For ConfirmDelete mode, filter the list to show only marked workspaces
-    let visible_workspaces: Vec<usize> = app.filtered_workspaces.clone();
+    // Workspace rows, with host group headers in the grouped view
+    let rows = app.list_rows();
     
     // Create the list items
-    let items: Vec<ListItem> = if visible_workspaces.is_empty() {
+    let items: Vec<ListItem> = if rows.is_empty() {
         // Show appropriate message based on whether there's a search filter
-        let message = if !app.search_query.is_empty() {
-            "No workspaces match your search criteria."
+        let message = if app.loading {
+            format!("{} {}", loading_spinner(app), app.loading_message)
+        } else if !app.search_query.is_empty() {
+            "No workspaces match your search criteria.".to_string()
         } else {
-            "No workspaces found in this VSCode profile."
+            "No workspaces found in this VSCode profile.".to_string()
         };
         
         vec![ListItem::new(message).style(
@@ -198,8 +361,8 @@ fn render_workspaces(f: &mut Frame, app: &App, area: Rect) {
         // We keep the selected_idx even in ConfirmDelete mode
         let selected_idx = app.selected_workspace_index;
         
-        // Calculate offset for scrolling (keep selected item in view)
-        let offset = if let Some(idx) = selected_idx {
+        // Calculate offset for scrolling (keep selected row in view)
+        let offset = if let Some(idx) = app.selected_row(&rows) {
             if idx >= list_height {
                 idx - list_height + 1
             } else {
@@ -213,12 +376,19 @@ fn render_workspaces(f: &mut Frame, app: &App, area: Rect) {
         let list_width = area.width.saturating_sub(2) as usize; // Subtract 2 for borders
         
         // Format items with style
-        visible_workspaces
+        rows
             .iter()
-            .enumerate()
             .skip(offset)
             .take(list_height)
-            .map(|(i, &workspace_idx)| {
+            .map(|row| {
+                let i = match row {
+                    ListRow::Workspace(i) => *i,
+                    ListRow::Group { host, count, collapsed } => {
+                        return format_group_header(host, *count, *collapsed, app.selected_group.as_ref() == Some(host), app);
+                    }
+                };
+                let workspace_idx = app.filtered_workspaces[i];
+                
                 // Get the workspace
                 if let Some(workspace) = app.workspaces.get(workspace_idx) {
                     // Check if this workspace is marked for deletion
@@ -232,7 +402,7 @@ fn render_workspaces(f: &mut Frame, app: &App, area: Rect) {
                         id: workspace.id.clone(),
                         name: workspace.name.clone(),
                         path: workspace.path.clone(),
-                        exists: crate::workspaces::workspace_exists(workspace),
+                        exists: app.workspace_exists_at(workspace_idx),
                         workspace_type: workspace_clone.get_type(),
                         is_remote: workspace_clone.is_remote(),
                         remote_user: workspace.parsed_info.as_ref()
@@ -242,10 +412,21 @@ fn render_workspaces(f: &mut Frame, app: &App, area: Rect) {
                         tags: workspace.parsed_info.as_ref()
                             .map(|info| info.tags.clone())
                             .unwrap_or_default(),
+                        last_used: workspace.last_used,
+                        color: workspace.color.clone(),
                     };
                     
-                    // Format the workspace entry with style
-                    let entry_spans = format_workspace_entry_styled(&workspace_info, is_marked, app);
+                    // Format the workspace entry with style, last used aligned to the right
+                    let mut entry_spans = format_workspace_entry_styled(&workspace_info, is_marked, app);
+                    let entry_width: usize = entry_spans.iter()
+                        .map(|span| unicode_width::UnicodeWidthStr::width(span.content.as_ref()))
+                        .sum();
+                    let gap = list_width.saturating_sub(entry_width + LAST_USED_WIDTH).max(1);
+                    entry_spans.push(Span::raw(" ".repeat(gap)));
+                    entry_spans.push(Span::styled(
+                        format_list_last_used(workspace_info.last_used),
+                        if app.ui_config.use_colors { Style::default().fg(Color::DarkGray) } else { Style::default() },
+                    ));
                     
                     // Handle selection highlighting
                     let item_text = if let Some(selected_idx) = selected_idx {
@@ -316,15 +497,161 @@ fn render_workspaces(f: &mut Frame, app: &App, area: Rect) {
         _ => "Workspaces",
     };
     
+    let block = Block::default().borders(Borders::ALL).title(title);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+    
+    // The filter pills and header are drawn above the list, so they are never selected
+    let list_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(pill_rows as u16), Constraint::Length(1), Constraint::Min(0)].as_ref())
+        .split(inner);
+    if !filters.is_empty() {
+        render_filter_pills(f, app, &filters, list_chunks[0]);
+    }
+    f.render_widget(Paragraph::new(list_header(app, inner.width as usize)), list_chunks[1]);
+    
     let list = List::new(items)
-        .block(Block::default().borders(Borders::ALL).title(title))
         .highlight_style(
             Style::default()
                 .bg(Color::DarkGray)
                 .add_modifier(Modifier::BOLD),
         );
 
-    f.render_widget(list, area);
+    f.render_widget(list, list_chunks[2]);
+}
+
+/// Get the filter modifiers with a value in a search query, as (name, value) pairs
+fn active_filters(query: &str) -> Vec<(&str, &str)> {
+    query.split_whitespace()
+        .filter_map(|word| {
+            FILTER_MODIFIERS.iter().find_map(|modifier| {
+                let value = word.strip_prefix(modifier)?;
+                (!value.is_empty()).then(|| (modifier.trim_matches(':'), value))
+            })
+        })
+        .collect()
+}
+
+/// Render the active filter modifiers as a row of pills, e.g. `[remote: yes] [type: folder]`
+fn render_filter_pills(f: &mut Frame, app: &App, filters: &[(&str, &str)], area: Rect) {
+    let pill_style = if app.ui_config.use_colors {
+        Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().add_modifier(Modifier::BOLD)
+    };
+    
+    let mut spans = Vec::new();
+    for (name, value) in filters {
+        if !spans.is_empty() {
+            spans.push(Span::raw(" "));
+        }
+        spans.push(Span::styled(format!("[{}: {}]", name, value), pill_style));
+    }
+    
+    f.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+/// Build the column header of the workspace list, with an arrow at the sorted column
+fn list_header(app: &App, width: usize) -> Line<'static> {
+    let arrow = if app.sort_order.default_descending() { " ▼" } else { " ▲" };
+    let style = if app.ui_config.use_colors {
+        Style::default().fg(Color::DarkGray).add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().add_modifier(Modifier::BOLD)
+    };
+    let active_style = if app.ui_config.use_colors {
+        Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+    };
+    let column = |label: &str, order: SortOrder| {
+        if app.sort_order == order {
+            Span::styled(format!("{}{}", label, arrow), active_style)
+        } else {
+            Span::styled(label.to_string(), style)
+        }
+    };
+    
+    // Mark, exists, type and remote columns, matching the widths of the row indicators
+    let [mark_width, exists_width, type_width, remote_width] = icon_column_widths(&app.ui_config.icons);
+    let indicators = format!(
+        "{}{}{}{}",
+        pad_icon("Del", mark_width),
+        pad_icon("E", exists_width),
+        pad_icon("T", type_width),
+        pad_icon("R", remote_width),
+    );
+    let mut spans = vec![
+        Span::styled(indicators, style),
+        column("Name", SortOrder::Name),
+        Span::styled(" (", style),
+        column("Path", SortOrder::Path),
+        Span::styled(")", style),
+    ];
+    
+    let used: usize = spans.iter()
+        .map(|span| unicode_width::UnicodeWidthStr::width(span.content.as_ref()))
+        .sum();
+    let last_used = column("Last Used", SortOrder::LastUsed);
+    let last_used_width = unicode_width::UnicodeWidthStr::width(last_used.content.as_ref());
+    spans.push(Span::raw(" ".repeat(width.saturating_sub(used + last_used_width).max(1))));
+    spans.push(last_used);
+    
+    Line::from(spans)
+}
+
+/// Widths of the mark, exists, type and remote columns for the configured icons,
+/// each including the space after the indicator
+fn icon_column_widths(icons: &IconConfig) -> [usize; 4] {
+    let width = |icon: &str| unicode_width::UnicodeWidthStr::width(icon);
+    [
+        width(&icons.marked) + 3,
+        width(&icons.exists).max(width(&icons.missing)) + 1,
+        [&icons.folder, &icons.workspace, &icons.file, &icons.worktree].into_iter()
+            .map(|icon| width(icon))
+            .max()
+            .unwrap_or(0)
+            .max(1) + 1,
+        width(&icons.remote).max(width(&icons.local)) + 1,
+    ]
+}
+
+/// Pad an indicator with spaces to the width of its column
+fn pad_icon(icon: &str, width: usize) -> String {
+    let padding = width.saturating_sub(unicode_width::UnicodeWidthStr::width(icon));
+    format!("{}{}", icon, " ".repeat(padding))
+}
+
+/// Format the last used time for the list column
+fn format_list_last_used(last_used: i64) -> String {
+    if last_used <= 0 {
+        return format!("{:>width$}", "Never", width = LAST_USED_WIDTH);
+    }
+    
+    chrono::DateTime::<chrono::Utc>::from_timestamp(last_used / 1000, 0)
+        .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_else(|| format!("{:>width$}", "Unknown", width = LAST_USED_WIDTH))
+}
+
+/// Format the header row of a host group in the grouped view
+fn format_group_header(host: &str, count: usize, collapsed: bool, selected: bool, app: &App) -> ListItem<'static> {
+    let marker = if collapsed { "▸" } else { "▾" };
+    let text = format!("{} {} ({})", marker, host, count);
+    
+    let style = if selected {
+        if app.ui_config.use_colors {
+            Style::default().fg(Color::Black).bg(Color::Yellow).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().add_modifier(Modifier::REVERSED | Modifier::BOLD)
+        }
+    } else if app.ui_config.use_colors {
+        Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().add_modifier(Modifier::BOLD)
+    };
+    
+    ListItem::new(text).style(style)
 }
 
 /// Format a workspace entry with color and style information
@@ -333,6 +660,8 @@ fn format_workspace_entry_styled(workspace: &WorkspaceInfo, is_marked: bool, app
     
     // Get whether to use colors or not
     let use_colors = app.ui_config.use_colors;
+    let icons = &app.ui_config.icons;
+    let [mark_width, exists_width, type_width, remote_width] = icon_column_widths(icons);
     
     // Add mark indicator
     let mark_style = if use_colors {
@@ -346,7 +675,11 @@ fn format_workspace_entry_styled(workspace: &WorkspaceInfo, is_marked: bool, app
     };
     
     spans.push(Span::styled(
-        if is_marked { "[X] ".to_string() } else { "[ ] ".to_string() },
+        if is_marked {
+            pad_icon(&format!("[{}]", icons.marked), mark_width)
+        } else {
+            pad_icon(&format!("[{}]", " ".repeat(mark_width - 3)), mark_width)
+        },
         mark_style
     ));
     
@@ -362,7 +695,7 @@ fn format_workspace_entry_styled(workspace: &WorkspaceInfo, is_marked: bool, app
     };
     
     spans.push(Span::styled(
-        if workspace.exists { "✓ ".to_string() } else { "✗ ".to_string() },
+        pad_icon(if workspace.exists { &icons.exists } else { &icons.missing }, exists_width),
         existence_style
     ));
     
@@ -379,14 +712,15 @@ fn format_workspace_entry_styled(workspace: &WorkspaceInfo, is_marked: bool, app
     };
     
     let type_icon = match workspace.workspace_type.as_str() {
-        "folder" => "📁 ",
-        "workspace" => "🔨 ",
-        "file" => "📄 ",
-        _ => "❓ ",
+        "folder" if workspace.tags.iter().any(|tag| tag == "git-worktree") => icons.worktree.as_str(),
+        "folder" => icons.folder.as_str(),
+        "workspace" => icons.workspace.as_str(),
+        "file" => icons.file.as_str(),
+        _ => "?",
     };
     
     spans.push(Span::styled(
-        type_icon.to_string(),
+        pad_icon(type_icon, type_width),
         type_style
     ));
     
@@ -402,7 +736,7 @@ fn format_workspace_entry_styled(workspace: &WorkspaceInfo, is_marked: bool, app
     };
     
     spans.push(Span::styled(
-        if workspace.is_remote { "🌐 ".to_string() } else { "🏠 ".to_string() },
+        pad_icon(if workspace.is_remote { &icons.remote } else { &icons.local }, remote_width),
         remote_style
     ));
     
@@ -423,6 +757,11 @@ fn format_workspace_entry_styled(workspace: &WorkspaceInfo, is_marked: bool, app
         _ => workspaces::extract_folder_basename(&workspace.path)
     };
     
+    // Mark workspaces that have a color assigned in the editor
+    if let Some(color) = workspace.color.as_deref().and_then(workspace_color).filter(|_| use_colors) {
+        spans.push(Span::styled("● ".to_string(), Style::default().fg(color)));
+    }
+    
     spans.push(Span::styled(
         name,
         name_style.add_modifier(Modifier::BOLD)
@@ -443,6 +782,36 @@ fn format_workspace_entry_styled(workspace: &WorkspaceInfo, is_marked: bool, app
     spans
 }
 
+/// Get the terminal color for a workspace color name (see `database::color_name`)
+fn workspace_color(name: &str) -> Option<Color> {
+    let color = match name {
+        "black" => Color::Black,
+        "red" => Color::Red,
+        "green" => Color::Green,
+        "yellow" => Color::Yellow,
+        "blue" => Color::Blue,
+        "magenta" | "purple" => Color::Magenta,
+        "cyan" => Color::Cyan,
+        "white" => Color::White,
+        "orange" => Color::Rgb(255, 165, 0),
+        "bright black" => Color::DarkGray,
+        "bright red" => Color::LightRed,
+        "bright green" => Color::LightGreen,
+        "bright yellow" => Color::LightYellow,
+        "bright blue" => Color::LightBlue,
+        "bright magenta" => Color::LightMagenta,
+        "bright cyan" => Color::LightCyan,
+        "bright white" => Color::Gray,
+        // Hex colors such as #ff8800
+        hex => {
+            let hex = hex.strip_prefix('#').filter(|hex| hex.len() == 6)?;
+            let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+            Color::Rgb(channel(0)?, channel(2)?, channel(4)?)
+        }
+    };
+    Some(color)
+}
+
 /// Format a workspace entry as plain string (used for simple display cases)
 #[allow(dead_code)]
 fn format_workspace_entry(workspace: &WorkspaceInfo, is_marked: bool) -> String {
@@ -482,35 +851,44 @@ fn format_workspace_entry(workspace: &WorkspaceInfo, is_marked: bool) -> String
 
 /// Render details pane showing information about the selected workspace
 fn render_details_pane(f: &mut Frame, app: &App, area: Rect) {
-    let selected_workspace = app.selected_workspace_index
+    let selected_index = app.selected_workspace_index
         .and_then(|i| app.filtered_workspaces.get(i))
-        .map(|&idx| &app.workspaces[idx]);
+        .copied();
+    let selected_workspace = selected_index.map(|idx| &app.workspaces[idx]);
     
     // Use brighter colors for the border to improve visibility
-    let border_color = if app.ui_config.use_colors { Color::Cyan } else { Color::White };
+    let border_color = match (app.ui_config.use_colors, app.details_focused) {
+        (true, true) => Color::Yellow,
+        (true, false) => Color::Cyan,
+        (false, _) => Color::White,
+    };
+    let title = if app.details_focused { "Details (Shift+↑/↓: scroll)" } else { "Details" };
     
     let block = Block::default()
         .borders(Borders::ALL)
-        .title("Details")
+        .title(title)
         .border_style(Style::default().fg(border_color));
     
     f.render_widget(block, area);
     
-    // Return early if no workspace is selected
+    // Create a smaller area for the content
+    let content_area = Layout::default()
+        .margin(1)
+        .constraints([Constraint::Min(0)].as_ref())
+        .split(area)[0];
+    
+    // Show statistics of all workspaces if no workspace is selected
     let workspace = match selected_workspace {
         Some(w) => w,
-        None => return,
+        None => {
+            render_statistics_sidebar(f, app, content_area);
+            return;
+        }
     };
     
     // Clone to be able to call methods
     let mut workspace_clone = workspace.clone();
     
-    // Create a smaller area for the content
-    let content_area = Layout::default()
-        .margin(1)
-        .constraints([Constraint::Min(0)].as_ref())
-        .split(area)[0];
-    
     // Check if workspace exists
     let exists = crate::workspaces::workspace_exists(&workspace_clone);
     
@@ -521,13 +899,19 @@ fn render_details_pane(f: &mut Frame, app: &App, area: Rect) {
         .map(|info| info.tags.join(", "))
         .unwrap_or_default();
     
-    // Get remote user and port
+    // Real path of a local workspace opened through a symlink, resolved in the background
+    let symlink_target = selected_index.and_then(|idx| app.symlink_target_at(idx));
+    
+    // Get remote user and port, falling back to the SSH config of host aliases
     let remote_host = workspace_clone.parsed_info.as_ref()
-        .and_then(|info| info.remote_host.clone());
+        .and_then(|info| match (&info.remote_host, &info.resolved_host) {
+            (Some(alias), Some(resolved)) => Some(format!("{} ({})", alias, resolved)),
+            (host, _) => host.clone(),
+        });
     let remote_user = workspace_clone.parsed_info.as_ref()
-        .and_then(|info| info.remote_user.clone());
+        .and_then(|info| info.remote_user.clone().or_else(|| info.resolved_user.clone()));
     let remote_port = workspace_clone.parsed_info.as_ref()
-        .and_then(|info| info.remote_port);
+        .and_then(|info| info.remote_port.or(info.resolved_port));
     
     // Format dates
     let last_used = if workspace.last_used > 0 {
@@ -554,6 +938,10 @@ fn render_details_pane(f: &mut Frame, app: &App, area: Rect) {
         Line::from(vec![
             Span::styled("Path: ", Style::default().fg(if app.ui_config.use_colors { Color::Yellow } else { Color::White })),
             Span::raw(&workspace.path),
+            Span::styled(
+                if symlink_target.is_some() { " (symlink)" } else { "" },
+                Style::default().fg(if app.ui_config.use_colors { Color::Cyan } else { Color::White }),
+            ),
         ]),
         Line::from(vec![
             Span::styled("Type: ", Style::default().fg(if app.ui_config.use_colors { Color::Yellow } else { Color::White })),
@@ -626,6 +1014,35 @@ fn render_details_pane(f: &mut Frame, app: &App, area: Rect) {
                 ),
             ]));
         }
+        
+        if let Some((service, compose_file)) = workspace_clone.parsed_info.as_ref().and_then(workspaces::parser::compose_service) {
+            let text = match compose_file {
+                Some(file) => format!("{} ({})", service, file),
+                None => service.to_string(),
+            };
+            detail_lines.push(Line::from(vec![
+                Span::styled("Compose Service: ", Style::default().fg(if app.ui_config.use_colors { Color::Yellow } else { Color::White })),
+                Span::styled(
+                    text,
+                    Style::default().fg(if app.ui_config.use_colors { Color::Cyan } else { Color::White }),
+                ),
+            ]));
+        }
+        
+        if let Some((_, others)) = app.workspaces_on_same_host(workspace) {
+            let text = match others {
+                0 => "No other workspaces on this host".to_string(),
+                1 => "1 more workspace on this host (H to show)".to_string(),
+                n => format!("{} more workspaces on this host (H to show)", n),
+            };
+            detail_lines.push(Line::from(vec![
+                Span::styled("By Host: ", Style::default().fg(if app.ui_config.use_colors { Color::Yellow } else { Color::White })),
+                Span::styled(
+                    text,
+                    Style::default().fg(if app.ui_config.use_colors { Color::Cyan } else { Color::White }),
+                ),
+            ]));
+        }
     }
     
     // Add remaining details
@@ -634,6 +1051,16 @@ fn render_details_pane(f: &mut Frame, app: &App, area: Rect) {
         Span::raw(last_used),
     ]));
     
+    detail_lines.push(Line::from(vec![
+        Span::styled("Opened via CLI: ", Style::default().fg(if app.ui_config.use_colors { Color::Yellow } else { Color::White })),
+        Span::raw(format!("{} times", workspace.open_count.unwrap_or(0))),
+    ]));
+    
+    detail_lines.push(Line::from(vec![
+        Span::styled("Files opened: ", Style::default().fg(if app.ui_config.use_colors { Color::Yellow } else { Color::White })),
+        Span::raw(workspace.recent_file_count.to_string()),
+    ]));
+    
     detail_lines.push(Line::from(""));
     
     detail_lines.push(Line::from(vec![
@@ -644,12 +1071,209 @@ fn render_details_pane(f: &mut Frame, app: &App, area: Rect) {
         ),
     ]));
     
+    for source in &workspace.sources {
+        detail_lines.push(Line::from(vec![
+            Span::styled("Source: ", Style::default().fg(if app.ui_config.use_colors { Color::Yellow } else { Color::White })),
+            Span::raw(match source {
+                workspaces::WorkspaceSource::Storage(path) => format!("Storage {}", path),
+                workspaces::WorkspaceSource::Database(key) => format!("Database {}", key),
+                workspaces::WorkspaceSource::Zed(channel) => format!("Zed {}", channel),
+                workspaces::WorkspaceSource::Profile(name) => format!("Profile {}", name),
+            }),
+        ]));
+    }
+    
+    if let Some(color) = &workspace.color {
+        let swatch_style = workspace_color(color)
+            .filter(|_| app.ui_config.use_colors)
+            .map(|color| Style::default().fg(color))
+            .unwrap_or_default();
+        detail_lines.push(Line::from(vec![
+            Span::styled("Color: ", Style::default().fg(if app.ui_config.use_colors { Color::Yellow } else { Color::White })),
+            Span::styled("● ", swatch_style),
+            Span::raw(color.clone()),
+        ]));
+    }
+    
+    if let Some(branch) = &workspace.git_branch {
+        detail_lines.push(Line::from(vec![
+            Span::styled("Branch: ", Style::default().fg(if app.ui_config.use_colors { Color::Yellow } else { Color::White })),
+            Span::styled(
+                branch.clone(),
+                Style::default().fg(if app.ui_config.use_colors { Color::Green } else { Color::White })
+            ),
+        ]));
+    }
+    
+    if !workspace.recommended_extensions.is_empty() {
+        detail_lines.push(Line::from(vec![
+            Span::styled("Extensions: ", Style::default().fg(if app.ui_config.use_colors { Color::Yellow } else { Color::White })),
+            Span::raw(note_preview(&workspace.recommended_extensions.join(", "), 80)),
+        ]));
+    }
+    
+    let groups = app.workspace_groups(workspace);
+    if !groups.is_empty() {
+        detail_lines.push(Line::from(vec![
+            Span::styled("Groups: ", Style::default().fg(if app.ui_config.use_colors { Color::Yellow } else { Color::White })),
+            Span::styled(
+                groups.join(", "),
+                Style::default().fg(if app.ui_config.use_colors { Color::Cyan } else { Color::White })
+            ),
+        ]));
+    }
+    
+    if let Some(note) = app.workspace_note(workspace) {
+        detail_lines.push(Line::from(""));
+        detail_lines.push(Line::from(vec![
+            Span::styled("Notes: ", Style::default().fg(if app.ui_config.use_colors { Color::Yellow } else { Color::White })),
+            Span::raw(note_preview(note, 120)),
+        ]));
+    }
+    
+    // Rows taken by the wrapped lines, to stop scrolling at the last one
+    let width = (content_area.width as usize).max(1);
+    let rows: usize = detail_lines.iter()
+        .map(|line| line.width().div_ceil(width).max(1))
+        .sum();
+    let max_scroll = rows.saturating_sub(content_area.height as usize);
+    app.details_max_scroll.set(max_scroll);
+    
     let detail_paragraph = Paragraph::new(Text::from(detail_lines))
-        .wrap(ratatui::widgets::Wrap { trim: true });
+        .wrap(ratatui::widgets::Wrap { trim: true })
+        .scroll((app.details_scroll_offset.min(max_scroll) as u16, 0));
     
     f.render_widget(detail_paragraph, content_area);
 }
 
+/// Render aggregate statistics of all workspaces in the details pane
+fn render_statistics_sidebar(f: &mut Frame, app: &App, area: Rect) {
+    let label_style = Style::default().fg(if app.ui_config.use_colors { Color::Yellow } else { Color::White });
+    let value_style = Style::default().fg(if app.ui_config.use_colors { Color::Cyan } else { Color::White });
+    
+    let total = app.workspaces.len();
+    // Workspace paths are parsed when they are loaded
+    let remote = app.workspaces.iter()
+        .filter(|w| w.is_remote_parsed())
+        .count();
+    let missing = (0..total).filter(|&i| !app.workspace_exists_at(i)).count();
+    let most_recent = app.workspaces.iter()
+        .filter(|w| w.last_used > 0)
+        .max_by_key(|w| w.last_used)
+        .map(|w| match w.name.as_deref() {
+            Some(name) if !name.is_empty() => name.to_string(),
+            _ => workspaces::extract_folder_basename(&w.path),
+        })
+        .unwrap_or_else(|| "None".to_string());
+    
+    let mut hosts: Vec<(&String, &usize)> = app.host_counts.iter().collect();
+    hosts.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+    
+    let mut lines = vec![
+        Line::from(Span::styled("No workspace selected", label_style.add_modifier(Modifier::BOLD))),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("Total: ", label_style),
+            Span::styled(total.to_string(), value_style),
+        ]),
+        Line::from(vec![
+            Span::styled("Local: ", label_style),
+            Span::styled((total - remote).to_string(), value_style),
+        ]),
+        Line::from(vec![
+            Span::styled("Remote: ", label_style),
+            Span::styled(remote.to_string(), value_style),
+        ]),
+        Line::from(vec![
+            Span::styled("Missing: ", label_style),
+            Span::styled(
+                missing.to_string(),
+                Style::default().fg(if app.ui_config.use_colors && missing > 0 { Color::Red } else { value_style.fg.unwrap_or(Color::White) }),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("Most Recent: ", label_style),
+            Span::raw(most_recent),
+        ]),
+    ];
+    
+    if !hosts.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled("Top Hosts:", label_style)));
+        for (host, count) in hosts.into_iter().take(3) {
+            lines.push(Line::from(vec![
+                Span::raw("- "),
+                Span::styled(host.clone(), value_style),
+                Span::raw(format!(" ({})", count)),
+            ]));
+        }
+    }
+    
+    let paragraph = Paragraph::new(Text::from(lines))
+        .wrap(ratatui::widgets::Wrap { trim: true });
+    f.render_widget(paragraph, area);
+}
+
+/// Render the editor for the note of the selected workspace
+fn render_notes_editor(f: &mut Frame, app: &App, area: Rect) {
+    let border_color = if app.ui_config.use_colors { Color::Yellow } else { Color::White };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Notes")
+        .border_style(Style::default().fg(border_color));
+    
+    let lines: Vec<Line> = app.notes_buffer.iter()
+        .map(|line| Line::from(line.as_str()))
+        .collect();
+    f.render_widget(Paragraph::new(Text::from(lines)).block(block), area);
+    
+    // Place the cursor at the end of the last line
+    let last_line = app.notes_buffer.last().map(|line| unicode_width::UnicodeWidthStr::width(line.as_str())).unwrap_or(0) as u16;
+    let row = app.notes_buffer.len().saturating_sub(1) as u16;
+    if row + 2 < area.height {
+        f.set_cursor(area.x + 1 + last_line.min(area.width.saturating_sub(3)), area.y + 1 + row);
+    }
+}
+
+/// Render the database entries that could not be parsed in place of the details pane
+fn render_parse_warnings(f: &mut Frame, app: &App, area: Rect) {
+    let border_color = if app.ui_config.use_colors { Color::Red } else { Color::White };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!("Unparsed Entries ({}) - W to close", app.parse_warnings.len()))
+        .border_style(Style::default().fg(border_color));
+    
+    let mut lines = Vec::new();
+    for warning in &app.parse_warnings {
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("{} #{}: ", warning.source, warning.entry_index),
+                Style::default().fg(if app.ui_config.use_colors { Color::Yellow } else { Color::White }),
+            ),
+            Span::raw(warning.reason.clone()),
+        ]));
+        lines.push(Line::from(Span::styled(
+            warning.entry_preview.clone(),
+            Style::default().fg(if app.ui_config.use_colors { Color::DarkGray } else { Color::White }),
+        )));
+    }
+    
+    let paragraph = Paragraph::new(Text::from(lines))
+        .block(block)
+        .wrap(ratatui::widgets::Wrap { trim: false });
+    f.render_widget(paragraph, area);
+}
+
+/// Shorten a note to its first characters on a single line
+fn note_preview(note: &str, max_chars: usize) -> String {
+    let single_line = note.lines().collect::<Vec<_>>().join(" / ");
+    if single_line.chars().count() > max_chars {
+        format!("{}…", single_line.chars().take(max_chars).collect::<String>())
+    } else {
+        single_line
+    }
+}
+
 /// Render the profile selection list
 fn render_profile_selection(f: &mut Frame, app: &App, area: Rect) {
     let items: Vec<ListItem> = if app.known_profile_paths.is_empty() {
@@ -678,7 +1302,10 @@ fn render_profile_selection(f: &mut Frame, app: &App, area: Rect) {
                 let exists = std::path::Path::new(path).exists();
                 let indicator = if exists { "●" } else { "○" };
                 
-                let text = format!("{} {}", indicator, path);
+                let text = match app.named_profiles.iter().find(|profile| &profile.path == path) {
+                    Some(profile) => format!("{} (Named) {}  {}", indicator, profile.name, path),
+                    None => format!("{} {}", indicator, path),
+                };
                 ListItem::new(text).style(style)
             })
             .collect()
@@ -695,11 +1322,16 @@ fn render_profile_selection(f: &mut Frame, app: &App, area: Rect) {
 /// Render the help text
 fn render_help_text(f: &mut Frame, app: &App, area: Rect) {
     let help_text = match app.input_mode {
-        InputMode::Normal => "q: quit, p: set profile, f/: search, r: reload, Enter: toggle item, Ctrl+Alt+A: select/deselect all, Ctrl+Alt+T: toggle each item, d: delete, ↑/↓: navigate",
-        InputMode::ProfilePath => "Enter: save, Esc: cancel",
+        InputMode::Normal => "q: quit, p: set profile, f/: search, Ctrl+P: quick open, G: group by host, H: same host, W: parse warnings, N: edit notes, R: rename, i: inspect, Tab: focus details, g: add to group, r: reload, Enter: toggle item, Ctrl+Alt+A: select/deselect all, Ctrl+Alt+T: toggle each item, d: delete, D: dry-run delete, c: check remote, ↑/↓: navigate, Home/End: first/last",
+        InputMode::ProfilePath => "Enter: save, Tab: complete directory, Esc: cancel",
         InputMode::SelectProfile => "Enter: select profile, c: enter custom path, ↑/↓: navigate, Esc: cancel",
-        InputMode::Searching => "Enter: toggle item, Tab: autocomplete, Ctrl+Alt+A: select/deselect all, Ctrl+Alt+T: toggle each item, ↑/↓: navigate, Esc: exit search, Filters: :existing:yes/no, :type:, :remote:yes/no, :tag:",
+        InputMode::Searching if app.quick_open => "Enter: open workspace, ↑/↓: navigate, Esc: cancel",
+        InputMode::Searching => "Enter: toggle item, Tab: autocomplete, Ctrl+Alt+A: select/deselect all, Ctrl+Alt+T: toggle each item, ↑/↓: navigate, Ctrl+Home/End: first/last, Esc: exit search, Filters: :existing:yes/no, :type:, :remote:yes/no, :tag:, :id:, :minfiles:n, :group:, :host:, :branch:, :source:, :extension:",
         InputMode::ConfirmDelete => "y: confirm, n/Esc: cancel, ↑/↓: navigate through selected workspaces, Enter: unmark selected workspace",
+        InputMode::EditNotes => "Enter: new line, Ctrl+Enter/Ctrl+S: save, Esc: cancel",
+        InputMode::AddToGroup => "Enter: add to group, Esc: cancel",
+        InputMode::Rename => "Enter: rename, Esc: cancel",
+        InputMode::Inspect => "↑/↓: scroll, PgUp/PgDn: scroll page, Home/End: top/bottom, Esc: back",
     };
 
     let help = Paragraph::new(help_text)
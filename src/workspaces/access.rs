@@ -0,0 +1,69 @@
+use anyhow::{Context, Result};
+use log::debug;
+use rusqlite::params;
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::workspaces::activity::{log_activity, ActivitySource};
+use crate::workspaces::database::open_database;
+use crate::workspaces::models::Workspace;
+use crate::workspaces::paths::{app_data_path, create_app_data_path, normalize_path};
+
+/// Name of the access tracking database in the data directory
+const ACCESS_DB_FILE: &str = "access.db";
+
+/// Open the access database, creating it and its table if needed
+fn open_access_database(path: &Path) -> Result<rusqlite::Connection> {
+    let conn = open_database(path)?;
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS workspace_access (
+            path TEXT PRIMARY KEY,
+            open_count INTEGER NOT NULL DEFAULT 0,
+            last_opened INTEGER NOT NULL DEFAULT 0
+        )",
+        [],
+    )
+    .context("Failed to create access table")?;
+
+    Ok(conn)
+}
+
+/// Record that a workspace was opened through this tool, counting it and adding it to the activity log
+pub fn record_workspace_open(workspace_path: &str, source: ActivitySource) -> Result<()> {
+    let path = create_app_data_path(ACCESS_DB_FILE)?;
+    let conn = open_access_database(&path)?;
+
+    let now = chrono::Utc::now().timestamp_millis();
+    conn.execute(
+        "INSERT INTO workspace_access (path, open_count, last_opened) VALUES (?1, 1, ?2)
+         ON CONFLICT(path) DO UPDATE SET open_count = open_count + 1, last_opened = ?2",
+        params![normalize_path(workspace_path), now],
+    )
+    .context("Failed to record workspace open")?;
+
+    debug!("Recorded open of workspace: {}", workspace_path);
+    log_activity(workspace_path, source)
+}
+
+/// Fill `open_count` for workspaces that have been opened through this tool
+pub fn apply_open_counts(workspaces: &mut [Workspace]) -> Result<()> {
+    let path = app_data_path(ACCESS_DB_FILE)?;
+    if !path.exists() {
+        return Ok(());
+    }
+
+    let conn = open_access_database(&path)?;
+    let mut stmt = conn.prepare("SELECT path, open_count FROM workspace_access")?;
+    let counts = stmt
+        .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)))?
+        .collect::<rusqlite::Result<HashMap<String, i64>>>()
+        .context("Failed to read access counts")?;
+
+    for workspace in workspaces.iter_mut() {
+        workspace.open_count = counts
+            .get(&normalize_path(&workspace.path))
+            .map(|&count| count.max(0) as u64);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use anyhow::{Context, Result};
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+
+use crate::workspaces::paths::{app_data_path, create_app_data_path};
+
+/// Where a workspace was opened from
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ActivitySource {
+    Cli,
+    Tauri,
+    Tui,
+}
+
+/// A workspace open recorded in the activity log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivityEntry {
+    /// When the workspace was opened (milliseconds since the epoch)
+    pub timestamp: i64,
+    /// Path or URI that was opened
+    pub path: String,
+    /// Where the workspace was opened from
+    pub source: ActivitySource,
+}
+
+/// Name of the activity log in the data directory
+const ACTIVITY_LOG_FILE: &str = "activity.log";
+
+/// Append a workspace open to the activity log
+pub fn log_activity(workspace_path: &str, source: ActivitySource) -> Result<()> {
+    let path = create_app_data_path(ACTIVITY_LOG_FILE)?;
+
+    let entry = ActivityEntry {
+        timestamp: chrono::Utc::now().timestamp_millis(),
+        path: workspace_path.to_string(),
+        source,
+    };
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open activity log: {}", path.display()))?;
+    writeln!(file, "{}", serde_json::to_string(&entry)?)
+        .with_context(|| format!("Failed to write activity log: {}", path.display()))?;
+
+    debug!("Logged {:?} open of workspace: {}", source, workspace_path);
+    Ok(())
+}
+
+/// Read the activity log, oldest first, keeping entries at or after `since` (milliseconds)
+pub fn read_activity_log(since: Option<i64>) -> Result<Vec<ActivityEntry>> {
+    let path = app_data_path(ACTIVITY_LOG_FILE)?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read activity log: {}", path.display()))?;
+
+    let mut entries = Vec::new();
+    for (number, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        match serde_json::from_str::<ActivityEntry>(line) {
+            Ok(entry) => match since {
+                Some(since) if entry.timestamp < since => {}
+                _ => entries.push(entry),
+            },
+            Err(e) => warn!("Skipping invalid activity log line {}: {}", number + 1, e),
+        }
+    }
+
+    Ok(entries)
+}
@@ -0,0 +1,87 @@
+use anyhow::{Context, Result};
+use log::debug;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::workspaces::models::Workspace;
+use crate::workspaces::paths::{app_data_path, write_app_data_file};
+
+/// A workspace removed from the active list by `archive_workspaces`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedWorkspace {
+    /// The workspace as it was when archived
+    pub workspace: Workspace,
+    /// Profile the workspace was archived from
+    pub profile_path: String,
+    /// When the workspace was archived (milliseconds since the epoch)
+    pub archived_at: i64,
+    /// The raw entry from the recently opened list, restored as-is
+    pub recent_entry: Option<serde_json::Value>,
+    /// Where the workspace storage directory was moved to, if it had one
+    pub archived_storage_dir: Option<String>,
+}
+
+/// Name of the archive file in the data directory
+const ARCHIVE_FILE: &str = "archive.json";
+
+/// Get the directory that archived workspace storage directories are moved to
+pub fn archive_storage_dir() -> Result<PathBuf> {
+    app_data_path("archive")
+}
+
+/// Load all archived workspaces, empty if nothing was archived yet
+pub fn load_archive() -> Result<Vec<ArchivedWorkspace>> {
+    let path = app_data_path(ARCHIVE_FILE)?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read archive: {}", path.display()))?;
+    serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse archive: {}", path.display()))
+}
+
+/// Save the archived workspaces, replacing the archive file
+pub fn save_archive(archived: &[ArchivedWorkspace]) -> Result<()> {
+    write_app_data_file(ARCHIVE_FILE, &serde_json::to_string_pretty(archived)?)
+        .context("Failed to save archive")
+}
+
+/// Move a directory, falling back to copy and delete when renaming fails (e.g. across filesystems)
+pub fn move_dir(from: &Path, to: &Path) -> Result<()> {
+    debug!("Moving {} to {}", from.display(), to.display());
+
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+
+    copy_dir(from, to)?;
+    fs::remove_dir_all(from)
+        .with_context(|| format!("Failed to remove directory: {}", from.display()))
+}
+
+/// Recursively copy a directory
+fn copy_dir(from: &Path, to: &Path) -> Result<()> {
+    fs::create_dir_all(to)
+        .with_context(|| format!("Failed to create directory: {}", to.display()))?;
+
+    for entry in fs::read_dir(from).with_context(|| format!("Failed to read directory: {}", from.display()))? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), &target)
+                .with_context(|| format!("Failed to copy {}", entry.path().display()))?;
+        }
+    }
+
+    Ok(())
+}
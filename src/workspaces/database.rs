@@ -1,15 +1,166 @@
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, Context, Result};
 use log::{debug, info, warn};
+use std::cell::RefCell;
 use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
 use uuid::Uuid;
 
-use crate::workspaces::models::{Workspace, WorkspaceSource};
-use crate::workspaces::paths::normalize_path;
+use crate::workspaces::models::{Workspace, WorkspaceParseWarning, WorkspaceSource};
+use crate::workspaces::paths::{generate_path_variations, normalize_path};
 
-/// Get workspace names and last used times from state database
-pub fn get_workspace_metadata(profile_path: &str, workspaces: &mut Vec<Workspace>) -> Result<()> {
+/// Open a SQLite database configured for concurrent access with a running editor:
+/// WAL journal mode, a 1 second busy timeout and `synchronous=NORMAL` (as VSCode uses)
+pub fn open_database<P: AsRef<Path>>(db_path: P) -> Result<rusqlite::Connection> {
+    open_database_with_flags(db_path, rusqlite::OpenFlags::default())
+}
+
+/// Open a SQLite database with the given flags, configured like `open_database`
+pub fn open_database_with_flags<P: AsRef<Path>>(db_path: P, flags: rusqlite::OpenFlags) -> Result<rusqlite::Connection> {
+    let db_path = db_path.as_ref();
+    let conn = rusqlite::Connection::open_with_flags(db_path, flags)?;
+    
+    conn.busy_timeout(std::time::Duration::from_millis(1000))?;
+    
+    // Switching the journal mode needs a write lock, so don't fail if the database is busy
+    if let Err(e) = conn.pragma_update_and_check(None, "journal_mode", "WAL", |row| row.get::<_, String>(0)) {
+        warn!("Failed to enable WAL mode for {}: {}", db_path.display(), e);
+    }
+    conn.pragma_update(None, "synchronous", "NORMAL")?;
+    
+    Ok(conn)
+}
+
+/// Database connections kept open for a batch operation, so workspaces sharing a
+/// database don't reopen it. Databases are opened read-write and never created.
+#[derive(Default)]
+pub struct DbConnectionPool {
+    connections: RefCell<HashMap<String, Rc<rusqlite::Connection>>>,
+}
+
+impl DbConnectionPool {
+    /// Create an empty pool
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Get the connection to a database, opening it on first use.
+    /// Connections to several databases can be held at the same time.
+    pub fn get(&self, db_path: &str) -> Result<Rc<rusqlite::Connection>> {
+        let mut connections = self.connections.borrow_mut();
+        if let Some(conn) = connections.get(db_path) {
+            return Ok(Rc::clone(conn));
+        }
+        
+        let conn = Rc::new(open_database_with_flags(db_path, rusqlite::OpenFlags::SQLITE_OPEN_READ_WRITE)?);
+        connections.insert(db_path.to_string(), Rc::clone(&conn));
+        Ok(conn)
+    }
+    
+    /// Close all connections, returning the first error.
+    /// Connections still held elsewhere are closed when the last handle is dropped.
+    pub fn close(self) -> Result<()> {
+        let mut result = Ok(());
+        for (db_path, conn) in self.connections.into_inner() {
+            let Ok(conn) = Rc::try_unwrap(conn) else {
+                debug!("Database {} is still in use, closing it later", db_path);
+                continue;
+            };
+            if let Err((_, e)) = conn.close() {
+                warn!("Failed to close database {}: {}", db_path, e);
+                if result.is_ok() {
+                    result = Err(anyhow!("Failed to close database {}: {}", db_path, e));
+                }
+            }
+        }
+        result
+    }
+}
+
+/// Run `f` inside an immediate transaction, committing if it succeeds and rolling back if it fails,
+/// so a write sequence is never left half-applied
+pub fn with_transaction<F, R>(conn: &rusqlite::Connection, f: F) -> Result<R>
+where
+    F: FnOnce(&rusqlite::Connection) -> Result<R>,
+{
+    conn.execute_batch("BEGIN IMMEDIATE")
+        .map_err(|e| anyhow!("Failed to begin transaction: {}", e))?;
+    
+    match f(conn) {
+        Ok(result) => {
+            conn.execute_batch("COMMIT")
+                .map_err(|e| anyhow!("Failed to commit transaction: {}", e))?;
+            Ok(result)
+        }
+        Err(e) => {
+            if let Err(rollback_error) = conn.execute_batch("ROLLBACK") {
+                warn!("Failed to roll back transaction: {}", rollback_error);
+            }
+            Err(e)
+        }
+    }
+}
+
+/// Number of rotating backups kept per database (.bak, .bak.1, .bak.2)
+const BACKUP_COUNT: usize = 3;
+
+/// Whether databases are backed up before the first write
+static BACKUPS_ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// Databases already backed up in this session
+static BACKED_UP_DATABASES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Enable or disable database backups before writes (e.g. for scripted use)
+pub fn set_backups_enabled(enabled: bool) {
+    BACKUPS_ENABLED.store(enabled, Ordering::SeqCst);
+}
+
+/// Back up the database before the first write in this session.
+///
+/// Existing backups are rotated (`.bak` -> `.bak.1` -> `.bak.2`) and the database is copied to
+/// `<db_path>.bak`. Returns an error if the backup can't be written, so the caller can abort the write.
+pub fn backup_database(conn: &rusqlite::Connection, db_path: &str) -> Result<()> {
+    if !BACKUPS_ENABLED.load(Ordering::SeqCst) {
+        return Ok(());
+    }
+    
+    let mut backed_up = BACKED_UP_DATABASES.lock().map_err(|_| anyhow!("Backup registry is poisoned"))?;
+    if backed_up.iter().any(|path| path == db_path) {
+        return Ok(());
+    }
+    
+    // Flush the WAL into the main file so the copy is complete
+    if let Err(e) = conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(())) {
+        warn!("Failed to checkpoint {} before backup: {}", db_path, e);
+    }
+    
+    // Rotate existing backups, dropping the oldest
+    let backup_path = |index: usize| match index {
+        0 => format!("{}.bak", db_path),
+        n => format!("{}.bak.{}", db_path, n),
+    };
+    for index in (0..BACKUP_COUNT - 1).rev() {
+        let from = backup_path(index);
+        if Path::new(&from).exists() {
+            fs::rename(&from, backup_path(index + 1))
+                .map_err(|e| anyhow!("Failed to rotate backup {}: {}", from, e))?;
+        }
+    }
+    
+    fs::copy(db_path, backup_path(0))
+        .map_err(|e| anyhow!("Failed to back up {} (aborting write): {}", db_path, e))?;
+    
+    info!("Backed up {} to {}", db_path, backup_path(0));
+    backed_up.push(db_path.to_string());
+    Ok(())
+}
+
+/// Get workspace names and last used times from state database.
+/// Returns the entries of the recently opened lists that could not be read.
+pub fn get_workspace_metadata(profile_path: &str, workspaces: &mut Vec<Workspace>) -> Result<Vec<WorkspaceParseWarning>> {
     let main_db_path = format!("{}/User/state.vscdb", profile_path);
     info!("Checking for database at path: {}", main_db_path);
     
@@ -59,11 +210,13 @@ pub fn get_workspace_metadata(profile_path: &str, workspaces: &mut Vec<Workspace
     
     // Check and process both databases if they exist
     let mut main_processed = false;
+    let mut warnings = Vec::new();
     
     // Try to get workspace metadata from the main database if it exists and has content
     if main_db_exists && main_db_size > 0 {
         match get_workspace_metadata_from_db(&main_db_path, workspaces, &main_db_relative_path) {
-            Ok(_) => {
+            Ok(main_warnings) => {
+                warnings.extend(main_warnings);
                 main_processed = true;
                 info!("Successfully processed main database");
             },
@@ -80,7 +233,8 @@ pub fn get_workspace_metadata(profile_path: &str, workspaces: &mut Vec<Workspace
     // Now try the alternative database
     if alt_db_exists && alt_db_size > 0 {
         match get_workspace_metadata_from_db(&alt_db_path, workspaces, &alt_db_relative_path) {
-            Ok(_) => {
+            Ok(alt_warnings) => {
+                warnings.extend(alt_warnings);
                 info!("Successfully processed alternative database");
                 if main_processed {
                     info!("Data merged from both databases");
@@ -106,20 +260,48 @@ pub fn get_workspace_metadata(profile_path: &str, workspaces: &mut Vec<Workspace
         }
     }
 
-    Ok(())
+    Ok(warnings)
+}
+
+/// Get workspace metadata from the databases of a named profile directory (`User/profiles/<id>`).
+/// Entries are labelled with their database path relative to the base profile.
+/// Returns the entries of the recently opened lists that could not be read.
+pub fn get_named_profile_metadata(base_profile_path: &str, profile_dir: &str, workspaces: &mut Vec<Workspace>) -> Result<Vec<WorkspaceParseWarning>> {
+    let mut warnings = Vec::new();
+    for db_path in [
+        format!("{}/state.vscdb", profile_dir),
+        format!("{}/globalStorage/state.vscdb", profile_dir),
+    ] {
+        let size = fs::metadata(&db_path).map(|m| m.len()).unwrap_or(0);
+        if size == 0 {
+            continue;
+        }
+        
+        let db_source = match db_path.strip_prefix(base_profile_path) {
+            Some(stripped) => stripped.trim_start_matches('/').to_string(),
+            None => db_path.clone(),
+        };
+        match get_workspace_metadata_from_db(&db_path, workspaces, &db_source) {
+            Ok(db_warnings) => warnings.extend(db_warnings),
+            Err(e) => warn!("Failed to process named profile database {}: {}", db_path, e),
+        }
+    }
+    
+    Ok(warnings)
 }
 
-/// Helper function to extract metadata from a database file
-fn get_workspace_metadata_from_db(db_path: &str, workspaces: &mut Vec<Workspace>, db_source: &str) -> Result<()> {
+/// Helper function to extract metadata from a database file.
+/// Returns the entries of the recently opened list that could not be read.
+fn get_workspace_metadata_from_db(db_path: &str, workspaces: &mut Vec<Workspace>, db_source: &str) -> Result<Vec<WorkspaceParseWarning>> {
     info!("Opening database connection: {}", db_path);
-    let conn = match rusqlite::Connection::open(db_path) {
+    let conn = match open_database(db_path) {
         Ok(conn) => {
             info!("Successfully opened database connection");
             conn
         },
         Err(e) => {
             warn!("Failed to open database: {}", e);
-            return Ok(());
+            return Ok(Vec::new());
         }
     };
     
@@ -136,12 +318,13 @@ fn get_workspace_metadata_from_db(db_path: &str, workspaces: &mut Vec<Workspace>
     
     if !table_names.contains(&"ItemTable".to_string()) {
         warn!("ItemTable not found in database, cannot retrieve workspace history");
-        return Ok(());
+        return Ok(Vec::new());
     }
     
     info!("Looking for history.recentlyOpenedPathsList in ItemTable");
     
     // Try to find and process workspaces from the history.recentlyOpenedPathsList key
+    let mut warnings = Vec::new();
     match conn.query_row(
         "SELECT value FROM ItemTable WHERE key = ?",
         ["history.recentlyOpenedPathsList"],
@@ -149,29 +332,188 @@ fn get_workspace_metadata_from_db(db_path: &str, workspaces: &mut Vec<Workspace>
     ) {
         Ok(value) => {
             info!("Found history.recentlyOpenedPathsList entry");
-            let count = process_workspace_rows(value, workspaces, db_source);
+            let (count, row_warnings) = process_workspace_rows(value, workspaces, db_source);
             info!("Processed {} workspaces from history.recentlyOpenedPathsList", count);
+            if !row_warnings.is_empty() {
+                warn!("{} entries of {} could not be parsed", row_warnings.len(), db_source);
+            }
+            warnings = row_warnings;
         }
         Err(e) => {
             warn!("Failed to retrieve history.recentlyOpenedPathsList from database: {}", e);
         }
     }
     
-    Ok(())
+    // Colors assigned to workspaces in the recently opened list
+    match workspace_colors(&conn) {
+        Ok(colors) if !colors.is_empty() => {
+            let count = apply_workspace_colors(&colors, workspaces);
+            info!("Applied {} workspace colors", count);
+        }
+        Ok(_) => {}
+        Err(e) => warn!("Failed to read workspace colors: {}", e),
+    }
+    
+    // Count the files opened in each workspace from the editor history
+    if let Ok(value) = conn.query_row(
+        "SELECT value FROM ItemTable WHERE key = ?",
+        ["workbench.editor.history"],
+        |row| row.get::<_, String>(0)
+    ) {
+        let count = apply_editor_history(&value, workspaces);
+        info!("Processed {} files from workbench.editor.history", count);
+    }
+    
+    Ok(warnings)
+}
+
+/// List the keys of a state database's `ItemTable` with the size of their values, sorted by key.
+/// Meant for diagnostics, to see what an editor installation stores.
+pub fn list_all_database_keys(db_path: &str) -> Result<Vec<(String, usize)>> {
+    // Opening a missing database would create it
+    if !Path::new(db_path).is_file() {
+        return Err(anyhow!("Database {} does not exist", db_path));
+    }
+    let conn = open_database(db_path)?;
+    
+    let mut stmt = conn.prepare("SELECT key, length(value) FROM ItemTable ORDER BY key")?;
+    let keys = stmt.query_map([], |row| {
+        let size: Option<i64> = row.get(1)?;
+        Ok((row.get::<_, String>(0)?, size.unwrap_or(0) as usize))
+    })?.collect::<rusqlite::Result<Vec<_>>>()?;
+    
+    Ok(keys)
+}
+
+/// Read the colors assigned to workspaces from the recently opened list of a state database.
+/// Returns a map of workspace path (or URI) to readable color name.
+#[allow(dead_code)]
+pub fn read_workspace_colors(db_path: &str) -> Result<HashMap<String, String>> {
+    let conn = open_database(db_path)?;
+    workspace_colors(&conn)
+}
+
+/// Read the `color` of the entries of `history.recentlyOpenedPathsList`; entries
+/// without one are skipped. VSCode theme color IDs are turned into readable names.
+fn workspace_colors(conn: &rusqlite::Connection) -> Result<HashMap<String, String>> {
+    let value: String = match conn.query_row(
+        "SELECT value FROM ItemTable WHERE key = ?",
+        ["history.recentlyOpenedPathsList"],
+        |row| row.get(0),
+    ) {
+        Ok(value) => value,
+        Err(rusqlite::Error::QueryReturnedNoRows) => return Ok(HashMap::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let value: serde_json::Value = serde_json::from_str(&value)
+        .context("Failed to parse history.recentlyOpenedPathsList")?;
+    
+    let mut colors = HashMap::new();
+    for entry in value.get("entries").and_then(|e| e.as_array()).into_iter().flatten() {
+        let Some(color) = entry.get("color").and_then(|c| c.as_str()) else {
+            continue;
+        };
+        let path = entry.get("folderUri").and_then(|u| u.as_str())
+            .or_else(|| entry.get("workspace").and_then(|w| w.get("configPath")).and_then(|p| p.as_str()));
+        if let Some(path) = path {
+            colors.insert(path.to_string(), color_name(color));
+        }
+    }
+    
+    Ok(colors)
+}
+
+/// Set the color of the workspaces found in a map of path to color. Returns the number set.
+fn apply_workspace_colors(colors: &HashMap<String, String>, workspaces: &mut [Workspace]) -> usize {
+    let colors: HashMap<String, &String> = colors.iter()
+        .map(|(path, color)| (normalize_path(path), color))
+        .collect();
+    
+    let mut count = 0;
+    for workspace in workspaces.iter_mut() {
+        if let Some(color) = colors.get(&normalize_path(&workspace.path)) {
+            workspace.color = Some(color.to_string());
+            count += 1;
+        }
+    }
+    count
+}
+
+/// Turn a VSCode theme color ID (e.g. `terminal.ansiBrightBlue`, `charts.orange`) into a
+/// readable name (`bright blue`, `orange`). Other values are kept as they are.
+pub fn color_name(color_id: &str) -> String {
+    let name = color_id
+        .strip_prefix("terminal.ansi")
+        .or_else(|| color_id.strip_prefix("charts."))
+        .unwrap_or(color_id);
+    
+    match name.strip_prefix("Bright") {
+        Some(base) => format!("bright {}", base.to_lowercase()),
+        None if name == color_id => name.to_string(),
+        None => name.to_lowercase(),
+    }
+}
+
+/// Set the `recent_file_count` of each workspace from `workbench.editor.history`, a list of
+/// recently opened file URIs (or objects with a `resource` URI). Returns the number of files read.
+fn apply_editor_history(history: &str, workspaces: &mut [Workspace]) -> usize {
+    let value: serde_json::Value = match serde_json::from_str(history) {
+        Ok(value) => value,
+        Err(e) => {
+            warn!("Failed to parse JSON from workbench.editor.history: {}", e);
+            return 0;
+        }
+    };
+    
+    let Some(entries) = value.as_array().or_else(|| value.get("entries").and_then(|e| e.as_array())) else {
+        warn!("Expected an array in workbench.editor.history but got: {}", value);
+        return 0;
+    };
+    
+    let files: Vec<String> = entries.iter()
+        .filter_map(history_entry_resource)
+        .map(normalize_path)
+        .collect();
+    
+    for workspace in workspaces.iter_mut() {
+        let folder = normalize_path(&workspace.path);
+        let folder = folder.trim_end_matches('/');
+        if folder.is_empty() {
+            continue;
+        }
+        
+        let count = files.iter()
+            .filter(|file| file.strip_prefix(folder).is_some_and(|rest| rest.starts_with('/')))
+            .count();
+        // Several databases can list the same history, so don't add them up
+        workspace.recent_file_count = workspace.recent_file_count.max(count);
+    }
+    
+    files.len()
+}
+
+/// Get the file URI of an editor history entry
+fn history_entry_resource(entry: &serde_json::Value) -> Option<&str> {
+    entry.as_str()
+        .or_else(|| entry.get("resource").and_then(|r| r.as_str()))
+        .or_else(|| entry.get("editor").and_then(|e| e.get("resource")).and_then(|r| r.as_str()))
 }
 
 // Helper function to process workspace rows from the database
-// Returns the number of rows processed successfully
-fn process_workspace_rows(rows: String, workspaces: &mut Vec<Workspace>, db_source: &str) -> usize {
+// Returns the number of rows processed successfully and a warning for each row that could not be
+fn process_workspace_rows(rows: String, workspaces: &mut Vec<Workspace>, db_source: &str) -> (usize, Vec<WorkspaceParseWarning>) {
     debug!("Processing history.recentlyOpenedPathsList");
     
-    // Create a map of workspace paths to their indices
+    // Create a map of workspace path variations to their indices
     let mut path_to_index = HashMap::new();
     for (i, workspace) in workspaces.iter().enumerate() {
-        path_to_index.insert(workspace.path.clone(), i);
+        for variation in generate_path_variations(&workspace.path) {
+            path_to_index.entry(variation).or_insert(i);
+        }
     }
     
     let mut processed_count = 0;
+    let mut warnings = Vec::new();
     
     match serde_json::from_str::<serde_json::Value>(&rows) {
         Ok(value) => {
@@ -185,21 +527,35 @@ fn process_workspace_rows(rows: String, workspaces: &mut Vec<Workspace>, db_sour
                     debug!("Processing entry {}: {:?}", i, entry);
                     
                     // Use db_source directly without adding "/entry-i" suffix
-                    if process_workspace_entry(entry, workspaces, &mut path_to_index, db_source) {
-                        processed_count += 1;
+                    match process_workspace_entry(entry, workspaces, &mut path_to_index, db_source) {
+                        Ok(true) => processed_count += 1,
+                        Ok(false) => {}
+                        Err(reason) => warnings.push(WorkspaceParseWarning::new(i, &entry.to_string(), reason, db_source)),
                     }
                 }
             } else {
                 warn!("Expected 'entries' array in history.recentlyOpenedPathsList but got: {}", value);
+                warnings.push(WorkspaceParseWarning::new(
+                    0,
+                    &value.to_string(),
+                    "history.recentlyOpenedPathsList has no 'entries' array".to_string(),
+                    db_source,
+                ));
             }
         }
         Err(e) => {
             warn!("Failed to parse JSON from history.recentlyOpenedPathsList: {}", e);
+            warnings.push(WorkspaceParseWarning::new(
+                0,
+                &rows,
+                format!("history.recentlyOpenedPathsList is not valid JSON: {}", e),
+                db_source,
+            ));
         }
     }
     
     info!("Processed {} workspaces from history.recentlyOpenedPathsList", processed_count);
-    processed_count
+    (processed_count, warnings)
 }
 
 // Helper function to check if paths would match after normalization
@@ -212,7 +568,7 @@ fn check_path_matching(db_path: &str, workspace_paths: &[String]) -> bool {
     
     // Try adding/removing file:// prefix
     let alt_path = if db_path.starts_with("file://") {
-        db_path.replace("file://", "")
+        crate::workspaces::paths::path_from_uri(db_path).unwrap_or_else(|_| db_path.to_string())
     } else {
         format!("file://{}", db_path)
     };
@@ -253,49 +609,52 @@ fn check_path_matching(db_path: &str, workspace_paths: &[String]) -> bool {
 }
 
 /// Process a workspace entry from the database
+///
+/// Returns whether a workspace was added or updated (file entries are skipped), or why the
+/// entry could not be read.
 fn process_workspace_entry(
     entry: &serde_json::Value,
     workspaces: &mut Vec<Workspace>,
     workspace_map: &mut HashMap<String, usize>,
     source_identifier: &str
-) -> bool {
-    let mut processed = false;
-    
+) -> Result<bool, String> {
     // Extract the workspace path from potential fields: folderUri, fileUri, workspace
-    let path = if let Some(folder_uri) = entry.get("folderUri").and_then(|u| u.as_str()) {
+    let workspace_path = if let Some(folder_uri) = entry.get("folderUri").and_then(|u| u.as_str()) {
         debug!("Found folderUri: {}", folder_uri);
-        Some(folder_uri)
+        folder_uri
     } else if let Some(file_uri) = entry.get("fileUri").and_then(|u| u.as_str()) {
         debug!("Found fileUri (skipping as it's a file, not a workspace): {}", file_uri);
         // Skip files, only process folders and workspaces
-        return false;
+        return Ok(false);
     } else if let Some(workspace) = entry.get("workspace") {
         // This is a workspace entry with a workspace object
         if let Some(workspace_uri) = workspace.get("uri").and_then(|u| u.as_str()) {
             debug!("Found workspace uri: {}", workspace_uri);
-            Some(workspace_uri)
+            workspace_uri
         } else if let Some(config_path) = workspace.get("configPath").and_then(|p| p.as_str()) {
             debug!("Found workspace configPath: {}", config_path);
-            Some(config_path)
+            config_path
         } else {
             warn!("Workspace entry missing uri and configPath: {:?}", workspace);
-            None
+            return Err("workspace entry has no uri or configPath".to_string());
         }
     } else {
         warn!("Entry is missing folderUri, fileUri, and workspace fields: {:?}", entry);
-        None
+        return Err("entry has no folderUri, fileUri or workspace".to_string());
     };
     
-    if let Some(workspace_path) = path {
-        // Extract name and last_used from the entry
-        let name = entry.get("name").and_then(|n| n.as_str()).map(|s| s.to_string());
-        let last_used = entry.get("lastUsed").and_then(|t| t.as_i64()).unwrap_or(0);
-
-        // Process the workspace with the extracted data
-        processed = process_workspace_details(workspace_path, name.unwrap_or_default().as_str(), last_used, workspaces, workspace_map, source_identifier);
+    if workspace_path.trim().is_empty() {
+        return Err("entry has an empty path".to_string());
     }
     
-    processed
+    // Extract name and last_used from the entry
+    let name = entry.get("name").and_then(|n| n.as_str()).map(|s| s.to_string());
+    let last_used = entry.get("lastUsed").and_then(|t| t.as_i64()).unwrap_or(0);
+    // Some VSCode versions mark recent items pinned by the user
+    let pinned = entry.get("pinned").and_then(|p| p.as_bool()).unwrap_or(false);
+
+    // Process the workspace with the extracted data
+    Ok(process_workspace_details(workspace_path, name.unwrap_or_default().as_str(), last_used, pinned, workspaces, workspace_map, source_identifier))
 }
 
 /// Process a workspace's details, creating or updating a workspace entry
@@ -303,6 +662,7 @@ fn process_workspace_details(
     workspace_path: &str, 
     workspace_name: &str, 
     workspace_last_used: i64, 
+    workspace_pinned: bool,
     workspaces: &mut Vec<Workspace>, 
     workspace_map: &mut HashMap<String, usize>,
     source_identifier: &str
@@ -319,26 +679,12 @@ fn process_workspace_details(
         debug!("  Map key: {}", key);
     }
     
-    // First try to find an exact match using normalized path
-    let mut found_idx = None;
-    if let Some(&idx) = workspace_map.get(&normalized_path) {
-        debug!("Found exact path match at index {} for path {}", idx, normalized_path);
-        found_idx = Some(idx);
-    } else {
-        debug!("No match found for normalized path: {}", normalized_path);
-        // Also check if there's a workspace with this path already
-        for (i, workspace) in workspaces.iter().enumerate() {
-            let existing_normalized = normalize_path(&workspace.path);
-            debug!("Comparing with existing workspace {} - original: {}, normalized: {}", 
-                  i, workspace.path, existing_normalized);
-            if existing_normalized == normalized_path {
-                debug!("Found matching workspace at index {}", i);
-                found_idx = Some(i);
-                // Update the map with the normalized path
-                workspace_map.insert(normalized_path.clone(), i);
-                break;
-            }
-        }
+    // Look up every variation of the path (normalized, case-insensitive, ...)
+    let variations = generate_path_variations(workspace_path);
+    let found_idx = variations.iter().find_map(|variation| workspace_map.get(variation).copied());
+    match found_idx {
+        Some(idx) => debug!("Found path match at index {} for path {}", idx, normalized_path),
+        None => debug!("No match found for normalized path: {}", normalized_path),
     }
     
     // Create a database source with the identifier
@@ -362,6 +708,9 @@ fn process_workspace_details(
             workspace.last_used = workspace_last_used;
         }
         
+        // Pinned in any database means pinned
+        workspace.pinned |= workspace_pinned;
+        
         // Add the database source to the sources list if it's not already there
         if !workspace.sources.iter().any(|src| matches!(src, WorkspaceSource::Database(_))) {
             workspace.sources.push(db_source);
@@ -384,15 +733,197 @@ fn process_workspace_details(
             storage_path: None,
             sources: vec![db_source],
             parsed_info: None,
+            pinned: workspace_pinned,
+            exists: false,
+            exists_checked: false,
+            open_count: None,
+            recent_file_count: 0,
+            git_branch: None,
+            color: None,
+            recommended_extensions: Vec::new(),
         };
         
         // Add the new workspace to the list
         workspaces.push(workspace);
         
-        // Update the map with the new index using normalized path
+        // Update the map with the new index using all path variations
         let new_idx = workspaces.len() - 1;
-        workspace_map.insert(normalized_path, new_idx);
+        for variation in variations {
+            workspace_map.entry(variation).or_insert(new_idx);
+        }
         
         true
     }
-} 
\ No newline at end of file
+} 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// history.recentlyOpenedPathsList value exported from a VSCode state database
+    const RECENTLY_OPENED_FIXTURE: &str = include_str!("../../tests/fixtures/recently_opened_paths_list.json");
+
+    /// Create a state database containing the fixture
+    fn create_fixture_database(name: &str) -> String {
+        let db_path = std::env::temp_dir()
+            .join(format!("vscode-workspaces-editor-{}-{}.vscdb", name, std::process::id()));
+        let _ = fs::remove_file(&db_path);
+
+        let conn = rusqlite::Connection::open(&db_path).unwrap();
+        conn.execute_batch("CREATE TABLE ItemTable (key TEXT UNIQUE ON CONFLICT REPLACE, value BLOB)").unwrap();
+        conn.execute(
+            "INSERT INTO ItemTable (key, value) VALUES (?, ?)",
+            ["history.recentlyOpenedPathsList", RECENTLY_OPENED_FIXTURE],
+        ).unwrap();
+
+        db_path.to_string_lossy().to_string()
+    }
+
+    #[test]
+    fn test_pinned_entries_from_database() {
+        let db_path = create_fixture_database("pinned");
+
+        // A workspace already known from storage
+        let mut workspaces = vec![Workspace {
+            storage_path: Some("workspaceStorage/storage-web/workspace.json".to_string()),
+            sources: vec![WorkspaceSource::Storage("workspaceStorage/storage-web/workspace.json".to_string())],
+            ..Workspace::test_fixture("storage-web", "/home/alice/projects/web")
+        }];
+
+        get_workspace_metadata_from_db(&db_path, &mut workspaces, "User/state.vscdb").unwrap();
+        fs::remove_file(&db_path).unwrap();
+
+        let pinned = |path: &str| workspaces.iter()
+            .find(|ws| ws.path == path)
+            .unwrap_or_else(|| panic!("workspace {} not found", path))
+            .pinned;
+
+        // File entries are skipped
+        assert_eq!(workspaces.len(), 4);
+        assert!(pinned("file:///home/alice/projects/api"));
+        assert!(!pinned("/home/alice/projects/web"));
+        assert!(pinned("file:///home/alice/work/team.code-workspace"));
+        assert!(!pinned("vscode-remote://ssh-remote%2Bdevbox/srv/app"));
+    }
+
+    #[test]
+    fn test_workspace_colors() {
+        let db_path = create_fixture_database("colors");
+        let conn = rusqlite::Connection::open(&db_path).unwrap();
+        conn.execute(
+            "INSERT INTO ItemTable (key, value) VALUES (?, ?)",
+            ["history.recentlyOpenedPathsList", r#"{"entries": [
+                {"folderUri": "file:///home/alice/projects/api", "color": "terminal.ansiBrightBlue"},
+                {"folderUri": "file:///home/alice/projects/web", "color": "charts.orange"},
+                {"folderUri": "file:///home/alice/projects/docs"}
+            ]}"#],
+        ).unwrap();
+        drop(conn);
+
+        let colors = read_workspace_colors(&db_path).unwrap();
+        fs::remove_file(&db_path).unwrap();
+
+        assert_eq!(colors.len(), 2);
+        assert_eq!(colors["file:///home/alice/projects/api"], "bright blue");
+        assert_eq!(colors["file:///home/alice/projects/web"], "orange");
+        assert_eq!(color_name("#ff0000"), "#ff0000");
+    }
+
+    #[test]
+    fn test_list_all_database_keys() {
+        let db_path = create_fixture_database("keys");
+        let conn = rusqlite::Connection::open(&db_path).unwrap();
+        conn.execute("INSERT INTO ItemTable (key, value) VALUES (?, ?)", ["colorThemeData", "{}"]).unwrap();
+        drop(conn);
+
+        let keys = list_all_database_keys(&db_path).unwrap();
+        fs::remove_file(&db_path).unwrap();
+
+        assert_eq!(keys.len(), 2);
+        assert_eq!(keys[0], ("colorThemeData".to_string(), 2));
+        assert_eq!(keys[1].0, "history.recentlyOpenedPathsList");
+        assert_eq!(keys[1].1, RECENTLY_OPENED_FIXTURE.len());
+        assert!(list_all_database_keys(&db_path).is_err());
+    }
+
+    #[test]
+    fn test_with_transaction_rolls_back_on_error() {
+        let db_path = create_fixture_database("transaction");
+        let conn = rusqlite::Connection::open(&db_path).unwrap();
+
+        let result: Result<()> = with_transaction(&conn, |conn| {
+            conn.execute("UPDATE ItemTable SET value = 'broken' WHERE key = ?", ["history.recentlyOpenedPathsList"])?;
+            Err(anyhow!("interrupted"))
+        });
+        assert!(result.is_err());
+
+        let value: String = conn.query_row(
+            "SELECT value FROM ItemTable WHERE key = ?",
+            ["history.recentlyOpenedPathsList"],
+            |row| row.get(0),
+        ).unwrap();
+        drop(conn);
+        fs::remove_file(&db_path).unwrap();
+
+        assert_eq!(value, RECENTLY_OPENED_FIXTURE);
+    }
+
+    #[test]
+    fn test_unreadable_entries_are_reported() {
+        let rows = r#"{"entries": [
+            {"folderUri": "file:///home/alice/projects/web"},
+            {"fileUri": "file:///home/alice/notes.txt"},
+            {"label": "no path"},
+            {"workspace": {"id": "abc"}}
+        ]}"#;
+
+        let mut workspaces = Vec::new();
+        let (count, warnings) = process_workspace_rows(rows.to_string(), &mut workspaces, "User/state.vscdb");
+
+        assert_eq!(count, 1);
+        assert_eq!(warnings.iter().map(|w| w.entry_index).collect::<Vec<_>>(), vec![2, 3]);
+        assert!(warnings[0].entry_preview.contains("no path"));
+        assert_eq!(warnings[1].source, "User/state.vscdb");
+    }
+
+    #[test]
+    fn test_recent_file_count_from_editor_history() {
+        let workspace = |path: &str| Workspace::test_fixture(path, path);
+        let mut workspaces = vec![
+            workspace("/home/alice/projects/web"),
+            workspace("file:///home/alice/projects/api"),
+        ];
+
+        let history = r#"[
+            "file:///home/alice/projects/web/index.html",
+            {"resource": "file:///home/alice/projects/web/src/app.ts"},
+            {"editor": {"resource": "file:///home/alice/projects/api/main.rs"}},
+            "file:///home/alice/projects/website/index.html"
+        ]"#;
+        assert_eq!(apply_editor_history(history, &mut workspaces), 4);
+
+        assert_eq!(workspaces[0].recent_file_count, 2);
+        assert_eq!(workspaces[1].recent_file_count, 1);
+    }
+
+    #[test]
+    fn test_pool_holds_several_connections() {
+        let first_path = create_fixture_database("pool-first");
+        let second_path = create_fixture_database("pool-second");
+        let count = |conn: &rusqlite::Connection| -> i64 {
+            conn.query_row("SELECT COUNT(*) FROM ItemTable", [], |row| row.get(0)).unwrap()
+        };
+
+        let pool = DbConnectionPool::new();
+        let first = pool.get(&first_path).unwrap();
+        let second = pool.get(&second_path).unwrap();
+        let first_again = pool.get(&first_path).unwrap();
+        assert_eq!(count(&first), 1);
+        assert_eq!(count(&second), 1);
+        assert!(Rc::ptr_eq(&first, &first_again));
+
+        drop((first, second, first_again));
+        pool.close().unwrap();
+        let _ = fs::remove_file(&first_path);
+        let _ = fs::remove_file(&second_path);
+    }
+}
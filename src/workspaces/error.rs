@@ -13,4 +13,30 @@ pub enum WorkspaceError {
     Database(String),
     #[error("Failed to write workspace file: {0}")]
     Write(String),
-} 
\ No newline at end of file
+    #[error("Workspace already exists: {0}")]
+    AlreadyExists(String),
+    #[error("Invalid workspace path: {0}")]
+    InvalidPath(String),
+    #[error("Workspace not found: {0}")]
+    NotFound(String),
+    #[error("Profile not found: {0}")]
+    ProfileNotFound(String),
+}
+
+#[allow(dead_code)]
+impl WorkspaceError {
+    /// Stable error code for frontends (e.g. `ALREADY_EXISTS`)
+    pub fn code(&self) -> &'static str {
+        match self {
+            WorkspaceError::HomeDir => "HOME_DIR",
+            WorkspaceError::Read(_) => "READ_ERROR",
+            WorkspaceError::Parse(_) => "PARSE_ERROR",
+            WorkspaceError::Database(_) => "DATABASE_ERROR",
+            WorkspaceError::Write(_) => "WRITE_ERROR",
+            WorkspaceError::AlreadyExists(_) => "ALREADY_EXISTS",
+            WorkspaceError::InvalidPath(_) => "INVALID_PATH",
+            WorkspaceError::NotFound(_) => "NOT_FOUND",
+            WorkspaceError::ProfileNotFound(_) => "PROFILE_NOT_FOUND",
+        }
+    }
+} 
@@ -0,0 +1,94 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+use crate::workspaces::error::WorkspaceError;
+use crate::workspaces::paths::{app_data_path, normalize_path, write_app_data_file};
+
+/// A user-defined collection of workspaces (e.g. by project, team or client)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkspaceGroup {
+    /// Name of the group
+    pub name: String,
+    /// Normalized paths of the workspaces in the group
+    pub workspace_paths: Vec<String>,
+    /// Color used to show the group
+    #[serde(default)]
+    pub color: Option<String>,
+}
+
+impl WorkspaceGroup {
+    /// Check whether a workspace belongs to the group
+    pub fn contains(&self, workspace_path: &str) -> bool {
+        let normalized = normalize_path(workspace_path);
+        self.workspace_paths.contains(&normalized)
+    }
+}
+
+/// Name of the groups file in the data directory
+const GROUPS_FILE: &str = "groups.json";
+
+/// Get all workspace groups
+pub fn list_groups() -> Result<Vec<WorkspaceGroup>> {
+    let path = app_data_path(GROUPS_FILE)?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read groups: {}", path.display()))?;
+    serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse groups: {}", path.display()))
+}
+
+/// Save the workspace groups, replacing the groups file
+fn save_groups(groups: &[WorkspaceGroup]) -> Result<()> {
+    write_app_data_file(GROUPS_FILE, &serde_json::to_string_pretty(groups)?)
+        .context("Failed to save groups")
+}
+
+/// Create an empty group. Fails with `WorkspaceError::AlreadyExists` if the name is taken.
+pub fn create_group(name: &str, color: Option<String>) -> Result<()> {
+    let name = name.trim();
+    if name.is_empty() || name.contains(char::is_whitespace) {
+        anyhow::bail!("Invalid group name '{}': it must be a single word", name);
+    }
+
+    let mut groups = list_groups()?;
+    if groups.iter().any(|group| group.name.eq_ignore_ascii_case(name)) {
+        return Err(WorkspaceError::AlreadyExists(format!("group {}", name)).into());
+    }
+
+    groups.push(WorkspaceGroup {
+        name: name.to_string(),
+        workspace_paths: Vec::new(),
+        color,
+    });
+    save_groups(&groups)
+}
+
+/// Add a workspace to a group. Fails with `WorkspaceError::NotFound` if the group doesn't exist.
+pub fn add_to_group(name: &str, workspace_path: &str) -> Result<()> {
+    let mut groups = list_groups()?;
+    let group = groups.iter_mut()
+        .find(|group| group.name.eq_ignore_ascii_case(name))
+        .ok_or_else(|| WorkspaceError::NotFound(format!("group {}", name)))?;
+
+    if !group.contains(workspace_path) {
+        group.workspace_paths.push(normalize_path(workspace_path));
+    }
+    save_groups(&groups)
+}
+
+/// Remove a workspace from a group. Fails with `WorkspaceError::NotFound` if the group doesn't exist.
+#[allow(dead_code)]
+pub fn remove_from_group(name: &str, workspace_path: &str) -> Result<()> {
+    let mut groups = list_groups()?;
+    let group = groups.iter_mut()
+        .find(|group| group.name.eq_ignore_ascii_case(name))
+        .ok_or_else(|| WorkspaceError::NotFound(format!("group {}", name)))?;
+
+    let normalized = normalize_path(workspace_path);
+    group.workspace_paths.retain(|path| *path != normalized);
+    save_groups(&groups)
+}
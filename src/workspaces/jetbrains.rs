@@ -0,0 +1,232 @@
+use anyhow::{Context, Result};
+use directories::BaseDirs;
+use home::home_dir;
+use log::{debug, info, warn};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::reader::Reader;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::workspaces::models::{SortOrder, Workspace, WorkspaceSource};
+
+/// Database source name of JetBrains recent projects
+pub const JETBRAINS_SOURCE: &str = "jetbrains";
+
+/// Whether JetBrains recent projects are merged into every workspace list
+static INCLUDE_JETBRAINS: AtomicBool = AtomicBool::new(false);
+
+/// Merge JetBrains recent projects into loaded workspaces (e.g. from `--include-jetbrains`)
+pub fn set_include_jetbrains(include: bool) {
+    INCLUDE_JETBRAINS.store(include, Ordering::Relaxed);
+}
+
+/// Check whether JetBrains recent projects should be merged into loaded workspaces
+pub fn include_jetbrains() -> bool {
+    INCLUDE_JETBRAINS.load(Ordering::Relaxed)
+}
+
+/// Get the JetBrains configuration directory for the current platform
+/// (e.g. ~/.config/JetBrains, ~/Library/Application Support/JetBrains or %APPDATA%\JetBrains)
+fn get_jetbrains_config_dir() -> Result<PathBuf> {
+    let base_dirs = BaseDirs::new().context("Could not find home directory")?;
+    Ok(base_dirs.config_dir().join("JetBrains"))
+}
+
+/// Get the recent projects of all installed JetBrains IDEs, most recently opened first.
+/// Projects known to several IDEs or versions are listed once, with their latest use.
+pub fn get_jetbrains_workspaces() -> Result<Vec<Workspace>> {
+    let config_dir = get_jetbrains_config_dir()?;
+    info!("Looking for JetBrains recent projects in: {}", config_dir.display());
+
+    if !config_dir.is_dir() {
+        debug!("JetBrains config directory does not exist: {}", config_dir.display());
+        return Ok(Vec::new());
+    }
+
+    let mut projects: HashMap<String, RecentProject> = HashMap::new();
+    for entry in std::fs::read_dir(&config_dir)?.flatten() {
+        let file = entry.path().join("options").join("recentProjects.xml");
+        if !file.exists() {
+            continue;
+        }
+
+        match read_recent_projects(&file) {
+            Ok(found) => {
+                info!("Found {} recent projects in {}", found.len(), file.display());
+                for project in found {
+                    match projects.get_mut(&project.path) {
+                        Some(existing) if existing.last_used >= project.last_used => {}
+                        _ => {
+                            projects.insert(project.path.clone(), project);
+                        }
+                    }
+                }
+            }
+            Err(e) => warn!("Failed to read JetBrains recent projects {}: {}", file.display(), e),
+        }
+    }
+
+    let mut workspaces: Vec<Workspace> = projects.into_values().map(to_workspace).collect();
+    SortOrder::default().sort(&mut workspaces);
+    Ok(workspaces)
+}
+
+/// A project read from recentProjects.xml
+#[derive(Debug, Default)]
+struct RecentProject {
+    path: String,
+    name: Option<String>,
+    last_used: i64,
+}
+
+/// Read the projects of a recentProjects.xml file.
+///
+/// Recent IDE versions list projects as `<entry key="path">` in the `additionalInfo` map,
+/// older ones as `<option value="path" />` in the `recentPaths` list.
+fn read_recent_projects(file: &Path) -> Result<Vec<RecentProject>> {
+    let content = std::fs::read_to_string(file)
+        .with_context(|| format!("Failed to read {}", file.display()))?;
+    let home = home_dir().map(|home| home.to_string_lossy().to_string()).unwrap_or_default();
+
+    let mut reader = Reader::from_str(&content);
+    let mut projects = Vec::new();
+    let mut current: Option<RecentProject> = None;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => match e.name().as_ref() {
+                b"entry" => {
+                    if let Some(key) = attribute(&e, b"key") {
+                        projects.extend(current.take());
+                        current = Some(RecentProject {
+                            path: expand_path_macros(&key, &home),
+                            ..Default::default()
+                        });
+                    }
+                }
+                b"option" => read_option(&e, &mut current, &mut projects, &home),
+                _ => {}
+            },
+            Ok(Event::Empty(e)) if e.name().as_ref() == b"option" => {
+                read_option(&e, &mut current, &mut projects, &home);
+            }
+            Ok(Event::End(e)) if e.name().as_ref() == b"entry" => {
+                projects.extend(current.take());
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => {
+                return Err(anyhow::anyhow!(
+                    "Invalid XML at position {}: {}",
+                    reader.buffer_position(),
+                    e
+                ))
+            }
+            _ => {}
+        }
+    }
+    projects.extend(current);
+
+    Ok(projects)
+}
+
+/// Read an `<option>` element, either a property of the current project or a legacy project path
+fn read_option(e: &BytesStart, current: &mut Option<RecentProject>, projects: &mut Vec<RecentProject>, home: &str) {
+    let Some(value) = attribute(e, b"value") else {
+        return;
+    };
+
+    match (attribute(e, b"name").as_deref(), current.as_mut()) {
+        (Some("activationTimestamp") | Some("projectOpenTimestamp"), Some(project)) => {
+            if let Ok(timestamp) = value.parse::<i64>() {
+                project.last_used = project.last_used.max(timestamp);
+            }
+        }
+        (Some("displayName"), Some(project)) => project.name = Some(value),
+        (None, None) => projects.push(RecentProject {
+            path: expand_path_macros(&value, home),
+            ..Default::default()
+        }),
+        _ => {}
+    }
+}
+
+/// Get the unescaped value of an attribute
+fn attribute(e: &BytesStart, name: &[u8]) -> Option<String> {
+    e.attributes()
+        .flatten()
+        .find(|attr| attr.key.as_ref() == name)
+        .and_then(|attr| attr.unescape_value().ok())
+        .map(|value| value.to_string())
+}
+
+/// Replace the `$USER_HOME$` macro JetBrains uses in stored paths
+fn expand_path_macros(path: &str, home: &str) -> String {
+    path.replace("$USER_HOME$", home)
+}
+
+/// Hash a project path (64-bit FNV-1a), so a project keeps its ID across loads and builds
+fn path_hash(path: &str) -> u64 {
+    path.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+/// Convert a recent project to a workspace tagged with `jetbrains`
+fn to_workspace(project: RecentProject) -> Workspace {
+    let mut workspace = Workspace {
+        id: format!("jetbrains-{:016x}", path_hash(&project.path)),
+        name: project.name,
+        path: project.path,
+        last_used: project.last_used,
+        storage_path: None,
+        sources: vec![WorkspaceSource::Database(JETBRAINS_SOURCE.to_string())],
+        parsed_info: None,
+        pinned: false,
+        exists: false,
+        exists_checked: false,
+        open_count: None,
+        recent_file_count: 0,
+        git_branch: None,
+        color: None,
+        recommended_extensions: Vec::new(),
+    };
+
+    let _ = workspace.parse_path();
+    if let Some(info) = workspace.parsed_info.as_mut() {
+        info.tags.push("jetbrains".to_string());
+    }
+    workspace
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_recent_projects() {
+        let file = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/jetbrains_recent_projects.xml");
+        let projects = read_recent_projects(&file).unwrap();
+        let home = home_dir().map(|home| home.to_string_lossy().to_string()).unwrap_or_default();
+
+        assert_eq!(projects.len(), 3);
+        assert_eq!(projects[0].path, format!("{}/projects/api", home));
+        assert_eq!(projects[0].name.as_deref(), Some("API server"));
+        assert_eq!(projects[0].last_used, 1700000200000);
+        assert_eq!(projects[1].path, "/srv/web & docs");
+        assert_eq!(projects[1].name, None);
+        assert_eq!(projects[1].last_used, 1690000000000);
+        assert_eq!(projects[2].path, "/opt/legacy");
+        assert_eq!(projects[2].last_used, 0);
+    }
+
+    #[test]
+    fn test_workspace_id_is_stable() {
+        let project = |path: &str| RecentProject { path: path.to_string(), ..Default::default() };
+        let first = to_workspace(project("/srv/web"));
+        assert_eq!(first.id, to_workspace(project("/srv/web")).id);
+        assert_ne!(first.id, to_workspace(project("/srv/api")).id);
+        assert!(first.id.starts_with("jetbrains-"));
+        assert!(first.parsed_info.unwrap().tags.contains(&"jetbrains".to_string()));
+    }
+}
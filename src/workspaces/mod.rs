@@ -1,9 +1,16 @@
 // Re-export all public items from submodules
+mod access;
+mod activity;
+mod archive;
 mod error;
+mod groups;
 mod models;
 mod storage;
 mod database;
+mod jetbrains;
+mod notes;
 mod paths;
+mod ssh_config;
 mod utils;
 pub mod parser;
 mod zed;
@@ -11,56 +18,343 @@ mod zed;
 // Public exports
 pub use models::Workspace;
 pub use models::WorkspaceSource;
-pub use paths::{get_default_profile_path, get_known_vscode_paths};
-pub use utils::{workspace_exists, extract_folder_basename};
+pub use models::SortOrder;
+#[allow(unused_imports)]
+pub use models::WorkspaceSort;
+#[allow(unused_imports)]
+pub use models::{WorkspaceQuery, WorkspaceStats, WorkspaceComparison, same_path};
+pub use models::NamedProfile;
+pub use models::ProgressUpdate;
+pub use models::WorkspaceParseWarning;
+pub use database::list_all_database_keys;
+#[allow(unused_imports)]
+pub use models::RestoreReport;
+pub use paths::{get_default_profile_path, get_known_vscode_paths, get_named_profiles, expand_tilde};
+#[allow(unused_imports)]
+pub use paths::detect_running_vscode_profile;
+pub use paths::{resolve_symlinks, normalize_path, path_from_uri};
+#[allow(unused_imports)]
+pub use paths::workspace_id_to_storage_path;
+pub use database::set_backups_enabled;
+pub use storage::{set_extra_storage_dirs, read_workspace_extension_recommendations};
+pub use jetbrains::set_include_jetbrains;
+pub use zed::ZED_PROFILE_NAME;
+pub use access::record_workspace_open;
+pub use activity::{read_activity_log, ActivitySource};
+pub use notes::{load_notes, note_key, set_workspace_note};
+pub use groups::{WorkspaceGroup, create_group, add_to_group, list_groups};
+#[allow(unused_imports)]
+pub use groups::remove_from_group;
+#[allow(unused_imports)]
+pub use activity::ActivityEntry;
+pub use utils::{workspace_exists, extract_folder_basename, check_remote_workspace_exists, workspace_count_by_host, extract_git_branch};
+#[allow(unused_imports)]
+pub use utils::is_git_worktree;
+#[allow(unused_imports)]
+pub use utils::{workspace_statistics, ExistenceCache, is_command_available, batch_check_workspace_existence, filter_workspaces};
+#[allow(unused_imports)]
+pub use error::WorkspaceError;
 
 // Public API
 pub use api::{
     get_workspaces,
+    get_workspaces_with_warnings,
+    get_named_profile_workspaces,
+    get_workspace_by_id,
     delete_workspace,
+    diff_profiles,
+    archive_workspaces,
+    list_archived_workspaces,
+    restore_workspaces,
+    snapshot_workspaces,
+    restore_snapshot,
+    rename_workspace,
 };
+#[allow(unused_imports)]
+pub use api::{add_workspace, query_workspaces, reorder_workspaces, watch_workspaces};
 
 mod api {
     use anyhow::{Context, Result};
     use log::{info, warn, debug};
+    use std::sync::mpsc;
+    use std::time::Instant;
     
-    use crate::workspaces::models::{Workspace, WorkspaceSource};
+    use crate::workspaces::archive;
+    use crate::workspaces::jetbrains;
+    use crate::workspaces::error::WorkspaceError;
+    use crate::workspaces::models::{Workspace, WorkspaceComparison, WorkspaceQuery, WorkspaceSource, WorkspaceParseWarning, RestoreReport, ProgressUpdate, SortOrder};
     use crate::workspaces::paths::{self, expand_tilde};
-    use crate::workspaces::storage::get_workspaces_from_storage;
-    use crate::workspaces::database::get_workspace_metadata;
+    use crate::workspaces::storage::{get_workspaces_from_storage, get_workspace_from_storage, get_workspaces_from_storage_dir, extra_storage_dirs, build_storage_dir_path, update_workspace_name_in_storage};
+    use crate::workspaces::database::{get_workspace_metadata, get_named_profile_metadata, open_database, backup_database, with_transaction, DbConnectionPool};
     use crate::workspaces::utils::{process_workspaces, filter_workspaces};
 
     /// Get all workspaces from the VSCode profile
-    pub fn get_workspaces(profile_path: &str) -> Result<Vec<Workspace>> {
+    ///
+    /// If a `progress` sender is given, the loading stages are reported on it; send errors
+    /// (e.g. the receiver was dropped) are ignored.
+    pub fn get_workspaces(profile_path: &str, progress: Option<mpsc::Sender<ProgressUpdate>>) -> Result<Vec<Workspace>> {
+        get_workspaces_with_warnings(profile_path, progress).map(|(workspaces, _)| workspaces)
+    }
+
+    /// Get all workspaces from the VSCode profile like `get_workspaces`, along with the
+    /// entries of the recently opened lists that could not be read
+    pub fn get_workspaces_with_warnings(profile_path: &str, progress: Option<mpsc::Sender<ProgressUpdate>>) -> Result<(Vec<Workspace>, Vec<WorkspaceParseWarning>)> {
         info!("Getting workspaces from: {}", profile_path);
+        let report = |update: ProgressUpdate| {
+            if let Some(sender) = &progress {
+                let _ = sender.send(update);
+            }
+        };
         
         // Handle the "::zed" fake profile
         if profile_path == crate::workspaces::zed::ZED_PROFILE_NAME {
             info!("Getting workspaces from Zed profile");
-            return crate::workspaces::zed::get_zed_workspaces();
+            report(ProgressUpdate::StartingDatabase);
+            let workspaces = crate::workspaces::zed::get_zed_workspaces()?;
+            report(ProgressUpdate::Done(workspaces.len()));
+            return Ok((workspaces, Vec::new()));
         }
         
-        // Get workspaces from storage
-        let mut workspaces = get_workspaces_from_storage(profile_path)?;
-        
-        // Try to update metadata from database and add any new workspaces
+        let started = Instant::now();
         let profile_path = expand_tilde(profile_path)?;
+        // Entries of the databases that could not be read
+        let mut warnings = Vec::new();
+        let mut workspaces = if let Some(base_profile_path) = paths::named_profile_base(&profile_path) {
+            // A named profile directory keeps its storage and databases at the top level
+            debug!("Reading named profile of {}...", base_profile_path);
+            report(ProgressUpdate::StartingStorage);
+            let (workspaces, profile_warnings) = read_named_profile_workspaces(&base_profile_path, &profile_path)?;
+            warnings = profile_warnings;
+            report(ProgressUpdate::StorageLoaded(workspaces.len()));
+            report(ProgressUpdate::StartingDatabase);
+            workspaces
+        } else {
+            // Get workspaces from storage
+            debug!("Scanning storage...");
+            report(ProgressUpdate::StartingStorage);
+            let mut workspaces = get_workspaces_from_storage(&profile_path, &extra_storage_dirs())?;
+            report(ProgressUpdate::StorageLoaded(workspaces.len()));
+            debug!("Found {} workspaces in storage in {:?}", workspaces.len(), started.elapsed());
+            
+            debug!("Reading database...");
+            report(ProgressUpdate::StartingDatabase);
+            
+            // Update metadata from database if available and add any new workspaces found only in database
+            match get_workspace_metadata(&profile_path, &mut workspaces) {
+                Ok(db_warnings) => warnings.extend(db_warnings),
+                Err(e) => warn!("Failed to get workspace metadata from database: {}", e),
+            }
+            
+            // Merge in the workspaces of named profiles (VSCode 1.75+)
+            match merge_named_profile_workspaces(&profile_path, &mut workspaces) {
+                Ok(profile_warnings) => warnings.extend(profile_warnings),
+                Err(e) => warn!("Failed to get workspaces from named profiles: {}", e),
+            }
+            workspaces
+        };
+        let step = Instant::now();
+        
+        // The same folder can be listed under different spellings (e.g. a path in storage
+        // and a URI in a database) that the database pass didn't match
+        workspaces = Workspace::dedup(workspaces);
         
-        // Update metadata from database if available and add any new workspaces found only in database
-        if let Err(e) = get_workspace_metadata(&profile_path, &mut workspaces) {
-            warn!("Failed to get workspace metadata from database: {}", e);
+        // Add JetBrains recent projects if requested (--include-jetbrains)
+        if jetbrains::include_jetbrains() {
+            match jetbrains::get_jetbrains_workspaces() {
+                Ok(projects) => workspaces.extend(projects),
+                Err(e) => warn!("Failed to get JetBrains recent projects: {}", e),
+            }
         }
+        report(ProgressUpdate::DatabaseLoaded(workspaces.len()));
+        debug!("Read databases in {:?}, {} workspaces", step.elapsed(), workspaces.len());
         
         // Parse workspace paths to extract additional information
+        let step = Instant::now();
+        debug!("Processing {} workspaces...", workspaces.len());
+        report(ProgressUpdate::Processing(workspaces.len()));
         if let Err(e) = process_workspaces(&mut workspaces) {
             warn!("Failed to process workspace paths: {}", e);
         }
         
+        // Add how often each workspace was opened through this tool
+        if let Err(e) = crate::workspaces::access::apply_open_counts(&mut workspaces) {
+            warn!("Failed to read workspace open counts: {}", e);
+        }
+        
+        debug!("Processed workspaces in {:?}", step.elapsed());
+        
         // Sort by last used time (descending)
-        workspaces.sort_by(|a, b| b.last_used.cmp(&a.last_used));
+        debug!("Sorting...");
+        SortOrder::default().sort(&mut workspaces);
         
-        info!("Found {} total workspaces", workspaces.len());
-        Ok(workspaces)
+        info!("Found {} total workspaces in {:?}", workspaces.len(), started.elapsed());
+        report(ProgressUpdate::Done(workspaces.len()));
+        Ok((workspaces, warnings))
+    }
+
+    /// Watch a profile for changes to its workspaces, e.g. VSCode opening a new workspace.
+    ///
+    /// `on_change` is called when a directory is created or removed in `User/workspaceStorage`
+    /// or a state database is modified. Paths that don't exist yet are not watched. Watching
+    /// stops when the returned watcher is dropped.
+    #[allow(dead_code)]
+    pub fn watch_workspaces(profile_path: &str, on_change: impl Fn() + Send + 'static) -> Result<impl notify::Watcher + Send> {
+        use notify::{RecursiveMode, Watcher};
+        
+        let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+            match res {
+                Ok(event) if !event.kind.is_access() => on_change(),
+                Ok(_) => {}
+                Err(e) => warn!("Workspace watcher error: {}", e),
+            }
+        }).context("Failed to create file system watcher")?;
+        
+        // Watch storage directories being added/removed and the state databases being modified
+        let profile = std::path::PathBuf::from(expand_tilde(profile_path)?).join("User");
+        let storage_dir = profile.join("workspaceStorage");
+        if storage_dir.exists() {
+            watcher.watch(&storage_dir, RecursiveMode::NonRecursive)
+                .with_context(|| format!("Failed to watch {}", storage_dir.display()))?;
+        }
+        for db_path in [profile.join("state.vscdb"), profile.join("globalStorage/state.vscdb")] {
+            if db_path.exists() {
+                watcher.watch(&db_path, RecursiveMode::NonRecursive)
+                    .with_context(|| format!("Failed to watch {}", db_path.display()))?;
+            }
+        }
+        
+        debug!("Watching workspaces of {}", profile_path);
+        Ok(watcher)
+    }
+
+    /// Add the workspaces of each named profile (`User/profiles/<id>`), tagged with
+    /// `WorkspaceSource::Profile`. Workspaces already known by path gain the profile's sources.
+    /// Returns the database entries of the named profiles that could not be read.
+    fn merge_named_profile_workspaces(profile_path: &str, workspaces: &mut Vec<Workspace>) -> Result<Vec<WorkspaceParseWarning>> {
+        let mut warnings = Vec::new();
+        for profile in paths::get_named_profiles(profile_path)? {
+            debug!("Reading workspaces of named profile '{}': {}", profile.name, profile.path);
+            
+            let (profile_workspaces, profile_warnings) = read_named_profile_workspaces(profile_path, &profile.path)?;
+            warnings.extend(profile_warnings);
+            
+            let mut index: std::collections::HashMap<String, usize> = workspaces.iter()
+                .enumerate()
+                .map(|(i, ws)| (paths::normalize_path(&ws.path), i))
+                .collect();
+            
+            for mut workspace in profile_workspaces {
+                workspace.sources.push(WorkspaceSource::Profile(profile.name.clone()));
+                
+                let normalized = paths::normalize_path(&workspace.path);
+                match index.get(&normalized) {
+                    Some(&i) => {
+                        let existing = &mut workspaces[i];
+                        existing.last_used = existing.last_used.max(workspace.last_used);
+                        if existing.name.is_none() {
+                            existing.name = workspace.name;
+                        }
+                        for source in workspace.sources {
+                            if !existing.sources.contains(&source) {
+                                existing.sources.push(source);
+                            }
+                        }
+                    },
+                    None => {
+                        index.insert(normalized, workspaces.len());
+                        workspaces.push(workspace);
+                    }
+                }
+            }
+        }
+        
+        Ok(warnings)
+    }
+
+    /// Get the workspaces of a named profile directory (`<base>/User/profiles/<id>`), which keeps
+    /// its `workspaceStorage` and `state.vscdb` directly in the directory
+    pub fn get_named_profile_workspaces(base_profile_path: &str, profile_dir: &str) -> Result<Vec<Workspace>> {
+        read_named_profile_workspaces(base_profile_path, profile_dir).map(|(workspaces, _)| workspaces)
+    }
+
+    /// Read the workspaces of a named profile directory along with the database entries
+    /// that could not be read
+    fn read_named_profile_workspaces(base_profile_path: &str, profile_dir: &str) -> Result<(Vec<Workspace>, Vec<WorkspaceParseWarning>)> {
+        let storage_dir = format!("{}/workspaceStorage", profile_dir);
+        let mut workspaces = get_workspaces_from_storage_dir(&storage_dir)?;
+        let warnings = get_named_profile_metadata(base_profile_path, profile_dir, &mut workspaces)?;
+        Ok((workspaces, warnings))
+    }
+
+    /// Get a single workspace by its ID
+    ///
+    /// Storage workspaces are read directly from their `workspace.json` instead of
+    /// scanning all of workspace storage; other IDs fall back to a full load.
+    pub fn get_workspace_by_id(profile_path: &str, workspace_id: &str) -> Result<Option<Workspace>> {
+        debug!("Looking up workspace '{}' in profile: {}", workspace_id, profile_path);
+        
+        let is_storage_id = profile_path != crate::workspaces::zed::ZED_PROFILE_NAME
+            && !workspace_id.is_empty()
+            && !workspace_id.contains(['/', '\\'])
+            && workspace_id != "."
+            && workspace_id != "..";
+        
+        if is_storage_id {
+            if let Some(workspace) = get_workspace_from_storage(profile_path, workspace_id)? {
+                let profile_path = expand_tilde(profile_path)?;
+                
+                // Enrich with database metadata, dropping the database-only entries it adds
+                let mut workspaces = vec![workspace];
+                if let Err(e) = get_workspace_metadata(&profile_path, &mut workspaces) {
+                    warn!("Failed to get workspace metadata from database: {}", e);
+                }
+                workspaces.retain(|w| w.id == workspace_id);
+                
+                if let Err(e) = process_workspaces(&mut workspaces) {
+                    warn!("Failed to process workspace paths: {}", e);
+                }
+                if let Err(e) = crate::workspaces::access::apply_open_counts(&mut workspaces) {
+                    warn!("Failed to read workspace open counts: {}", e);
+                }
+                
+                return Ok(workspaces.into_iter().next());
+            }
+        }
+        
+        Ok(get_workspaces(profile_path, None)?
+            .into_iter()
+            .find(|w| w.id == workspace_id))
+    }
+
+    /// Compare the workspaces of two profiles, matching them by normalized path
+    pub fn diff_profiles(profile_a: &str, profile_b: &str) -> Result<WorkspaceComparison> {
+        info!("Comparing profiles '{}' and '{}'", profile_a, profile_b);
+        
+        let workspaces_a = get_workspaces(profile_a, None)?;
+        let mut workspaces_b: Vec<Option<Workspace>> = get_workspaces(profile_b, None)?
+            .into_iter()
+            .map(Some)
+            .collect();
+        
+        let mut index_b = std::collections::HashMap::new();
+        for (i, workspace) in workspaces_b.iter().enumerate() {
+            if let Some(workspace) = workspace {
+                index_b.entry(paths::normalize_path(&workspace.path)).or_insert(i);
+            }
+        }
+        
+        let mut comparison = WorkspaceComparison::default();
+        for workspace in workspaces_a {
+            let matched = index_b.get(&paths::normalize_path(&workspace.path))
+                .and_then(|&i| workspaces_b[i].take());
+            match matched {
+                Some(other) => comparison.in_both.push((workspace, other)),
+                None => comparison.only_in_a.push(workspace),
+            }
+        }
+        comparison.only_in_b = workspaces_b.into_iter().flatten().collect();
+        
+        Ok(comparison)
     }
 
     /// Search workspaces using filtering criteria
@@ -69,7 +363,7 @@ mod api {
         info!("Searching workspaces in profile '{}' with query: '{}'", profile_path, query);
         
         // First get all workspaces
-        let mut all_workspaces = get_workspaces(profile_path)?;
+        let mut all_workspaces = get_workspaces(profile_path, None)?;
         
         // Apply the filter
         let filtered_workspaces = filter_workspaces(&mut all_workspaces, query);
@@ -84,18 +378,45 @@ mod api {
         Ok(filtered_results)
     }
     
+    /// Get the workspaces matching a structured query, sorted and limited as requested
+    #[allow(dead_code)]
+    pub fn query_workspaces(profile_path: &str, query: &WorkspaceQuery) -> Result<Vec<Workspace>> {
+        info!("Querying workspaces in profile '{}' with {:?}", profile_path, query);
+        
+        let mut workspaces = get_workspaces(profile_path, None)?;
+        workspaces.retain_mut(|ws| query.matches(ws));
+        
+        // Sort and truncate after filtering
+        query.sort_by.sort_directed(&mut workspaces, query.sort_desc);
+        if let Some(limit) = query.limit {
+            workspaces.truncate(limit);
+        }
+        
+        info!("Found {} matching workspaces", workspaces.len());
+        Ok(workspaces)
+    }
+    
     /// Delete a workspace from VSCode
-    pub fn delete_workspace(profile_path: &str, workspaces: &[Workspace]) -> Result<bool> {
+    ///
+    /// When `dry_run` is true, every action is logged but nothing is removed,
+    /// and `Ok(false)` is returned to indicate that no changes were made.
+    pub fn delete_workspace(profile_path: &str, workspaces: &[Workspace], dry_run: bool) -> Result<bool> {
         if workspaces.is_empty() {
             info!("No workspaces to delete");
-            return Ok(true);
+            return Ok(!dry_run);
         }
         
-        info!("Attempting to delete {} workspaces from profile {}", workspaces.len(), profile_path);
+        if dry_run {
+            info!("Dry run: previewing deletion of {} workspaces from profile {}", workspaces.len(), profile_path);
+        } else {
+            info!("Attempting to delete {} workspaces from profile {}", workspaces.len(), profile_path);
+        }
         let profile_path = expand_tilde(profile_path)?;
         
         let mut success = true;
         let mut deleted_count = 0;
+        // Workspaces of a batch usually share their databases, open each only once
+        let pool = DbConnectionPool::new();
         
         // Process each workspace
         for workspace in workspaces {
@@ -107,11 +428,13 @@ mod api {
                     WorkspaceSource::Storage(storage_path) => {
                         // For storage, we need to delete the folder in workspaceStorage
                         if let Some(storage_dir) = build_storage_dir_path(&profile_path, storage_path) {
-                            if let Err(e) = delete_storage_workspace(&storage_dir) {
+                            if let Err(e) = delete_storage_workspace(&storage_dir, dry_run) {
                                 warn!("Failed to delete storage workspace at {}: {}", storage_dir, e);
                                 success = false;
                             } else {
-                                info!("Successfully deleted storage workspace at {}", storage_dir);
+                                if !dry_run {
+                                    info!("Successfully deleted storage workspace at {}", storage_dir);
+                                }
                                 deleted_count += 1;
                             }
                         } else {
@@ -119,17 +442,24 @@ mod api {
                             success = false;
                         }
                     },
+                    WorkspaceSource::Database(db_source) if db_source == jetbrains::JETBRAINS_SOURCE => {
+                        // JetBrains IDEs keep their recent projects to themselves
+                        warn!("Deleting JetBrains recent projects is not supported: {}", workspace.path);
+                        success = false;
+                    },
                     WorkspaceSource::Database(db_source) => {
                         // For database, we need to update the JSON in the database
                         // Parse the source to determine which database to use
                         if let Some((db_path, _)) = parse_db_source(&profile_path, db_source) {
-                            if let Err(e) = delete_database_workspace(&db_path, &workspace.path) {
+                            if let Err(e) = delete_database_workspace(&pool, &db_path, &workspace.path, dry_run) {
                                 warn!("Failed to delete workspace {} from database {}: {}",
                                       workspace.path, db_path, e);
                                 success = false;
                             } else {
-                                info!("Successfully removed workspace {} from database {}",
-                                      workspace.path, db_path);
+                                if !dry_run {
+                                    info!("Successfully removed workspace {} from database {}",
+                                          workspace.path, db_path);
+                                }
                                 deleted_count += 1;
                             }
                         } else {
@@ -138,32 +468,583 @@ mod api {
                         }
                     },
                     WorkspaceSource::Zed(channel) => {
-                        // Zed workspace deletion is not yet supported
-                        warn!("Deletion of Zed workspaces is not yet supported (channel: {})", channel);
-                        success = false;
+                        // For Zed, delete the row from the channel's workspaces table
+                        if let Err(e) = crate::workspaces::zed::delete_zed_workspace(channel, &workspace.id, dry_run) {
+                            warn!("Failed to delete workspace {} from Zed ({}): {}", workspace.id, channel, e);
+                            success = false;
+                        } else {
+                            if !dry_run {
+                                info!("Successfully removed workspace {} from Zed ({})", workspace.id, channel);
+                            }
+                            deleted_count += 1;
+                        }
+                    },
+                    WorkspaceSource::Profile(_) => {
+                        // Named profile entries are removed through their storage and database sources
                     }
                 }
             }
         }
         
+        if let Err(e) = pool.close() {
+            warn!("{}", e);
+        }
+        
+        if dry_run {
+            info!("Dry run: {} workspace sources would be deleted", deleted_count);
+            return Ok(false);
+        }
+        
         info!("Deleted {} workspace sources", deleted_count);
         Ok(success)
     }
     
-    // Helper function to build the full path to a workspace storage directory
-    fn build_storage_dir_path(profile_path: &str, storage_path: &str) -> Option<String> {
-        // Extract the workspace ID from the storage path
-        // Expected format: workspaceStorage/WORKSPACE_ID/workspace.json
-        let parts: Vec<&str> = storage_path.split('/').collect();
-        if parts.len() >= 2 && parts[0] == "workspaceStorage" {
-            let workspace_id = parts[1];
-            return Some(format!("{}/User/workspaceStorage/{}", profile_path, workspace_id));
-        }
-        None
+    /// Add a workspace to the VSCode recently opened list
+    ///
+    /// `workspace_path` may be a local path (which must exist) or a URI such as
+    /// `vscode-remote://...`. Fails with `WorkspaceError::InvalidPath` if the path is
+    /// invalid, `WorkspaceError::AlreadyExists` if a workspace with the same normalized
+    /// path is already known and `WorkspaceError::Write` if the database can't be updated.
+    pub fn add_workspace(profile_path: &str, workspace_path: &str) -> Result<()> {
+        let workspace_path = workspace_path.trim();
+        if workspace_path.is_empty() {
+            return Err(WorkspaceError::InvalidPath("workspace path is empty".to_string()).into());
+        }
+        
+        if profile_path == crate::workspaces::zed::ZED_PROFILE_NAME {
+            return Err(WorkspaceError::Write("adding workspaces to Zed is not supported".to_string()).into());
+        }
+        
+        let (uri, is_dir) = workspace_path_to_uri(workspace_path)?;
+        
+        // Refuse duplicates by comparing normalized paths
+        let normalized = paths::normalize_path(&uri);
+        let existing = get_workspaces(profile_path, None)?;
+        if existing.iter().any(|ws| paths::normalize_path(&ws.path) == normalized) {
+            return Err(WorkspaceError::AlreadyExists(workspace_path.to_string()).into());
+        }
+        
+        // Build the entry in the same shape VSCode uses
+        let entry = if uri.ends_with(".code-workspace") {
+            serde_json::json!({
+                "workspace": {
+                    "id": uuid::Uuid::new_v4().simple().to_string(),
+                    "configPath": uri,
+                }
+            })
+        } else if is_dir {
+            serde_json::json!({ "folderUri": uri })
+        } else {
+            serde_json::json!({ "fileUri": uri })
+        };
+        
+        let profile_path = expand_tilde(profile_path)?;
+        add_database_entry(&profile_path, entry)
+            .map_err(|e| WorkspaceError::Write(format!("{:#}", e)))?;
+        
+        info!("Added workspace {} to profile {}", uri, profile_path);
+        Ok(())
+    }
+    
+    // Helper function to validate a workspace path and convert it to a URI.
+    // Returns the URI and whether it refers to a folder.
+    fn workspace_path_to_uri(workspace_path: &str) -> Result<(String, bool)> {
+        if workspace_path.contains("://") {
+            // Remote and file URIs must be parseable
+            let info = crate::workspaces::parser::parse_workspace_path(workspace_path)
+                .map_err(|e| WorkspaceError::InvalidPath(format!("{}: {}", workspace_path, e)))?;
+            
+            if workspace_path.starts_with("file://") {
+                let decoded = paths::normalize_path(&paths::path_from_uri(workspace_path)?);
+                let path = std::path::Path::new(&decoded);
+                if !path.exists() {
+                    return Err(WorkspaceError::InvalidPath(format!("{} does not exist", decoded)).into());
+                }
+                return Ok((workspace_path.to_string(), path.is_dir()));
+            }
+            
+            let is_dir = info.workspace_type != crate::workspaces::parser::WorkspaceType::File;
+            return Ok((workspace_path.to_string(), is_dir));
+        }
+        
+        let expanded = expand_tilde(workspace_path)?;
+        let path = std::fs::canonicalize(&expanded)
+            .map_err(|_| WorkspaceError::InvalidPath(format!("{} does not exist", workspace_path)))?;
+        
+        // Encode each path segment, e.g. "C:\My Project" -> "file:///c%3A/My%20Project"
+        let path_str = path.to_string_lossy().replace('\\', "/");
+        let path_str = path_str.trim_start_matches("//?/");
+        let encoded: Vec<String> = path_str.trim_start_matches('/')
+            .split('/')
+            .map(|segment| urlencoding::encode(segment).into_owned())
+            .collect();
+        
+        Ok((format!("file:///{}", encoded.join("/")), path.is_dir()))
+    }
+    
+    // Helper function to add an entry to the front of history.recentlyOpenedPathsList
+    fn add_database_entry(profile_path: &str, entry: serde_json::Value) -> Result<()> {
+        // Prefer the global storage database used by current VSCode versions
+        let db_path = ["User/globalStorage/state.vscdb", "User/state.vscdb"].iter()
+            .map(|relative| format!("{}/{}", profile_path, relative))
+            .find(|path| std::path::Path::new(path).exists())
+            .ok_or_else(|| anyhow::anyhow!("No state database found in profile {}", profile_path))?;
+        
+        let conn = open_database(&db_path)
+            .with_context(|| format!("Failed to open database: {}", db_path))?;
+        
+        // Back up the database before the first write
+        backup_database(&conn, &db_path)?;
+        
+        // Read and update the list in one transaction so concurrent changes aren't lost
+        with_transaction(&conn, |conn| {
+            let existing: Option<String> = conn.query_row(
+                "SELECT value FROM ItemTable WHERE key = ?",
+                ["history.recentlyOpenedPathsList"],
+                |row| row.get(0)
+            ).ok();
+            
+            let mut json: serde_json::Value = match existing {
+                Some(value) => serde_json::from_str(&value)
+                    .with_context(|| "Failed to parse history.recentlyOpenedPathsList")?,
+                None => serde_json::json!({ "entries": [] }),
+            };
+            
+            let entries = json.as_object_mut()
+                .context("history.recentlyOpenedPathsList is not an object")?
+                .entry("entries")
+                .or_insert_with(|| serde_json::json!([]))
+                .as_array_mut()
+                .context("history.recentlyOpenedPathsList entries is not an array")?;
+            entries.insert(0, entry);
+            
+            conn.execute(
+                "INSERT OR REPLACE INTO ItemTable (key, value) VALUES (?, ?)",
+                ["history.recentlyOpenedPathsList", &serde_json::to_string(&json)?]
+            ).with_context(|| format!("Failed to update database: {}", db_path))?;
+            
+            Ok(())
+        })
+    }
+    
+    /// Timestamp (2100-01-01 in milliseconds) above any real `lastUsed` value, used to keep
+    /// reordered entries at the top
+    const REORDER_BASE_TIMESTAMP: i64 = 4_102_444_800_000;
+    
+    /// Move the given workspaces to the top of VSCode's recently opened list, in the given order.
+    ///
+    /// The remaining entries keep their relative order after them. The moved entries also get
+    /// decreasing `lastUsed` timestamps well above any real timestamp so they stay on top.
+    /// Workspace paths are accepted in place of IDs, since database-only IDs change on every load.
+    #[allow(dead_code)]
+    pub fn reorder_workspaces(profile_path: &str, ordered_ids: &[String]) -> Result<()> {
+        if profile_path == crate::workspaces::zed::ZED_PROFILE_NAME {
+            return Err(WorkspaceError::Write("reordering Zed workspaces is not supported".to_string()).into());
+        }
+        
+        let workspaces = get_workspaces(profile_path, None)?;
+        let ordered_paths = ordered_ids.iter()
+            .map(|id| workspaces.iter()
+                .find(|ws| &ws.id == id || &ws.path == id)
+                .map(|ws| paths::normalize_path(&ws.path))
+                .ok_or_else(|| anyhow::anyhow!("Workspace not found: {}", id)))
+            .collect::<Result<Vec<_>>>()?;
+        
+        let profile_path = expand_tilde(profile_path)?;
+        let mut reordered_any = false;
+        for relative in ["User/state.vscdb", "User/globalStorage/state.vscdb"] {
+            let db_path = format!("{}/{}", profile_path, relative);
+            if std::path::Path::new(&db_path).exists() {
+                reordered_any |= reorder_database_entries(&db_path, &ordered_paths)?;
+            }
+        }
+        
+        if !reordered_any {
+            warn!("No recently opened list found to reorder in profile {}", profile_path);
+        }
+        Ok(())
+    }
+    
+    /// Reorder the recently opened entries of one database; returns whether the list was found
+    fn reorder_database_entries(db_path: &str, ordered_paths: &[String]) -> Result<bool> {
+        let conn = open_database(db_path)
+            .with_context(|| format!("Failed to open database: {}", db_path))?;
+        
+        // Back up the database before the first write
+        backup_database(&conn, db_path)?;
+        
+        // Read and update the list in one transaction so concurrent changes aren't lost
+        with_transaction(&conn, |conn| {
+            let Ok(value) = conn.query_row(
+                "SELECT value FROM ItemTable WHERE key = ?",
+                ["history.recentlyOpenedPathsList"],
+                |row| row.get::<_, String>(0)
+            ) else {
+                return Ok(false);
+            };
+            
+            let mut json: serde_json::Value = serde_json::from_str(&value)
+                .with_context(|| "Failed to parse history.recentlyOpenedPathsList")?;
+            let Some(entries) = json.get_mut("entries").and_then(|e| e.as_array_mut()) else {
+                return Ok(false);
+            };
+            
+            // Pull out the entries to move, in the requested order
+            let mut remaining = std::mem::take(entries);
+            let mut reordered = Vec::with_capacity(remaining.len());
+            for path in ordered_paths {
+                if let Some(pos) = remaining.iter().position(|entry| {
+                    recent_entry_path(entry).is_some_and(|p| &paths::normalize_path(p) == path)
+                }) {
+                    reordered.push(remaining.remove(pos));
+                }
+            }
+            
+            let count = reordered.len() as i64;
+            for (i, entry) in reordered.iter_mut().enumerate() {
+                if let Some(object) = entry.as_object_mut() {
+                    object.insert("lastUsed".to_string(), serde_json::json!(REORDER_BASE_TIMESTAMP + count - i as i64));
+                }
+            }
+            reordered.extend(remaining);
+            *entries = reordered;
+            
+            conn.execute(
+                "UPDATE ItemTable SET value = ? WHERE key = ?",
+                [&serde_json::to_string(&json)?, "history.recentlyOpenedPathsList"]
+            ).with_context(|| format!("Failed to update database: {}", db_path))?;
+            Ok(true)
+        })
+    }
+    
+    /// Give a workspace a display name, or remove its name when `name` is empty.
+    ///
+    /// The name is stored in the `name` key of the workspace's recently opened entries and,
+    /// for workspaces read from workspace storage, in their workspace.json (see
+    /// `update_workspace_name_in_storage`). Workspace paths are accepted in place of IDs,
+    /// since database-only IDs change on every load.
+    pub fn rename_workspace(profile_path: &str, id_or_path: &str, name: &str) -> Result<()> {
+        if profile_path == crate::workspaces::zed::ZED_PROFILE_NAME {
+            return Err(WorkspaceError::Write("renaming Zed workspaces is not supported".to_string()).into());
+        }
+        
+        let workspace = get_workspaces(profile_path, None)?
+            .into_iter()
+            .find(|ws| ws.id == id_or_path || ws.path == id_or_path)
+            .ok_or_else(|| WorkspaceError::NotFound(id_or_path.to_string()))?;
+        
+        let profile_path = expand_tilde(profile_path)?;
+        let normalized = paths::normalize_path(&workspace.path);
+        let mut renamed_any = false;
+        for relative in ["User/state.vscdb", "User/globalStorage/state.vscdb"] {
+            let db_path = format!("{}/{}", profile_path, relative);
+            if std::path::Path::new(&db_path).exists() {
+                renamed_any |= rename_database_entries(&db_path, &normalized, name)?;
+            }
+        }
+        if !renamed_any {
+            debug!("No recently opened entry found for {}", workspace.path);
+        }
+        
+        if workspace.sources.iter().any(|source| matches!(source, WorkspaceSource::Storage(_))) {
+            update_workspace_name_in_storage(&profile_path, &workspace.id, name)?;
+        }
+        
+        info!("Renamed workspace {} ({}) to {:?}", workspace.id, workspace.path, name);
+        Ok(())
+    }
+    
+    /// Set the name of the recently opened entries of one database; returns whether an entry matched
+    fn rename_database_entries(db_path: &str, normalized_path: &str, name: &str) -> Result<bool> {
+        let conn = open_database(db_path)
+            .with_context(|| format!("Failed to open database: {}", db_path))?;
+        
+        // Back up the database before the first write
+        backup_database(&conn, db_path)?;
+        
+        // Read and update the list in one transaction so concurrent changes aren't lost
+        with_transaction(&conn, |conn| {
+            let Ok(value) = conn.query_row(
+                "SELECT value FROM ItemTable WHERE key = ?",
+                ["history.recentlyOpenedPathsList"],
+                |row| row.get::<_, String>(0)
+            ) else {
+                return Ok(false);
+            };
+            
+            let mut json: serde_json::Value = serde_json::from_str(&value)
+                .with_context(|| "Failed to parse history.recentlyOpenedPathsList")?;
+            let Some(entries) = json.get_mut("entries").and_then(|e| e.as_array_mut()) else {
+                return Ok(false);
+            };
+            
+            let mut matched = false;
+            for entry in entries.iter_mut() {
+                if recent_entry_path(entry).map(paths::normalize_path).as_deref() != Some(normalized_path) {
+                    continue;
+                }
+                let Some(object) = entry.as_object_mut() else { continue };
+                if name.is_empty() {
+                    object.remove("name");
+                } else {
+                    object.insert("name".to_string(), serde_json::json!(name));
+                }
+                matched = true;
+            }
+            
+            if matched {
+                conn.execute(
+                    "UPDATE ItemTable SET value = ? WHERE key = ?",
+                    [&serde_json::to_string(&json)?, "history.recentlyOpenedPathsList"]
+                ).with_context(|| format!("Failed to update database: {}", db_path))?;
+            }
+            Ok(matched)
+        })
+    }
+    
+    /// Move workspaces out of the profile into the archive store
+    ///
+    /// The entries are removed from `state.vscdb` like `delete_workspace`, but the raw
+    /// recently opened entry and the workspace storage directory are kept in the archive
+    /// (see `archive::load_archive` and `archive::archive_storage_dir`) so
+    /// `restore_workspaces` can bring them back. The archive is saved after each workspace,
+    /// so a failure part way never loses a moved storage directory.
+    pub fn archive_workspaces(profile_path: &str, workspaces: &[Workspace]) -> Result<()> {
+        if workspaces.is_empty() {
+            info!("No workspaces to archive");
+            return Ok(());
+        }
+
+        if profile_path == crate::workspaces::zed::ZED_PROFILE_NAME {
+            return Err(WorkspaceError::Write("archiving Zed workspaces is not supported".to_string()).into());
+        }
+
+        let profile_path = expand_tilde(profile_path)?;
+        let storage_archive = archive::archive_storage_dir()?;
+        let mut archived = archive::load_archive()?;
+        let now = chrono::Utc::now().timestamp_millis();
+        let mut database_only = Vec::with_capacity(workspaces.len());
+
+        for workspace in workspaces {
+            info!("Archiving workspace: {} ({})", workspace.id, workspace.path);
+
+            // Move the storage directory out of the profile
+            let mut archived_storage_dir = None;
+            for source in &workspace.sources {
+                let WorkspaceSource::Storage(storage_path) = source else { continue };
+                let Some(storage_dir) = build_storage_dir_path(&profile_path, storage_path) else {
+                    warn!("Could not determine storage directory for {}", storage_path);
+                    continue;
+                };
+                let storage_dir = std::path::Path::new(&storage_dir);
+                if !storage_dir.exists() {
+                    warn!("Storage directory does not exist: {}", storage_dir.display());
+                    continue;
+                }
+
+                let target = storage_archive.join(format!("{}-{}", workspace.id, now));
+                archive::move_dir(storage_dir, &target)?;
+                archived_storage_dir = Some(target.to_string_lossy().to_string());
+            }
+
+            archived.push(archive::ArchivedWorkspace {
+                workspace: workspace.clone(),
+                profile_path: profile_path.clone(),
+                archived_at: now,
+                recent_entry: find_recent_entry(&profile_path, &workspace.path),
+                archived_storage_dir,
+            });
+            // Save before touching the databases so nothing is lost on failure
+            archive::save_archive(&archived)?;
+
+            // The storage directory is gone already, only the database entries are left
+            let mut remaining = workspace.clone();
+            remaining.sources.retain(|source| matches!(source, WorkspaceSource::Database(_)));
+            database_only.push(remaining);
+        }
+
+        if !delete_workspace(&profile_path, &database_only, false)? {
+            warn!("Some archived workspaces could not be removed from the profile");
+        }
+
+        info!("Archived {} workspaces from profile {}", workspaces.len(), profile_path);
+        Ok(())
+    }
+
+    /// Get the workspaces in the archive store, most recently used first
+    pub fn list_archived_workspaces() -> Result<Vec<Workspace>> {
+        let mut workspaces: Vec<Workspace> = archive::load_archive()?
+            .into_iter()
+            .map(|archived| {
+                let mut workspace = archived.workspace;
+                workspace.parsed_info = crate::workspaces::parser::parse_workspace_path(&workspace.path).ok();
+                workspace
+            })
+            .collect();
+        SortOrder::default().sort(&mut workspaces);
+        Ok(workspaces)
+    }
+
+    /// Move archived workspaces back into the given profile; returns how many were restored
+    ///
+    /// Workspaces are matched by ID or path. Entries already in the profile's recently
+    /// opened list are not added again. Each workspace is removed from the archive as soon
+    /// as it is restored, and restoring a workspace whose storage directory was already
+    /// moved back only adds its entry, so a restore that failed part way can be re-run.
+    pub fn restore_workspaces(profile_path: &str, ids_or_paths: &[String]) -> Result<usize> {
+        if profile_path == crate::workspaces::zed::ZED_PROFILE_NAME {
+            return Err(WorkspaceError::Write("restoring workspaces to Zed is not supported".to_string()).into());
+        }
+
+        let mut archived = archive::load_archive()?;
+        let matches = |a: &archive::ArchivedWorkspace, id: &String| {
+            &a.workspace.id == id || paths::normalize_path(&a.workspace.path) == paths::normalize_path(id)
+        };
+        if let Some(missing) = ids_or_paths.iter().find(|id| !archived.iter().any(|a| matches(a, id))) {
+            return Err(WorkspaceError::NotFound(format!("{} in the archive", missing)).into());
+        }
+
+        let existing = get_workspaces(profile_path, None)?;
+        let profile_path = expand_tilde(profile_path)?;
+        let mut restored = 0;
+
+        for id in ids_or_paths {
+            // Already restored when the same workspace was given twice
+            let Some(pos) = archived.iter().position(|a| matches(a, id)) else { continue };
+            let entry = &archived[pos];
+            let workspace = &entry.workspace;
+            info!("Restoring workspace: {} ({})", workspace.id, workspace.path);
+
+            if let Some(archived_dir) = &entry.archived_storage_dir {
+                let archived_dir = std::path::Path::new(archived_dir);
+                let target = paths::workspace_id_to_storage_path(&profile_path, &workspace.id);
+                if !archived_dir.exists() {
+                    if target.exists() {
+                        debug!("Storage directory was already restored: {}", target.display());
+                    } else {
+                        warn!("Archived storage directory is missing: {}", archived_dir.display());
+                    }
+                } else if target.exists() {
+                    warn!("Storage directory already exists, keeping it: {}", target.display());
+                } else {
+                    archive::move_dir(archived_dir, &target)?;
+                }
+            }
+
+            if existing.iter().any(|ws| crate::workspaces::models::same_path(ws, workspace)) {
+                debug!("Workspace already in recently opened list: {}", workspace.path);
+            } else if let Some(recent_entry) = &entry.recent_entry {
+                add_database_entry(&profile_path, recent_entry.clone())
+                    .map_err(|e| WorkspaceError::Write(format!("{:#}", e)))?;
+            }
+
+            archived.remove(pos);
+            archive::save_archive(&archived)?;
+            restored += 1;
+        }
+
+        info!("Restored {} workspaces to profile {}", restored, profile_path);
+        Ok(restored)
+    }
+
+    /// Save all workspaces of the profile to `<snapshot_path>/<timestamp>.json`
+    ///
+    /// The snapshot contains every field of every workspace and can be brought back
+    /// with `restore_snapshot`, e.g. before cleaning or batch deleting workspaces.
+    pub fn snapshot_workspaces(profile_path: &str, snapshot_path: &str) -> Result<()> {
+        let workspaces = get_workspaces(profile_path, None)?;
+        let snapshot_dir = std::path::PathBuf::from(expand_tilde(snapshot_path)?);
+        std::fs::create_dir_all(&snapshot_dir)
+            .with_context(|| format!("Failed to create snapshot directory: {}", snapshot_dir.display()))?;
+
+        let file = snapshot_dir.join(format!("{}.json", chrono::Local::now().format("%Y%m%d-%H%M%S")));
+        if file.exists() {
+            return Err(WorkspaceError::AlreadyExists(file.display().to_string()).into());
+        }
+
+        std::fs::write(&file, serde_json::to_string_pretty(&workspaces)?)
+            .with_context(|| format!("Failed to write snapshot: {}", file.display()))?;
+
+        info!("Saved snapshot of {} workspaces to {}", workspaces.len(), file.display());
+        Ok(())
+    }
+
+    /// Add the workspaces of a snapshot that are no longer in the profile back to it
+    ///
+    /// Workspaces are added with `add_workspace`, so only the recently opened entry is
+    /// restored. Workspaces that can't be added (e.g. local folders that were removed)
+    /// are listed in `RestoreReport::failed` instead of failing the whole restore.
+    pub fn restore_snapshot(profile_path: &str, snapshot_file: &str) -> Result<RestoreReport> {
+        let snapshot_file = expand_tilde(snapshot_file)?;
+        let content = std::fs::read_to_string(&snapshot_file)
+            .with_context(|| format!("Failed to read snapshot: {}", snapshot_file))?;
+        let snapshot: Vec<Workspace> = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse snapshot: {}", snapshot_file))?;
+
+        let existing = get_workspaces(profile_path, None)?;
+        let mut report = RestoreReport::default();
+
+        // Add the oldest first so the most recently used workspaces end up on top
+        for workspace in snapshot.iter().rev() {
+            if existing.iter().any(|ws| crate::workspaces::models::same_path(ws, workspace)) {
+                report.skipped.push(workspace.path.clone());
+                continue;
+            }
+
+            match add_workspace(profile_path, &workspace.path) {
+                Ok(()) => report.restored.push(workspace.path.clone()),
+                Err(e) => match e.downcast_ref::<WorkspaceError>() {
+                    Some(WorkspaceError::AlreadyExists(_)) => report.skipped.push(workspace.path.clone()),
+                    _ => {
+                        warn!("Failed to restore {}: {:#}", workspace.path, e);
+                        report.failed.push((workspace.path.clone(), format!("{:#}", e)));
+                    }
+                },
+            }
+        }
+
+        info!(
+            "Restored {} workspaces from {} ({} already present, {} failed)",
+            report.restored.len(), snapshot_file, report.skipped.len(), report.failed.len()
+        );
+        Ok(report)
+    }
+
+    /// Find the raw recently opened entry for a workspace path in either state database
+    fn find_recent_entry(profile_path: &str, workspace_path: &str) -> Option<serde_json::Value> {
+        let normalized = paths::normalize_path(workspace_path);
+
+        ["User/globalStorage/state.vscdb", "User/state.vscdb"].iter()
+            .map(|relative| format!("{}/{}", profile_path, relative))
+            .filter(|db_path| std::path::Path::new(db_path).exists())
+            .find_map(|db_path| {
+                let conn = open_database(&db_path).ok()?;
+                let value: String = conn.query_row(
+                    "SELECT value FROM ItemTable WHERE key = ?",
+                    ["history.recentlyOpenedPathsList"],
+                    |row| row.get(0)
+                ).ok()?;
+                let json: serde_json::Value = serde_json::from_str(&value).ok()?;
+                json.get("entries")?.as_array()?.iter()
+                    .find(|entry| recent_entry_path(entry).is_some_and(|p| paths::normalize_path(p) == normalized))
+                    .cloned()
+            })
+    }
+
+    /// Get the folder or workspace path of a recently opened entry
+    fn recent_entry_path(entry: &serde_json::Value) -> Option<&str> {
+        if let Some(folder_uri) = entry.get("folderUri").and_then(|u| u.as_str()) {
+            Some(folder_uri)
+        } else if let Some(workspace) = entry.get("workspace") {
+            workspace.get("uri").and_then(|u| u.as_str())
+                .or_else(|| workspace.get("configPath").and_then(|p| p.as_str()))
+        } else {
+            entry.get("fileUri").and_then(|u| u.as_str())
+        }
     }
     
     // Helper function to delete a workspace storage directory
-    fn delete_storage_workspace(storage_dir: &str) -> Result<()> {
+    fn delete_storage_workspace(storage_dir: &str, dry_run: bool) -> Result<()> {
         info!("Deleting storage directory: {}", storage_dir);
         
         if !std::path::Path::new(storage_dir).exists() {
@@ -171,6 +1052,11 @@ mod api {
             return Ok(());
         }
         
+        if dry_run {
+            info!("Dry run: would remove storage directory {}", storage_dir);
+            return Ok(());
+        }
+        
         // Remove the directory and all its contents
         std::fs::remove_dir_all(storage_dir)
             .with_context(|| format!("Failed to delete storage directory: {}", storage_dir))?;
@@ -187,7 +1073,7 @@ mod api {
     }
     
     // Helper function to delete a workspace from a database
-    fn delete_database_workspace(db_path: &str, workspace_path: &str) -> Result<()> {
+    fn delete_database_workspace(pool: &DbConnectionPool, db_path: &str, workspace_path: &str, dry_run: bool) -> Result<()> {
         info!("Deleting workspace {} from database: {}", workspace_path, db_path);
         
         // Check if the database exists
@@ -196,8 +1082,8 @@ mod api {
             return Ok(());
         }
         
-        // Open the database connection
-        let conn = rusqlite::Connection::open(db_path)
+        // Reuse the connection of an earlier workspace of the batch
+        let conn = pool.get(db_path)
             .with_context(|| format!("Failed to open database: {}", db_path))?;
         
         // Check if the ItemTable exists
@@ -212,6 +1098,45 @@ mod api {
             return Ok(());
         }
         
+        if dry_run {
+            if remove_recent_entries(&conn, workspace_path)?.is_some() {
+                info!("Dry run: would update history.recentlyOpenedPathsList in {} to remove {}",
+                      db_path, workspace_path);
+            } else {
+                info!("No matching entries found in database to remove");
+            }
+            return Ok(());
+        }
+        
+        // Back up the database before the first write
+        backup_database(&conn, db_path)?;
+        
+        // Read and update the list in one transaction so concurrent changes aren't lost
+        let updated = with_transaction(&conn, |conn| {
+            match remove_recent_entries(conn, workspace_path)? {
+                Some(updated_json) => Ok(Some(conn.execute(
+                    "UPDATE ItemTable SET value = ? WHERE key = ?",
+                    [&updated_json, "history.recentlyOpenedPathsList"]
+                )?)),
+                None => Ok(None),
+            }
+        });
+        match updated {
+            Ok(Some(rows)) if rows > 0 => info!("Successfully updated database"),
+            Ok(Some(_)) => warn!("No rows were updated in the database"),
+            Ok(None) => info!("No matching entries found in database to remove"),
+            Err(e) => {
+                warn!("Failed to update database: {}", e);
+                return Err(anyhow::anyhow!("Failed to update database: {}", e));
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// Remove the entries of a workspace from the recently opened list of a database.
+    /// Returns the updated list, or `None` if the list is missing, unreadable or has no match.
+    fn remove_recent_entries(conn: &rusqlite::Connection, workspace_path: &str) -> Result<Option<String>> {
         // Get the history.recentlyOpenedPathsList entry
         let json_value: String = match conn.query_row(
             "SELECT value FROM ItemTable WHERE key = ?",
@@ -221,7 +1146,7 @@ mod api {
             Ok(value) => value,
             Err(e) => {
                 warn!("Failed to retrieve history.recentlyOpenedPathsList: {}", e);
-                return Ok(());
+                return Ok(None);
             }
         };
         
@@ -230,7 +1155,7 @@ mod api {
             Ok(parsed) => parsed,
             Err(e) => {
                 warn!("Failed to parse JSON from database: {}", e);
-                return Ok(());
+                return Ok(None);
             }
         };
         
@@ -282,38 +1207,11 @@ mod api {
             false
         };
         
-        // Only update the database if we actually removed something
-        if entries_modified {
-            // Serialize the updated JSON back to a string
-            let updated_json = match serde_json::to_string(&json) {
-                Ok(serialized) => serialized,
-                Err(e) => {
-                    warn!("Failed to serialize updated JSON: {}", e);
-                    return Ok(());
-                }
-            };
-            
-            // Update the database entry
-            match conn.execute(
-                "UPDATE ItemTable SET value = ? WHERE key = ?",
-                [&updated_json, "history.recentlyOpenedPathsList"]
-            ) {
-                Ok(rows) => {
-                    if rows > 0 {
-                        info!("Successfully updated database");
-                    } else {
-                        warn!("No rows were updated in the database");
-                    }
-                },
-                Err(e) => {
-                    warn!("Failed to update database: {}", e);
-                    return Err(anyhow::anyhow!("Failed to update database: {}", e));
-                }
-            }
-        } else {
-            info!("No matching entries found in database to remove");
+        if !entries_modified {
+            return Ok(None);
         }
         
-        Ok(())
+        // Serialize the updated JSON back to a string
+        Ok(Some(serde_json::to_string(&json)?))
     }
 } 
\ No newline at end of file
@@ -1,7 +1,8 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize, Serializer};
 use crate::workspaces::parser::WorkspacePathInfo;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Workspace {
     pub id: String,
     pub name: Option<String>,
@@ -10,17 +11,211 @@ pub struct Workspace {
     pub storage_path: Option<String>,
     #[serde(skip_deserializing)]
     #[serde(serialize_with = "serialize_sources")]
+    #[schemars(with = "Vec<String>")]
     pub sources: Vec<WorkspaceSource>,
     #[serde(skip_deserializing)]
     #[serde(serialize_with = "serialize_parsed_info")]
     pub parsed_info: Option<WorkspacePathInfo>,
+    /// Whether the entry is pinned in VSCode's recently opened list
+    #[serde(default)]
+    pub pinned: bool,
+    /// Result of the last explicit existence check (only meaningful when `exists_checked` is set)
+    #[serde(default)]
+    pub exists: bool,
+    /// Whether `exists` has been populated by an existence check
+    #[serde(default)]
+    pub exists_checked: bool,
+    /// Number of times the workspace was opened through this tool
+    #[serde(default)]
+    pub open_count: Option<u64>,
+    /// Number of files in the workspace listed in the editor history
+    #[serde(default)]
+    pub recent_file_count: usize,
+    /// Checked out Git branch of a local folder workspace (filled in by the TUI)
+    #[serde(default)]
+    pub git_branch: Option<String>,
+    /// Color assigned to the workspace in the editor, as a readable name (e.g. `red`)
+    #[serde(default)]
+    pub color: Option<String>,
+    /// Extensions recommended in `.vscode/extensions.json` of a local folder workspace (filled in by the TUI)
+    #[serde(default)]
+    pub recommended_extensions: Vec<String>,
+}
+
+/// Check whether two workspaces point at the same location, comparing normalized paths
+pub fn same_path(a: &Workspace, b: &Workspace) -> bool {
+    path_key(a) == path_key(b)
+}
+
+/// Normalized path used to compare workspace locations
+fn path_key(workspace: &Workspace) -> String {
+    crate::workspaces::paths::normalize_path(&workspace.path)
+}
+
+/// Workspaces are equal when they point at the same location (see [`same_path`]),
+/// regardless of their IDs, sources or metadata
+impl PartialEq for Workspace {
+    fn eq(&self, other: &Self) -> bool {
+        same_path(self, other)
+    }
+}
+
+impl Eq for Workspace {}
+
+/// Workspaces are ordered by normalized path, consistent with equality.
+/// Use [`SortOrder::sort`] for the listing order (most recently used first).
+impl Ord for Workspace {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        path_key(self).cmp(&path_key(other))
+    }
+}
+
+impl PartialOrd for Workspace {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub enum WorkspaceSource {
     Storage(String),     // From workspace.json file with path
     Database(String),    // From state.vscdb with entry key
     Zed(String),         // From Zed's db.sqlite with channel name
+    Profile(String),     // From a named VSCode profile with profile name
+}
+
+impl WorkspaceSource {
+    /// Lowercase name of the kind of source (`storage`, `database`, `zed` or `profile`)
+    pub fn kind(&self) -> &'static str {
+        match self {
+            WorkspaceSource::Storage(_) => "storage",
+            WorkspaceSource::Database(_) => "database",
+            WorkspaceSource::Zed(_) => "zed",
+            WorkspaceSource::Profile(_) => "profile",
+        }
+    }
+    
+    /// Storage path, database key, Zed channel or profile name of the source
+    pub fn value(&self) -> &str {
+        match self {
+            WorkspaceSource::Storage(value)
+            | WorkspaceSource::Database(value)
+            | WorkspaceSource::Zed(value)
+            | WorkspaceSource::Profile(value) => value,
+        }
+    }
+    
+    /// Check whether the source matches a `:source:` filter value such as `storage` or
+    /// `profile:work`; the part after the kind must be contained in the source's value
+    pub fn matches_filter(&self, filter: &str) -> bool {
+        let filter = filter.to_lowercase();
+        let (kind, value) = match filter.split_once(':') {
+            Some((kind, value)) => (kind, Some(value)),
+            None => (filter.as_str(), None),
+        };
+        
+        self.kind() == kind && match value {
+            Some(value) => self.value().to_lowercase().contains(value),
+            None => true,
+        }
+    }
+}
+
+/// Order in which workspaces are listed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    /// Most recently used first
+    #[default]
+    LastUsed,
+    /// Alphabetically by name (or folder basename when unnamed)
+    Name,
+    /// Alphabetically by path
+    Path,
+}
+
+impl SortOrder {
+    /// Sort the workspaces in place using this order
+    pub fn sort(self, workspaces: &mut [Workspace]) {
+        workspaces.sort_by(|a, b| WorkspaceSort(a, self).cmp(&WorkspaceSort(b, self)));
+    }
+    
+    /// Sort the workspaces in place by this field, ascending or descending
+    pub fn sort_directed(self, workspaces: &mut [Workspace], descending: bool) {
+        workspaces.sort_by(|a, b| {
+            let ordering = self.compare(a, b);
+            if descending { ordering.reverse() } else { ordering }
+        });
+    }
+    
+    /// Whether this field is sorted descending by default (most recent first for last used)
+    pub fn default_descending(self) -> bool {
+        matches!(self, SortOrder::LastUsed)
+    }
+    
+    /// Compare two workspaces by this field in ascending order
+    pub fn compare(self, a: &Workspace, b: &Workspace) -> std::cmp::Ordering {
+        match self {
+            SortOrder::LastUsed => a.last_used.cmp(&b.last_used),
+            SortOrder::Name => sort_name(a).cmp(&sort_name(b)),
+            SortOrder::Path => a.path.to_lowercase().cmp(&b.path.to_lowercase()),
+        }
+    }
+}
+
+/// Default workspace ordering: most recently used first, then by name, then by path
+fn default_order(a: &Workspace, b: &Workspace) -> std::cmp::Ordering {
+    b.last_used.cmp(&a.last_used)
+        .then_with(|| sort_name(a).cmp(&sort_name(b)))
+        .then_with(|| a.path.cmp(&b.path))
+}
+
+/// Workspace ordered by the given [`SortOrder`] in its natural direction, with ties broken
+/// by the default ordering (most recently used, then name, then path)
+#[derive(Debug, Clone, Copy)]
+pub struct WorkspaceSort<'a>(pub &'a Workspace, pub SortOrder);
+
+impl Ord for WorkspaceSort<'_> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        let ordering = self.1.compare(self.0, other.0);
+        let ordering = if self.1.default_descending() { ordering.reverse() } else { ordering };
+        ordering.then_with(|| default_order(self.0, other.0))
+    }
+}
+
+impl PartialOrd for WorkspaceSort<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for WorkspaceSort<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == std::cmp::Ordering::Equal
+    }
+}
+
+impl Eq for WorkspaceSort<'_> {}
+
+/// Name used when sorting by name (folder basename when unnamed)
+fn sort_name(workspace: &Workspace) -> String {
+    match workspace.name.as_deref() {
+        Some(name) if !name.is_empty() => name.to_lowercase(),
+        _ => crate::workspaces::extract_folder_basename(&workspace.path).to_lowercase(),
+    }
+}
+
+impl std::str::FromStr for SortOrder {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace('-', "_").as_str() {
+            "last_used" | "recent" => Ok(SortOrder::LastUsed),
+            "name" => Ok(SortOrder::Name),
+            "path" => Ok(SortOrder::Path),
+            _ => Err(format!("Unknown sort order '{}' (expected last_used, name or path)", s)),
+        }
+    }
 }
 
 impl Default for WorkspaceSource {
@@ -39,6 +234,7 @@ where
             WorkspaceSource::Storage(path) => format!("Storage({})", path),
             WorkspaceSource::Database(key) => format!("Database({})", key),
             WorkspaceSource::Zed(channel) => format!("Zed({})", channel),
+            WorkspaceSource::Profile(name) => format!("Profile({})", name),
         }
     }).collect();
     
@@ -61,6 +257,9 @@ where
                 "remote_host": info.remote_host,
                 "remote_user": info.remote_user,
                 "remote_port": info.remote_port,
+                "resolved_host": info.resolved_host,
+                "resolved_user": info.resolved_user,
+                "resolved_port": info.resolved_port,
                 "path": info.path,
                 "container_path": info.container_path,
                 "label": info.label,
@@ -77,6 +276,64 @@ where
 }
 
 impl Workspace {
+    /// Combine two entries of the same workspace (e.g. one from storage, one from a database).
+    ///
+    /// The ID and path of `self` are kept. `last_used`, the open and file counts are the
+    /// highest of both, sources are combined (without duplicates, so every database the
+    /// workspace is listed in is kept) and the name, storage path and parsed info are
+    /// taken from `other` when `self` has none.
+    pub fn merge(mut self, other: &Workspace) -> Workspace {
+        self.last_used = self.last_used.max(other.last_used);
+        
+        for source in &other.sources {
+            if !self.sources.contains(source) {
+                self.sources.push(source.clone());
+            }
+        }
+        if self.name.as_deref().unwrap_or_default().is_empty() {
+            self.name = other.name.clone();
+        }
+        if self.storage_path.is_none() {
+            self.storage_path = other.storage_path.clone();
+        }
+        if self.parsed_info.is_none() {
+            self.parsed_info = other.parsed_info.clone();
+        }
+        if self.color.is_none() {
+            self.color = other.color.clone();
+        }
+        self.pinned |= other.pinned;
+        self.open_count = self.open_count.max(other.open_count);
+        self.recent_file_count = self.recent_file_count.max(other.recent_file_count);
+        
+        self
+    }
+    
+    /// Merge workspaces that point at the same location, keeping the order of first appearance.
+    /// The entry with the highest `last_used` wins and the others are merged into it.
+    pub fn dedup(workspaces: Vec<Workspace>) -> Vec<Workspace> {
+        let mut result: Vec<Workspace> = Vec::with_capacity(workspaces.len());
+        let mut index = std::collections::HashMap::new();
+        
+        for workspace in workspaces {
+            let key = crate::workspaces::paths::normalize_path(&workspace.path);
+            let Some(&existing_idx) = index.get(&key) else {
+                index.insert(key, result.len());
+                result.push(workspace);
+                continue;
+            };
+            
+            let existing = &mut result[existing_idx];
+            *existing = if workspace.last_used > existing.last_used {
+                workspace.merge(existing)
+            } else {
+                existing.clone().merge(&workspace)
+            };
+        }
+        
+        result
+    }
+    
     /// Parse the workspace path and return detailed information
     pub fn parse_path(&mut self) -> Option<&WorkspacePathInfo> {
         if self.parsed_info.is_none() {
@@ -92,6 +349,20 @@ impl Workspace {
         self.parsed_info.as_ref()
     }
     
+    /// Get the human-readable path: the parsed path if available, otherwise the raw path
+    pub fn get_display_path(&self) -> &str {
+        self.parsed_info.as_ref()
+            .map(|info| info.path.as_str())
+            .unwrap_or(&self.path)
+    }
+    
+    /// Get the exact original URI or path, as stored by the editor
+    pub fn get_original_uri(&self) -> &str {
+        self.parsed_info.as_ref()
+            .map(|info| info.original_path.as_str())
+            .unwrap_or(&self.path)
+    }
+    
     /// Get the readable label for this workspace
     pub fn get_label(&mut self) -> String {
         if let Some(name) = &self.name {
@@ -149,9 +420,367 @@ impl Workspace {
     /// Check if this is a remote workspace
     pub fn is_remote(&mut self) -> bool {
         if let Some(info) = self.parse_path() {
-            info.remote_authority.is_some()
+            info.is_remote()
         } else {
             false
         }
     }
-} 
\ No newline at end of file
+
+    /// Check if this is a remote workspace, without parsing the path.
+    ///
+    /// Returns `false` if the path has not been parsed yet.
+    pub fn is_remote_parsed(&self) -> bool {
+        self.parsed_info.as_ref().is_some_and(|info| info.is_remote())
+    }
+
+    /// Check if this is a local workspace, without parsing the path.
+    ///
+    /// Returns `false` if the path has not been parsed yet.
+    pub fn is_local_parsed(&self) -> bool {
+        self.parsed_info.as_ref().is_some_and(|info| info.is_local())
+    }
+    
+    /// Get the workspace properties as shell variables (`WORKSPACE_PATH`, `WORKSPACE_NAME`, ...).
+    /// `WORKSPACE_PATH` is a plain path: local `file://` URIs are decoded and remote workspaces
+    /// use the path on the remote machine. Host, user and port are only set when known.
+    // Takes `&mut self` like the other accessors, which cache the parsed path
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_shell_env_vars(&mut self) -> Vec<(String, String)> {
+        let workspace_type = self.get_type();
+        let remote = self.is_remote();
+        let name = match self.name.as_deref() {
+            Some(name) if !name.is_empty() => name.to_string(),
+            _ => crate::workspaces::utils::extract_folder_basename(&self.path),
+        };
+        let path = crate::workspaces::paths::path_from_uri(self.get_display_path())
+            .unwrap_or_else(|_| self.get_display_path().to_string());
+        
+        let mut vars = vec![
+            ("WORKSPACE_ID".to_string(), self.id.clone()),
+            ("WORKSPACE_NAME".to_string(), name),
+            ("WORKSPACE_PATH".to_string(), path),
+            ("WORKSPACE_URI".to_string(), self.get_original_uri().to_string()),
+            ("WORKSPACE_TYPE".to_string(), workspace_type),
+            ("WORKSPACE_REMOTE".to_string(), remote.to_string()),
+            ("WORKSPACE_LAST_USED".to_string(), self.last_used.to_string()),
+        ];
+        
+        if let Some(info) = &self.parsed_info {
+            if let Some(host) = &info.remote_host {
+                vars.push(("WORKSPACE_HOST".to_string(), host.clone()));
+            }
+            if let Some(user) = &info.remote_user {
+                vars.push(("WORKSPACE_USER".to_string(), user.clone()));
+            }
+            if let Some(port) = info.remote_port {
+                vars.push(("WORKSPACE_PORT".to_string(), port.to_string()));
+            }
+            vars.push(("WORKSPACE_TAGS".to_string(), info.tags.join(",")));
+        }
+        
+        vars
+    }
+} 
+
+#[cfg(test)]
+impl Workspace {
+    /// Workspace with the given ID and path and no metadata, for tests
+    pub(crate) fn test_fixture(id: &str, path: &str) -> Workspace {
+        Workspace {
+            id: id.to_string(),
+            name: None,
+            path: path.to_string(),
+            last_used: 0,
+            storage_path: None,
+            sources: Vec::new(),
+            parsed_info: None,
+            pinned: false,
+            exists: false,
+            exists_checked: false,
+            open_count: None,
+            recent_file_count: 0,
+            git_branch: None,
+            color: None,
+            recommended_extensions: Vec::new(),
+        }
+    }
+}
+
+/// Structured workspace filter; every criterion that is set must match
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WorkspaceQuery {
+    /// Case-insensitive text matched against the label, name and path
+    pub text: Option<String>,
+    /// Only remote (true) or only local (false) workspaces
+    pub remote: Option<bool>,
+    /// Workspace type: folder, file or workspace
+    pub workspace_type: Option<String>,
+    /// Tag that must be present (case-insensitive substring)
+    pub tag: Option<String>,
+    /// Only existing (true) or only missing (false) workspaces
+    pub exists: Option<bool>,
+    /// Remote host (case-insensitive substring)
+    pub host: Option<String>,
+    /// Field to sort the results by
+    pub sort_by: SortOrder,
+    /// Sort descending instead of ascending
+    pub sort_desc: bool,
+    /// Maximum number of results to return
+    pub limit: Option<usize>,
+}
+
+impl Default for WorkspaceQuery {
+    fn default() -> Self {
+        Self {
+            text: None,
+            remote: None,
+            workspace_type: None,
+            tag: None,
+            exists: None,
+            host: None,
+            sort_by: SortOrder::LastUsed,
+            sort_desc: SortOrder::LastUsed.default_descending(),
+            limit: None,
+        }
+    }
+}
+
+impl WorkspaceQuery {
+    /// Check whether the workspace matches all criteria of the query
+    pub fn matches(&self, workspace: &mut Workspace) -> bool {
+        if let Some(text) = self.text.as_deref().map(str::to_lowercase).filter(|t| !t.is_empty()) {
+            let label = workspace.get_label().to_lowercase();
+            let name = workspace.name.as_deref().unwrap_or_default().to_lowercase();
+            let path = workspace.path.to_lowercase();
+            if !text.split_whitespace().all(|word|
+                label.contains(word) || name.contains(word) || path.contains(word)
+            ) {
+                return false;
+            }
+        }
+        
+        if let Some(remote) = self.remote {
+            if workspace.is_remote() != remote {
+                return false;
+            }
+        }
+        
+        if let Some(workspace_type) = &self.workspace_type {
+            if !workspace.get_type().eq_ignore_ascii_case(workspace_type) {
+                return false;
+            }
+        }
+        
+        if let Some(tag) = &self.tag {
+            let tag = tag.to_lowercase();
+            let has_tag = workspace.parse_path()
+                .map(|info| info.tags.iter().any(|t| t.to_lowercase().contains(&tag)))
+                .unwrap_or(false);
+            if !has_tag {
+                return false;
+            }
+        }
+        
+        if let Some(host) = &self.host {
+            let host = host.to_lowercase();
+            let host_matches = workspace.parse_path()
+                .and_then(|info| info.remote_host.as_ref())
+                .map(|h| h.to_lowercase().contains(&host))
+                .unwrap_or(false);
+            if !host_matches {
+                return false;
+            }
+        }
+        
+        // Checked last since it touches the filesystem
+        if let Some(exists) = self.exists {
+            if crate::workspaces::workspace_exists(workspace) != exists {
+                return false;
+            }
+        }
+        
+        true
+    }
+}
+
+/// Aggregate statistics over a set of workspaces
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct WorkspaceStats {
+    /// Total number of workspaces
+    pub total: usize,
+    /// Number of workspaces per type (folder, file, workspace)
+    pub by_type: std::collections::HashMap<String, usize>,
+    /// Number of remote workspaces
+    pub remote_count: usize,
+    /// Number of local workspaces
+    pub local_count: usize,
+    /// Number of local workspaces whose path no longer exists
+    pub missing_count: usize,
+    /// Most recently used workspace
+    pub most_recent: Option<Workspace>,
+    /// Least recently used workspace with a known last used time
+    pub oldest_used: Option<Workspace>,
+    /// Distinct remote hosts, sorted
+    pub hosts: Vec<String>,
+    /// Number of remote workspaces per remote host
+    pub by_host: std::collections::HashMap<String, usize>,
+}
+
+/// Result of comparing the workspaces of two profiles
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct WorkspaceComparison {
+    /// Workspaces only present in the first profile
+    pub only_in_a: Vec<Workspace>,
+    /// Workspaces only present in the second profile
+    pub only_in_b: Vec<Workspace>,
+    /// Workspaces present in both profiles, as (first, second)
+    pub in_both: Vec<(Workspace, Workspace)>,
+}
+
+/// Loading stage reported by `get_workspaces`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgressUpdate {
+    /// Reading the workspace storage directories
+    StartingStorage,
+    /// Workspaces found in the storage directories
+    StorageLoaded(usize),
+    /// Reading the state databases
+    StartingDatabase,
+    /// Workspaces found after reading the databases
+    DatabaseLoaded(usize),
+    /// Parsing the paths of the workspaces found
+    Processing(usize),
+    /// Loading finished with this many workspaces
+    Done(usize),
+}
+
+/// An entry of a recently opened list that could not be read as a workspace
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WorkspaceParseWarning {
+    /// Position of the entry in the list
+    pub entry_index: usize,
+    /// Start of the raw entry, to find it in the database
+    pub entry_preview: String,
+    /// Why the entry was skipped
+    pub reason: String,
+    /// Database the entry was read from (relative to the profile)
+    pub source: String,
+}
+
+impl WorkspaceParseWarning {
+    /// Maximum number of characters kept in `entry_preview`
+    const PREVIEW_CHARS: usize = 80;
+
+    pub fn new(entry_index: usize, entry: &str, reason: String, source: &str) -> Self {
+        let mut entry_preview: String = entry.chars().take(Self::PREVIEW_CHARS).collect();
+        if entry.chars().count() > Self::PREVIEW_CHARS {
+            entry_preview.push('…');
+        }
+        
+        Self {
+            entry_index,
+            entry_preview,
+            reason,
+            source: source.to_string(),
+        }
+    }
+}
+
+/// Result of restoring a snapshot with `restore_snapshot`
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RestoreReport {
+    /// Paths of the workspaces added back to the profile
+    pub restored: Vec<String>,
+    /// Paths of the workspaces that were already in the profile
+    pub skipped: Vec<String>,
+    /// Paths of the workspaces that could not be added, with the reason
+    pub failed: Vec<(String, String)>,
+}
+
+/// A named VSCode profile (VSCode 1.75+) stored under `User/profiles/<id>` of a base profile
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NamedProfile {
+    /// Profile ID, the name of its directory under `User/profiles`
+    pub id: String,
+    /// Display name chosen in VSCode
+    pub name: String,
+    /// Full path to the profile directory
+    pub path: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn workspace(id: &str, path: &str, last_used: i64, source: WorkspaceSource) -> Workspace {
+        Workspace {
+            last_used,
+            sources: vec![source],
+            ..Workspace::test_fixture(id, path)
+        }
+    }
+
+    #[test]
+    fn test_source_matches_filter() {
+        let source = WorkspaceSource::Profile("Work".to_string());
+        assert!(source.matches_filter("profile"));
+        assert!(source.matches_filter("profile:work"));
+        assert!(!source.matches_filter("profile:home"));
+        assert!(!source.matches_filter("storage"));
+        assert!(WorkspaceSource::Database("User/state.vscdb".to_string()).matches_filter("database:user/"));
+    }
+
+    #[test]
+    fn test_dedup_merges_same_path() {
+        let mut from_db = workspace("db-1", "file:///home/alice/api/", 200, WorkspaceSource::Database("User/state.vscdb".to_string()));
+        from_db.name = Some("API".to_string());
+        let workspaces = vec![
+            workspace("abc", "/home/alice/api", 100, WorkspaceSource::Storage("workspaceStorage/abc/workspace.json".to_string())),
+            workspace("def", "/home/alice/web", 50, WorkspaceSource::Storage("workspaceStorage/def/workspace.json".to_string())),
+            from_db,
+        ];
+
+        assert!(workspaces[0] == workspaces[2]);
+        assert!(workspaces[0] != workspaces[1]);
+
+        let deduped = Workspace::dedup(workspaces);
+        assert_eq!(deduped.len(), 2);
+        assert_eq!(deduped[0].id, "db-1");
+        assert_eq!(deduped[0].last_used, 200);
+        assert_eq!(deduped[0].name.as_deref(), Some("API"));
+        assert_eq!(deduped[0].sources.len(), 2);
+        assert_eq!(deduped[1].id, "def");
+    }
+
+    #[test]
+    fn test_is_remote_parsed_does_not_parse() {
+        let mut remote = workspace("a", "vscode-remote://ssh-remote+host/home/alice/api", 0, WorkspaceSource::Storage("workspaceStorage/a/workspace.json".to_string()));
+        assert!(!remote.is_remote_parsed());
+        assert!(!remote.is_local_parsed());
+        assert!(remote.parsed_info.is_none());
+
+        remote.parse_path();
+        assert!(remote.is_remote_parsed());
+        assert!(!remote.is_local_parsed());
+
+        let mut local = workspace("b", "/home/alice/web", 0, WorkspaceSource::Storage("workspaceStorage/b/workspace.json".to_string()));
+        local.parse_path();
+        assert!(local.is_local_parsed());
+        assert!(!local.is_remote_parsed());
+    }
+
+    #[test]
+    fn test_ord_matches_path_equality() {
+        let storage = || WorkspaceSource::Storage("workspaceStorage/a/workspace.json".to_string());
+        let mut workspaces = [
+            workspace("a", "/home/alice/web", 300, storage()),
+            workspace("b", "file:///home/alice/api/", 100, storage()),
+            workspace("c", "/home/alice/api", 200, storage()),
+        ];
+
+        assert_eq!(workspaces[1].cmp(&workspaces[2]), std::cmp::Ordering::Equal);
+        workspaces.sort();
+        assert_eq!(workspaces.iter().map(|ws| ws.id.as_str()).collect::<Vec<_>>(), ["b", "c", "a"]);
+    }
+}
@@ -0,0 +1,39 @@
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::fs;
+
+use crate::workspaces::paths::{app_data_path, normalize_path, write_app_data_file};
+
+/// Name of the notes file in the data directory
+const NOTES_FILE: &str = "notes.json";
+
+/// Load all workspace notes, keyed by normalized workspace path
+pub fn load_notes() -> Result<HashMap<String, String>> {
+    let path = app_data_path(NOTES_FILE)?;
+    if !path.exists() {
+        return Ok(HashMap::new());
+    }
+
+    let content = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read notes: {}", path.display()))?;
+    serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse notes: {}", path.display()))
+}
+
+/// Get the key of a workspace in the notes file
+pub fn note_key(workspace_path: &str) -> String {
+    normalize_path(workspace_path)
+}
+
+/// Set the note of a workspace, removing it when the note is blank
+pub fn set_workspace_note(workspace_path: &str, note: &str) -> Result<()> {
+    let mut notes = load_notes()?;
+    if note.trim().is_empty() {
+        notes.remove(&note_key(workspace_path));
+    } else {
+        notes.insert(note_key(workspace_path), note.to_string());
+    }
+
+    write_app_data_file(NOTES_FILE, &serde_json::to_string_pretty(&notes)?)
+        .context("Failed to save notes")
+}
@@ -1,3 +1,4 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use urlencoding::decode;
@@ -5,11 +6,12 @@ use anyhow::{Result, anyhow};
 use log::{debug, warn};
 
 /// WorkspacePathInfo represents the fully parsed information from a workspace path
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct WorkspacePathInfo {
     /// Original path as stored in the workspace
     pub original_path: String,
     /// The type of workspace (folder, file, workspace)
+    #[serde(rename = "type")] // Same key as serialize_parsed_info
     pub workspace_type: WorkspaceType,
     /// For remote workspaces, the remote authority (e.g., SSH host)
     pub remote_authority: Option<String>,
@@ -19,6 +21,15 @@ pub struct WorkspacePathInfo {
     pub remote_user: Option<String>,
     /// Port for remote connections
     pub remote_port: Option<u16>,
+    /// For SSH aliases, the `HostName` set in ~/.ssh/config
+    #[serde(default)]
+    pub resolved_host: Option<String>,
+    /// For SSH aliases, the `User` set in ~/.ssh/config
+    #[serde(default)]
+    pub resolved_user: Option<String>,
+    /// For SSH aliases, the `Port` set in ~/.ssh/config
+    #[serde(default)]
+    pub resolved_port: Option<u16>,
     /// Local path on the remote machine
     pub path: String,
     /// Container path for devcontainers
@@ -29,7 +40,19 @@ pub struct WorkspacePathInfo {
     pub tags: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+impl WorkspacePathInfo {
+    /// Check if the path points to a remote machine (SSH, WSL, container, ...)
+    pub fn is_remote(&self) -> bool {
+        self.remote_authority.is_some()
+    }
+
+    /// Check if the path is on the local machine
+    pub fn is_local(&self) -> bool {
+        !self.is_remote()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 #[derive(Default)]
 pub enum WorkspaceType {
     #[default]
@@ -59,17 +82,33 @@ pub fn parse_workspace_path(path: &str) -> Result<WorkspacePathInfo> {
         remote_host: None,
         remote_user: None,
         remote_port: None,
+        resolved_host: None,
+        resolved_user: None,
+        resolved_port: None,
         path: path.to_string(),
         container_path: None,
         label: None,
         tags: Vec::new(),
     };
 
+    // Handle GitHub repository URLs opened through Codespaces
+    if let Some(repo_path) = path.strip_prefix("https://github.com/") {
+        info.workspace_type = WorkspaceType::Workspace;
+        info.remote_authority = Some("github.com".to_string());
+        info.remote_host = Some("github.com".to_string());
+        info.path = repo_path.trim_end_matches('/').trim_end_matches(".git").to_string();
+        info.tags.push("remote".to_string());
+        info.tags.push("github".to_string());
+        info.tags.push("codespaces".to_string());
+        debug!("Parsed as GitHub Codespaces workspace: {}", info.path);
+        return Ok(info);
+    }
     
     // Handle simple local folder path
     if !path.starts_with("vscode-remote://") {
         // check if it is a file or a folder
-        if std::path::Path::new(path).is_file() {
+        let local_path = crate::workspaces::paths::path_from_uri(path).unwrap_or_else(|_| path.to_string());
+        if std::path::Path::new(&local_path).is_file() {
             info.workspace_type = WorkspaceType::File;
             debug!("Parsed as local file: {}", path);
         } else {
@@ -153,6 +192,8 @@ pub fn parse_workspace_path(path: &str) -> Result<WorkspacePathInfo> {
                 parse_ssh_remote_string(ssh_remote, &mut info);
             }
         }
+        
+        resolve_ssh_alias(&mut info);
     }
     // Handle Dev Container remote
     else if let Some(container_remote) = remote_authority.strip_prefix("dev-container+") {
@@ -224,10 +265,92 @@ pub fn parse_workspace_path(path: &str) -> Result<WorkspacePathInfo> {
         }
     }
     
+    // Handle containers attached to with "Attach to Running Container"
+    else if let Some(container_remote) = remote_authority.strip_prefix("attached-container+") {
+        info.tags.push("attached-container".to_string());
+        
+        match decode_hex_if_needed(container_remote) {
+            Ok(decoded_config) if decoded_config.starts_with('{') => {
+                debug!("Parsing JSON attached container config: {}", decoded_config);
+                if let Err(e) = parse_attached_container_config(&decoded_config, &mut info) {
+                    warn!("Failed to parse attached container JSON config: {}", e);
+                }
+            },
+            Ok(container_name) => {
+                info.remote_host = Some(container_name.trim_start_matches('/').to_string());
+            },
+            Err(e) => {
+                warn!("Failed to decode hex-encoded attached container: {}", e);
+                info.remote_host = Some(container_remote.to_string());
+            }
+        }
+    }
+    
     debug!("Parsed workspace info: {:?}", info);
     Ok(info)
 }
 
+/// Parse the JSON config of an attached container.
+///
+/// Plain containers are identified by `containerName`; Docker Compose services by
+/// `composeConfig.files` and `service`, stored as `<service>@<compose-file-name>`.
+fn parse_attached_container_config(json_config: &str, info: &mut WorkspacePathInfo) -> Result<()> {
+    let config: serde_json::Value = serde_json::from_str(json_config)?;
+    
+    if let Some(compose) = config.get("composeConfig") {
+        info.tags.push("docker-compose".to_string());
+        
+        let service = config.get("service")
+            .or_else(|| compose.get("service"))
+            .and_then(|service| service.as_str())
+            .ok_or_else(|| anyhow!("Compose config without a service"))?;
+        let compose_file = compose.get("files")
+            .and_then(|files| files.as_array())
+            .and_then(|files| files.first())
+            .and_then(|file| file.as_str())
+            .map(|file| file.rsplit(['/', '\\']).next().unwrap_or(file).to_string());
+        
+        info.remote_host = Some(match compose_file {
+            Some(file) => format!("{}@{}", service, file),
+            None => service.to_string(),
+        });
+        return Ok(());
+    }
+    
+    let container_name = config.get("containerName")
+        .and_then(|name| name.as_str())
+        .ok_or_else(|| anyhow!("Attached container config without a containerName"))?;
+    info.remote_host = Some(container_name.trim_start_matches('/').to_string());
+    Ok(())
+}
+
+/// Get the Docker Compose service and file of an attached Compose container
+pub fn compose_service(info: &WorkspacePathInfo) -> Option<(&str, Option<&str>)> {
+    if !info.tags.iter().any(|tag| tag == "docker-compose") {
+        return None;
+    }
+    
+    let host = info.remote_host.as_deref()?;
+    Some(match host.split_once('@') {
+        Some((service, file)) => (service, Some(file)),
+        None => (host, None),
+    })
+}
+
+/// Fill in the resolved connection settings of an SSH host alias from ~/.ssh/config
+fn resolve_ssh_alias(info: &mut WorkspacePathInfo) {
+    let Some(alias) = info.remote_host.as_deref() else {
+        return;
+    };
+    
+    if let Some(resolved) = crate::workspaces::ssh_config::resolve_ssh_alias(alias) {
+        debug!("Resolved SSH alias {}: {:?}", alias, resolved);
+        info.resolved_host = resolved.host_name.filter(|host| host != alias);
+        info.resolved_user = resolved.user;
+        info.resolved_port = resolved.port;
+    }
+}
+
 /// Try to decode a hex-encoded string (especially for JSON config in remote URIs)
 pub fn decode_hex_if_needed(input: &str) -> Result<String> {
     // Check if it might be hex encoded
@@ -272,9 +395,14 @@ fn parse_json_remote_config(json_config: &str) -> Result<RemoteConfig> {
             .map(String::from)
         );
     
-    let host_path = config.get("hostPath")
-        .and_then(|path| path.as_str())
-        .map(String::from);
+    // Configs with several compose files list them all, the first one is used
+    let host_path = match config.get("hostPath") {
+        Some(serde_json::Value::Array(paths)) => paths.iter()
+            .find_map(|path| path.as_str())
+            .map(String::from),
+        Some(path) => path.as_str().map(String::from),
+        None => None,
+    };
     
     let scheme = config.get("scheme")
         .and_then(|scheme| scheme.as_str())
@@ -430,6 +558,54 @@ mod tests {
         assert!(info.tags.contains(&"devcontainer".to_string()));
     }
     
+    #[test]
+    fn test_parse_dev_container_host_path() {
+        let hex = |json: &str| json.bytes().map(|b| format!("{:02x}", b)).collect::<String>();
+        
+        let single = hex(r#"{"hostPath":"/home/alice/app"}"#);
+        let info = parse_workspace_path(&format!("vscode-remote://dev-container+{}/workspaces/app", single)).unwrap();
+        assert_eq!(info.path, "/home/alice/app");
+        assert_eq!(info.container_path, Some("/workspaces/app".to_string()));
+        
+        let multiple = hex(r#"{"hostPath":["/home/alice/app/docker-compose.yml","/home/alice/app/docker-compose.dev.yml"]}"#);
+        let info = parse_workspace_path(&format!("vscode-remote://dev-container+{}/workspaces/app", multiple)).unwrap();
+        assert_eq!(info.path, "/home/alice/app/docker-compose.yml");
+        assert_eq!(info.container_path, Some("/workspaces/app".to_string()));
+    }
+    
+    #[test]
+    fn test_parse_attached_container() {
+        let hex = |json: &str| json.bytes().map(|b| format!("{:02x}", b)).collect::<String>();
+        
+        let compose = hex(r#"{"composeConfig":{"files":["/home/alice/app/docker-compose.yml"]},"service":"app"}"#);
+        let info = parse_workspace_path(&format!("vscode-remote://attached-container+{}/workspace", compose)).unwrap();
+        assert_eq!(info.remote_host, Some("app@docker-compose.yml".to_string()));
+        assert!(info.tags.contains(&"attached-container".to_string()));
+        assert!(info.tags.contains(&"docker-compose".to_string()));
+        assert_eq!(compose_service(&info), Some(("app", Some("docker-compose.yml"))));
+        
+        let container = hex(r#"{"containerName":"/brave_turing"}"#);
+        let info = parse_workspace_path(&format!("vscode-remote://attached-container+{}/workspace", container)).unwrap();
+        assert_eq!(info.remote_host, Some("brave_turing".to_string()));
+        assert_eq!(info.path, "/workspace");
+        assert!(!info.tags.contains(&"docker-compose".to_string()));
+        assert_eq!(compose_service(&info), None);
+    }
+    
+    #[test]
+    fn test_parse_github_codespaces() {
+        let path = "https://github.com/vhqtvn/vscode-workspaces-editor";
+        let info = parse_workspace_path(path).unwrap();
+        
+        assert_eq!(info.original_path, path);
+        assert_eq!(info.workspace_type, WorkspaceType::Workspace);
+        assert_eq!(info.remote_host, Some("github.com".to_string()));
+        assert_eq!(info.path, "vhqtvn/vscode-workspaces-editor");
+        assert!(info.tags.contains(&"remote".to_string()));
+        assert!(info.tags.contains(&"github".to_string()));
+        assert!(info.tags.contains(&"codespaces".to_string()));
+    }
+    
     #[test]
     fn test_decode_hex() {
         // Test JSON input
@@ -443,6 +619,48 @@ mod tests {
         assert_eq!(result, "{\"host\":\"example.com\"}");
     }
 
+    #[test]
+    fn test_lookup_ssh_host() {
+        use crate::workspaces::ssh_config::lookup_ssh_host;
+        
+        let config = r#"
+# Personal servers
+Host myserver dev-*
+    HostName 10.0.0.5
+    User alice
+    Port 2222
+
+Host dev-db !dev-web
+    HostName db.internal
+
+Host *.example.com
+    HostName=%h.internal
+    User "bob"
+
+Host *
+    User root
+    Port 22
+"#;
+        
+        let resolved = lookup_ssh_host(config, "myserver");
+        assert_eq!(resolved.host_name, Some("10.0.0.5".to_string()));
+        assert_eq!(resolved.user, Some("alice".to_string()));
+        assert_eq!(resolved.port, Some(2222));
+        
+        // The first matching block wins for each setting
+        let resolved = lookup_ssh_host(config, "dev-db");
+        assert_eq!(resolved.host_name, Some("10.0.0.5".to_string()));
+        
+        let resolved = lookup_ssh_host(config, "build.example.com");
+        assert_eq!(resolved.host_name, Some("build.example.com.internal".to_string()));
+        assert_eq!(resolved.user, Some("bob".to_string()));
+        assert_eq!(resolved.port, Some(22));
+        
+        let resolved = lookup_ssh_host(config, "other");
+        assert!(resolved.host_name.is_none());
+        assert_eq!(resolved.user, Some("root".to_string()));
+    }
+
     #[test]
     fn test_parse_ssh_remote_string() {
         // Test user@host format
@@ -453,6 +671,9 @@ mod tests {
             remote_host: None,
             remote_user: None,
             remote_port: None,
+            resolved_host: None,
+            resolved_user: None,
+            resolved_port: None,
             path: "original/path".to_string(),
             container_path: None,
             label: None,
@@ -497,6 +718,9 @@ mod tests {
             remote_host: None,
             remote_user: None,
             remote_port: None,
+            resolved_host: None,
+            resolved_user: None,
+            resolved_port: None,
             path: "original/path".to_string(),
             container_path: None,
             label: None,
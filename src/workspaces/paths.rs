@@ -1,13 +1,26 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use directories::BaseDirs;
 use home::home_dir;
 use log::debug;
 
 use crate::workspaces::error::WorkspaceError;
+use crate::workspaces::models::NamedProfile;
 use crate::workspaces::zed::ZED_PROFILE_NAME;
 
-/// Get the default VSCode profile path for the current platform
+/// Get the default VSCode profile path for the current platform.
+/// A running VSCode started with `--user-data-dir` takes precedence over the platform default,
+/// followed by a `user-data-dir` set in argv.json.
 pub fn get_default_profile_path() -> Result<String> {
+    if let Some(path) = detect_running_vscode_profile() {
+        debug!("Using profile of running VSCode instance: {}", path);
+        return Ok(path);
+    }
+
+    if let Some(path) = get_profile_from_argv_json() {
+        debug!("Using user-data-dir from argv.json: {}", path);
+        return Ok(path);
+    }
+
     if let Some(base_dirs) = BaseDirs::new() {
         #[allow(unused_variables)]
         let config_dir = base_dirs.config_dir();
@@ -32,6 +45,204 @@ pub fn get_default_profile_path() -> Result<String> {
     Ok(home.join(".config/Code").to_string_lossy().to_string())
 }
 
+/// Get the `user-data-dir` set in VSCode's `argv.json` (`~/.vscode/argv.json` on all platforms).
+///
+/// The file allows comments like the other VSCode config files. Only directories that
+/// exist are returned.
+pub fn get_profile_from_argv_json() -> Option<String> {
+    let argv_path = home_dir()?.join(".vscode").join("argv.json");
+    let content = std::fs::read_to_string(&argv_path).ok()?;
+    let json: serde_json::Value = serde_json::from_str(&crate::workspaces::utils::strip_jsonc(&content))
+        .map_err(|e| debug!("Failed to parse {}: {}", argv_path.display(), e))
+        .ok()?;
+
+    let dir = expand_tilde(json.get("user-data-dir")?.as_str()?).ok()?;
+    std::path::Path::new(&dir).is_dir().then_some(dir)
+}
+
+/// Get the `--user-data-dir` of a running VSCode instance, if any.
+///
+/// Processes are read from `/proc/<pid>/cmdline` on Linux and `ps` on macOS; other
+/// platforms are not inspected. Only directories that exist are returned. The processes
+/// are only scanned on the first call, later calls return the same result.
+pub fn detect_running_vscode_profile() -> Option<String> {
+    static DETECTED: std::sync::OnceLock<Option<String>> = std::sync::OnceLock::new();
+    DETECTED.get_or_init(scan_running_vscode_profile).clone()
+}
+
+/// Scan the running processes for a VSCode instance started with `--user-data-dir`
+fn scan_running_vscode_profile() -> Option<String> {
+    #[cfg(target_os = "linux")]
+    let command_lines: Vec<Vec<String>> = std::fs::read_dir("/proc").ok()?
+        .flatten()
+        .filter(|entry| entry.file_name().to_string_lossy().chars().all(|c| c.is_ascii_digit()))
+        .filter_map(|entry| std::fs::read(entry.path().join("cmdline")).ok())
+        .map(|cmdline| {
+            cmdline.split(|b| *b == 0)
+                .filter(|arg| !arg.is_empty())
+                .map(|arg| String::from_utf8_lossy(arg).to_string())
+                .collect()
+        })
+        .collect();
+
+    // ps joins the arguments with spaces, so split before each option instead
+    #[cfg(target_os = "macos")]
+    let command_lines: Vec<Vec<String>> = {
+        let output = std::process::Command::new("ps").args(["-axww", "-o", "command="]).output().ok()?;
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(|line| {
+                let mut args: Vec<String> = line.split(" --").map(|arg| arg.trim().to_string()).collect();
+                args.iter_mut().skip(1).for_each(|arg| arg.insert_str(0, "--"));
+                args
+            })
+            .collect()
+    };
+
+    #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+    let command_lines: Vec<Vec<String>> = Vec::new();
+
+    command_lines.iter()
+        .filter(|args| args.first().is_some_and(|program| is_vscode_program(program)))
+        .filter_map(|args| user_data_dir_arg(args))
+        .find(|dir| std::path::Path::new(dir).is_dir())
+}
+
+/// Executable names of VSCode and its forks, lowercase and without `.exe`
+const VSCODE_PROGRAMS: &[&str] = &[
+    "code", "code-insiders", "code-oss", "codium", "codium-insiders", "vscodium", "cursor", "windsurf",
+];
+
+/// Check whether a program path is a VSCode executable (code, code-insiders, codium, ...)
+fn is_vscode_program(program: &str) -> bool {
+    let name = program.rsplit(['/', '\\']).next().unwrap_or(program).to_lowercase();
+    let name = name.strip_suffix(".exe").unwrap_or(&name);
+    // The macOS app bundles run a generic Electron executable
+    VSCODE_PROGRAMS.contains(&name) || program.contains("Visual Studio Code.app") || program.contains("VSCodium.app")
+}
+
+/// Get the value of `--user-data-dir` from a process' arguments
+fn user_data_dir_arg(args: &[String]) -> Option<String> {
+    args.iter().enumerate().find_map(|(i, arg)| {
+        if let Some(value) = arg.strip_prefix("--user-data-dir=") {
+            Some(value.to_string())
+        } else if let Some(value) = arg.strip_prefix("--user-data-dir ") {
+            Some(value.trim().to_string())
+        } else if arg == "--user-data-dir" {
+            args.get(i + 1).cloned()
+        } else {
+            None
+        }
+    })
+}
+
+/// Get the base profile of a named profile directory (`<base>/User/profiles/<id>`),
+/// or `None` if the path is not a named profile directory
+pub fn named_profile_base(path: &str) -> Option<String> {
+    let profiles_dir = std::path::Path::new(path.trim_end_matches(['/', '\\'])).parent()?;
+    let user_dir = profiles_dir.parent()?;
+    if profiles_dir.file_name()? != "profiles" || user_dir.file_name()? != "User" {
+        return None;
+    }
+    Some(user_dir.parent()?.to_string_lossy().to_string())
+}
+
+/// Get the directory for this tool's own data files (e.g. ~/.local/share/vscode-workspaces-editor)
+pub fn app_data_dir() -> Result<std::path::PathBuf> {
+    let base_dirs = BaseDirs::new().ok_or(WorkspaceError::HomeDir)?;
+    Ok(base_dirs.data_dir().join("vscode-workspaces-editor"))
+}
+
+/// Get the path of a file in this tool's data directory (e.g. `app_data_path("notes.json")`
+/// is ~/.local/share/vscode-workspaces-editor/notes.json)
+pub fn app_data_path(name: &str) -> Result<std::path::PathBuf> {
+    Ok(app_data_dir()?.join(name))
+}
+
+/// Create this tool's data directory if needed and return the path of a file in it
+pub fn create_app_data_path(name: &str) -> Result<std::path::PathBuf> {
+    let dir = app_data_dir()?;
+    std::fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create data directory: {}", dir.display()))?;
+    Ok(dir.join(name))
+}
+
+/// Replace a file in this tool's data directory, creating the directory if needed
+pub fn write_app_data_file(name: &str, contents: &str) -> Result<()> {
+    write_file_atomic(&create_app_data_path(name)?, contents)
+}
+
+/// Replace a file through a temporary file next to it, so a failed write can't leave
+/// a truncated file behind
+pub fn write_file_atomic(path: &std::path::Path, contents: &str) -> Result<()> {
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(".tmp");
+    let tmp_path = std::path::PathBuf::from(tmp_path);
+
+    std::fs::write(&tmp_path, contents)
+        .with_context(|| format!("Failed to write {}", tmp_path.display()))?;
+    std::fs::rename(&tmp_path, path)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Get the storage directory of a workspace (`<profile_path>/User/workspaceStorage/<workspace_id>`)
+pub fn workspace_id_to_storage_path(profile_path: &str, workspace_id: &str) -> std::path::PathBuf {
+    std::path::Path::new(profile_path)
+        .join("User")
+        .join("workspaceStorage")
+        .join(workspace_id)
+}
+
+/// Get the named profiles of a VSCode installation
+///
+/// Profiles are found in `<base>/User/profiles/<id>`; their names are read from
+/// `userDataProfiles` in `User/globalStorage/storage.json`, falling back to the ID.
+pub fn get_named_profiles(base_profile_path: &str) -> Result<Vec<NamedProfile>> {
+    let base_profile_path = expand_tilde(base_profile_path)?;
+    let user_dir = std::path::Path::new(&base_profile_path).join("User");
+    let profiles_dir = user_dir.join("profiles");
+    if !profiles_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    // Map profile IDs to the names shown in VSCode
+    let mut names = std::collections::HashMap::new();
+    if let Ok(content) = std::fs::read_to_string(user_dir.join("globalStorage/storage.json")) {
+        match serde_json::from_str::<serde_json::Value>(&content) {
+            Ok(storage) => {
+                let profiles = storage.get("userDataProfiles").and_then(|p| p.as_array());
+                for profile in profiles.into_iter().flatten() {
+                    if let (Some(location), Some(name)) = (
+                        profile.get("location").and_then(|l| l.as_str()),
+                        profile.get("name").and_then(|n| n.as_str()),
+                    ) {
+                        names.insert(location.to_string(), name.to_string());
+                    }
+                }
+            },
+            Err(e) => debug!("Failed to parse storage.json of {}: {}", base_profile_path, e),
+        }
+    }
+
+    let mut profiles = Vec::new();
+    for entry in std::fs::read_dir(&profiles_dir)?.flatten() {
+        if !entry.path().is_dir() {
+            continue;
+        }
+
+        let id = entry.file_name().to_string_lossy().to_string();
+        profiles.push(NamedProfile {
+            name: names.get(&id).cloned().unwrap_or_else(|| id.clone()),
+            path: entry.path().to_string_lossy().to_string(),
+            id,
+        });
+    }
+
+    profiles.sort_by_key(|profile| profile.name.to_lowercase());
+    debug!("Found {} named profiles in {}", profiles.len(), base_profile_path);
+    Ok(profiles)
+}
+
 /// Expand ~ in path to home directory
 pub fn expand_tilde(path: &str) -> Result<String> {
     if let Some(stripped) = path.strip_prefix("~") {
@@ -48,43 +259,189 @@ pub fn expand_tilde(path: &str) -> Result<String> {
 }
 
 /// Normalize a path or URI to a consistent format
+///
+/// Local paths and `file://` URIs are decoded and converted to the local separator, with
+/// Windows drive letters lowercased, so `file:///C%3A/Users/alice/project` and
+/// `C:\Users\alice\project` normalize to the same value. Other URIs (e.g. `vscode-remote://`)
+/// are kept as-is apart from decoding and trailing slash removal.
 pub fn normalize_path(uri_or_path: &str) -> String {
     debug!("Normalizing path: {}", uri_or_path);
     
-    // First decode any URL encoding
+    // First decode any URL encoding (e.g. %3A -> :)
     let decoded = match urlencoding::decode(uri_or_path) {
         Ok(decoded) => decoded.into_owned(),
         Err(_) => uri_or_path.to_string(),
     };
     
-    // Handle file:// and vscode-remote:// prefixes
-    let path = if decoded.starts_with("vscode-remote://") {
-        // Keep remote paths as-is to maintain uniqueness
-        decoded
-    } else if decoded.starts_with("file://") {
-        // Remove file:// prefix and normalize
-        decoded.replace("file://", "")
-    } else {
-        decoded
+    // Keep remote and other non-file URIs as-is to maintain uniqueness
+    if decoded.contains("://") && !decoded.starts_with("file://") {
+        let normalized = decoded.trim_end_matches('/').trim_end_matches('\\').replace('\\', "/");
+        debug!("Normalized result: {}", normalized);
+        return normalized;
+    }
+    
+    // Remove the file:// prefix; a host in the URI (file://server/share) denotes a UNC path
+    let mut path = match decoded.strip_prefix("file://") {
+        Some(rest) if !rest.is_empty() && !rest.starts_with('/') => format!("//{}", rest),
+        Some(rest) => rest.to_string(),
+        None => decoded.clone(),
     };
     
+    // file:///C:/... leaves a slash before the drive letter
+    if path.strip_prefix('/').is_some_and(has_drive_letter) {
+        path.remove(0);
+    }
+    
+    // Lowercase the drive letter
+    if has_drive_letter(&path) {
+        path.replace_range(..1, &path[..1].to_ascii_lowercase());
+    }
+    
     // Remove any trailing slashes
     let clean_path = path.trim_end_matches('/').trim_end_matches('\\');
     
-    // Normalize path separators to forward slashes
-    let normalized = clean_path.replace('\\', "/");
+    // Normalize path separators to the local separator
+    let normalized = clean_path
+        .replace(['/', '\\'], std::path::MAIN_SEPARATOR_STR);
     
     debug!("Normalized result: {}", normalized);
     normalized
 }
 
-/// Check if we're running inside WSL
-fn is_wsl() -> bool {
-    if let Ok(release) = std::fs::read_to_string("/proc/version") {
-        return release.to_lowercase().contains("microsoft")
-            || release.to_lowercase().contains("wsl");
+/// Get the local path of a `file://` URI, decoding its percent-encoding.
+///
+/// `file:///home/alice/project` gives `/home/alice/project` and `file:///c%3A/Users/alice`
+/// gives `c:/Users/alice`. A host other than `localhost` is the server of a Windows network
+/// share, so `file://server/share` gives `//server/share`. Plain paths are passed through;
+/// other URIs (e.g. `vscode-remote://`) fail with `WorkspaceError::InvalidPath`.
+pub fn path_from_uri(uri: &str) -> Result<String> {
+    let Some(rest) = uri.strip_prefix("file://") else {
+        if uri.contains("://") {
+            return Err(WorkspaceError::InvalidPath(format!("{} is not a file URI", uri)).into());
+        }
+        return Ok(uri.to_string());
+    };
+    
+    let rest = match rest.strip_prefix("localhost") {
+        Some(path) if path.starts_with('/') => path,
+        _ => rest,
+    };
+    let mut path = urlencoding::decode(rest)
+        .map_err(|e| WorkspaceError::InvalidPath(format!("{}: {}", uri, e)))?
+        .into_owned();
+    
+    if !rest.is_empty() && !rest.starts_with('/') {
+        path.insert_str(0, "//");
+    }
+    
+    // file:///C:/... leaves a slash before the drive letter
+    if path.strip_prefix('/').is_some_and(has_drive_letter) {
+        path.remove(0);
+    }
+    
+    Ok(path)
+}
+
+/// Check if the path starts with a Windows drive letter (e.g. `C:`)
+fn has_drive_letter(path: &str) -> bool {
+    let bytes = path.as_bytes();
+    bytes.len() >= 2
+        && bytes[0].is_ascii_alphabetic()
+        && bytes[1] == b':'
+        && (bytes.len() == 2 || bytes[2] == b'/' || bytes[2] == b'\\')
+}
+
+/// Generate the equivalent forms of a path used as lookup keys when matching workspaces.
+/// The first entry is always the normalized path.
+///
+/// Variations are purely lexical since they are generated for every path while loading;
+/// symlinks are resolved in the background existence check instead (see `resolve_symlinks`).
+pub fn generate_path_variations(uri_or_path: &str) -> Vec<String> {
+    let normalized = normalize_path(uri_or_path);
+    let mut variations = vec![normalized.clone()];
+    
+    // Windows network shares: \\server\share, //server/share and file:////server/share
+    if is_unc_path(uri_or_path) {
+        let share = normalized.trim_start_matches(['/', '\\']).replace('\\', "/");
+        variations.push(format!("\\\\{}", share.replace('/', "\\")));
+        variations.push(format!("//{}", share));
+        variations.push(format!("file:////{}", share));
+    }
+    
+    // macOS filesystems are case-insensitive by default
+    #[cfg(target_os = "macos")]
+    {
+        if !normalized.contains("://") {
+            variations.push(normalized.to_lowercase());
+        }
+    }
+    
+    let mut seen = std::collections::HashSet::new();
+    variations.retain(|variation| seen.insert(variation.clone()));
+    variations
+}
+
+/// Resolve the symlinks in a local path or `file://` URI, returning the normalized real path.
+/// Fails if the path (or the target of a symlink in it) does not exist.
+pub fn resolve_symlinks(path: &str) -> Result<String> {
+    let local = normalize_path(path);
+    let resolved = std::fs::canonicalize(&local)
+        .with_context(|| format!("Failed to resolve path: {}", local))?;
+    
+    // Windows returns verbatim paths (\\?\C:\...)
+    let resolved = resolved.to_string_lossy();
+    let resolved = resolved.strip_prefix(r"\\?\").unwrap_or(&resolved);
+    Ok(normalize_path(resolved))
+}
+
+/// Check if the path is a Windows UNC path (`\\server\share`, `//server/share` or a `file://` URI to a share)
+fn is_unc_path(uri_or_path: &str) -> bool {
+    let path = match uri_or_path.strip_prefix("file://") {
+        // file://server/share carries the server as the URI host
+        Some(rest) if !rest.is_empty() && !rest.starts_with('/') => return true,
+        Some(rest) => rest,
+        None => uri_or_path,
+    };
+    path.starts_with("\\\\") || (path.starts_with("//") && !path.starts_with("///"))
+}
+
+/// Check if we're running inside WSL (checked once per process)
+pub fn is_wsl() -> bool {
+    static IS_WSL: std::sync::OnceLock<bool> = std::sync::OnceLock::new();
+    
+    *IS_WSL.get_or_init(|| {
+        if let Ok(release) = std::fs::read_to_string("/proc/version") {
+            return release.to_lowercase().contains("microsoft")
+                || release.to_lowercase().contains("wsl");
+        }
+        false
+    })
+}
+
+/// Convert a Windows path to the path seen from WSL: drive paths (`C:\Users\alice`) become
+/// their mount (`/mnt/c/Users/alice`) and paths inside a distribution (`\\wsl.localhost\Ubuntu\home\alice`
+/// or `\\wsl$\...`) become the Linux path (`/home/alice`). Returns `None` for other paths.
+pub fn convert_windows_path_to_wsl(windows_path: &str) -> Option<String> {
+    let path = windows_path.replace('\\', "/");
+    
+    if has_drive_letter(&path) {
+        let drive = path[..1].to_ascii_lowercase();
+        let rest = path[2..].trim_matches('/');
+        return Some(if rest.is_empty() {
+            format!("/mnt/{}", drive)
+        } else {
+            format!("/mnt/{}/{}", drive, rest)
+        });
     }
-    false
+    
+    let lower = path.to_lowercase();
+    ["//wsl.localhost/", "//wsl$/"].iter()
+        .find(|prefix| lower.starts_with(*prefix))
+        .map(|prefix| {
+            // Skip the distribution name
+            let rest = &path[prefix.len()..];
+            rest.find('/').map(|i| rest[i..].to_string()).unwrap_or_else(|| "/".to_string())
+        })
 }
 
 /// Get all possible known VSCode configuration paths for the current system
@@ -173,3 +530,167 @@ pub fn get_known_vscode_paths() -> Vec<String> {
     debug!("Found {} known VSCode paths", paths.len());
     paths
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_path_matches_uri_and_windows_path() {
+        let pairs = [
+            ("file:///C:/Users/alice/project", "C:\\Users\\alice\\project"),
+            ("file:///c%3A/Users/alice/project", "C:\\Users\\alice\\project\\"),
+            ("file:///D%3A/work/app/", "d:/work/app"),
+            ("file:///home/alice/project", "/home/alice/project/"),
+        ];
+
+        for (uri, path) in pairs {
+            assert_eq!(normalize_path(uri), normalize_path(path), "{} vs {}", uri, path);
+        }
+    }
+
+    #[test]
+    fn test_normalize_path_keeps_remote_uris() {
+        assert_eq!(
+            normalize_path("vscode-remote://ssh-remote%2Bhost/home/alice/project/"),
+            "vscode-remote://ssh-remote+host/home/alice/project"
+        );
+    }
+
+    #[test]
+    fn test_generate_path_variations_starts_with_normalized() {
+        let variations = generate_path_variations("file:///Users/Alice/Project/");
+        assert_eq!(variations[0], normalize_path("/Users/Alice/Project"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_resolve_symlinks() {
+        let dir = std::env::temp_dir().join(format!("vscode-workspaces-editor-symlink-{}", std::process::id()));
+        let target = dir.join("target");
+        let link = dir.join("link");
+        std::fs::create_dir_all(&target).unwrap();
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        let link_path = link.to_string_lossy().to_string();
+        let target_path = resolve_symlinks(&target.to_string_lossy()).unwrap();
+        let resolved = resolve_symlinks(&format!("file://{}", link_path)).unwrap();
+        // Matching while loading must not touch the filesystem
+        let variations = generate_path_variations(&format!("file://{}", link_path));
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(resolved, target_path);
+        assert_eq!(variations, [link_path]);
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    #[test]
+    fn test_workspace_id_to_storage_path() {
+        assert_eq!(
+            workspace_id_to_storage_path("/home/alice/.config/Code", "1234abcd"),
+            std::path::PathBuf::from("/home/alice/.config/Code/User/workspaceStorage/1234abcd")
+        );
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    #[test]
+    fn test_named_profile_base() {
+        assert_eq!(named_profile_base("/home/alice/.config/Code/User/profiles/-5a2b").as_deref(), Some("/home/alice/.config/Code"));
+        assert_eq!(named_profile_base("/home/alice/.config/Code/User/profiles/-5a2b/").as_deref(), Some("/home/alice/.config/Code"));
+        assert_eq!(named_profile_base("/home/alice/.config/Code"), None);
+        assert_eq!(named_profile_base("/home/alice/profiles/work"), None);
+    }
+
+    #[test]
+    fn test_convert_windows_path_to_wsl() {
+        assert_eq!(convert_windows_path_to_wsl("C:\\Users\\Alice\\project").as_deref(), Some("/mnt/c/Users/Alice/project"));
+        assert_eq!(convert_windows_path_to_wsl("d:/").as_deref(), Some("/mnt/d"));
+        assert_eq!(convert_windows_path_to_wsl("\\\\wsl.localhost\\Ubuntu\\home\\alice").as_deref(), Some("/home/alice"));
+        assert_eq!(convert_windows_path_to_wsl("//wsl$/Debian/srv").as_deref(), Some("/srv"));
+        assert_eq!(convert_windows_path_to_wsl("/home/alice"), None);
+    }
+
+    #[test]
+    fn test_generate_path_variations_unc() {
+        let expected = [
+            "\\\\server\\share\\project",
+            "//server/share/project",
+            "file:////server/share/project",
+        ];
+
+        for path in expected {
+            let variations = generate_path_variations(path);
+            for form in expected {
+                assert!(variations.contains(&form.to_string()), "{} missing from {:?}", form, variations);
+            }
+        }
+
+        assert!(is_unc_path("file://server/share/project"));
+        assert!(!is_unc_path("file:///home/alice/project"));
+        assert!(!is_unc_path("/home/alice/project"));
+    }
+
+    #[test]
+    fn test_user_data_dir_arg() {
+        let args = |line: &[&str]| line.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(user_data_dir_arg(&args(&["/usr/share/code/code", "--user-data-dir=/tmp/vsc"])), Some("/tmp/vsc".to_string()));
+        assert_eq!(user_data_dir_arg(&args(&["code", "--user-data-dir", "/tmp/vsc", "."])), Some("/tmp/vsc".to_string()));
+        assert_eq!(user_data_dir_arg(&args(&["code", "--user-data-dir /tmp/my vsc"])), Some("/tmp/my vsc".to_string()));
+        assert_eq!(user_data_dir_arg(&args(&["code", "--type=renderer"])), None);
+
+        assert!(is_vscode_program("/usr/share/code/code"));
+        assert!(is_vscode_program("/Applications/Visual Studio Code.app/Contents/MacOS/Electron"));
+        assert!(!is_vscode_program("/usr/bin/vscode-workspaces-editor"));
+        assert!(is_vscode_program("C:\\Program Files\\Microsoft VS Code\\Code.exe"));
+        assert!(is_vscode_program("/usr/bin/code-insiders"));
+        assert!(is_vscode_program("/opt/cursor/cursor"));
+        assert!(!is_vscode_program("/usr/local/bin/codex"));
+        assert!(!is_vscode_program("/usr/bin/codeql"));
+    }
+
+    #[cfg(target_os = "macos")]
+    #[test]
+    fn test_generate_path_variations_macos_case_insensitive() {
+        let variations = generate_path_variations("/Users/Alice/Project");
+        assert!(variations.contains(&"/Users/Alice/Project".to_string()));
+        assert!(variations.contains(&"/users/alice/project".to_string()));
+
+        // Both spellings share a lookup key
+        let other = generate_path_variations("file:///users/alice/project");
+        assert!(variations.iter().any(|v| other.contains(v)));
+    }
+
+    #[test]
+    fn test_path_from_uri() {
+        let cases = [
+            ("file:///home/alice/project", "/home/alice/project"),
+            ("file://localhost/home/alice/project", "/home/alice/project"),
+            ("file:///home/alice/my%20project", "/home/alice/my project"),
+            ("file:///home/alice/caf%C3%A9/", "/home/alice/café/"),
+            ("file:///C:/Users/alice/project", "C:/Users/alice/project"),
+            ("file:///c%3A/Users/alice/project", "c:/Users/alice/project"),
+            ("file:///d%3A", "d:"),
+            ("file://server/share/project", "//server/share/project"),
+            ("file:////server/share/project", "//server/share/project"),
+            ("/home/alice/my%20project", "/home/alice/my%20project"),
+            ("C:\\Users\\alice\\project", "C:\\Users\\alice\\project"),
+            ("\\\\server\\share", "\\\\server\\share"),
+        ];
+        for (uri, expected) in cases {
+            assert_eq!(path_from_uri(uri).unwrap(), expected, "{}", uri);
+        }
+        
+        assert!(path_from_uri("vscode-remote://ssh-remote+devbox/home/alice").is_err());
+        assert!(path_from_uri("file:///home/alice/%FF").is_err());
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_normalize_path_windows() {
+        assert_eq!(normalize_path("file:///C:/Users/alice/project"), "c:\\Users\\alice\\project");
+        assert_eq!(normalize_path("file:///c%3A/Users/alice/project"), "c:\\Users\\alice\\project");
+        assert_eq!(normalize_path("C:\\Users\\alice\\project"), "c:\\Users\\alice\\project");
+        assert_eq!(normalize_path("C:/Users/alice/project/"), "c:\\Users\\alice\\project");
+    }
+}
@@ -0,0 +1,197 @@
+use home::home_dir;
+use log::{debug, warn};
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Maximum nesting of `Include` directives, as in OpenSSH
+const MAX_INCLUDE_DEPTH: usize = 16;
+
+/// Connection settings of a host alias from `~/.ssh/config`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SshHostConfig {
+    /// `HostName` the alias connects to
+    pub host_name: Option<String>,
+    /// `User` to log in as
+    pub user: Option<String>,
+    /// `Port` to connect to
+    pub port: Option<u16>,
+}
+
+impl SshHostConfig {
+    /// Check whether the configuration sets anything for the host
+    pub fn is_empty(&self) -> bool {
+        self.host_name.is_none() && self.user.is_none() && self.port.is_none()
+    }
+}
+
+/// Look up a host alias in the user's `~/.ssh/config`.
+///
+/// The config (with its `Include`s) is read once per process. Returns `None` if there
+/// is no config or it sets nothing for the alias.
+pub fn resolve_ssh_alias(alias: &str) -> Option<SshHostConfig> {
+    static CONFIG: OnceLock<String> = OnceLock::new();
+
+    let config = CONFIG.get_or_init(|| {
+        let Some(ssh_dir) = home_dir().map(|home| home.join(".ssh")) else {
+            return String::new();
+        };
+        let config_path = ssh_dir.join("config");
+        if !config_path.exists() {
+            debug!("No SSH config found at {}", config_path.display());
+            return String::new();
+        }
+        read_config(&config_path, &ssh_dir, 0)
+    });
+
+    let resolved = lookup_ssh_host(config, alias);
+    (!resolved.is_empty()).then_some(resolved)
+}
+
+/// Read an SSH config file, inlining the files of its `Include` directives
+fn read_config(path: &Path, ssh_dir: &Path, depth: usize) -> String {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) => {
+            warn!("Failed to read SSH config {}: {}", path.display(), e);
+            return String::new();
+        }
+    };
+
+    let mut expanded = String::with_capacity(content.len());
+    for line in content.lines() {
+        match parse_line(line) {
+            Some((keyword, args)) if keyword.eq_ignore_ascii_case("include") && depth < MAX_INCLUDE_DEPTH => {
+                for pattern in args {
+                    // Relative includes are relative to ~/.ssh
+                    let pattern = match pattern.strip_prefix("~/") {
+                        Some(rest) => ssh_dir.parent().unwrap_or(ssh_dir).join(rest),
+                        None => ssh_dir.join(&pattern),
+                    };
+                    let Ok(paths) = glob::glob(&pattern.to_string_lossy()) else {
+                        continue;
+                    };
+                    for included in paths.flatten() {
+                        expanded.push_str(&read_config(&included, ssh_dir, depth + 1));
+                        expanded.push('\n');
+                    }
+                }
+            }
+            _ => {
+                expanded.push_str(line);
+                expanded.push('\n');
+            }
+        }
+    }
+    expanded
+}
+
+/// Get the `HostName`, `User` and `Port` that an SSH config sets for a host alias.
+///
+/// Like OpenSSH, the first value found for each setting wins, and `Host` patterns may
+/// use `*`, `?` and `!` negation. `Match` blocks are not evaluated and never apply.
+pub fn lookup_ssh_host(config: &str, alias: &str) -> SshHostConfig {
+    let mut resolved = SshHostConfig::default();
+    // Settings before the first Host line apply to every host
+    let mut applies = true;
+
+    for line in config.lines() {
+        let Some((keyword, args)) = parse_line(line) else {
+            continue;
+        };
+        let Some(value) = args.first() else {
+            continue;
+        };
+
+        match keyword.to_lowercase().as_str() {
+            "host" => applies = host_matches(&args, alias),
+            "match" => applies = false,
+            "hostname" if applies && resolved.host_name.is_none() => {
+                resolved.host_name = Some(value.replace("%h", alias).replace("%%", "%"));
+            }
+            "user" if applies && resolved.user.is_none() => {
+                resolved.user = Some(value.clone());
+            }
+            "port" if applies && resolved.port.is_none() => {
+                resolved.port = value.parse().ok();
+            }
+            _ => {}
+        }
+    }
+
+    resolved
+}
+
+/// Split a config line into its keyword and arguments, skipping comments and blank lines
+fn parse_line(line: &str) -> Option<(String, Vec<String>)> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+
+    // Keywords are separated from their arguments by whitespace or `=`
+    let split = line.find(|c: char| c.is_whitespace() || c == '=')?;
+    let keyword = line[..split].to_string();
+    let rest = line[split..].trim_start_matches(|c: char| c.is_whitespace() || c == '=');
+
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    for c in rest.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            c if c.is_whitespace() && !quoted => {
+                if !current.is_empty() {
+                    args.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        args.push(current);
+    }
+
+    Some((keyword, args))
+}
+
+/// Check whether a host matches the patterns of a `Host` line
+fn host_matches(patterns: &[String], host: &str) -> bool {
+    let mut matched = false;
+    for pattern in patterns {
+        if let Some(negated) = pattern.strip_prefix('!') {
+            if wildcard_match(negated, host) {
+                return false;
+            }
+        } else if wildcard_match(pattern, host) {
+            matched = true;
+        }
+    }
+    matched
+}
+
+/// Match a host against a pattern with `*` (any characters) and `?` (one character)
+fn wildcard_match(pattern: &str, host: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let host: Vec<char> = host.to_lowercase().chars().collect();
+
+    let (mut p, mut h) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while h < host.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == host[h]) {
+            p += 1;
+            h += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, h));
+            p += 1;
+        } else if let Some((star_p, star_h)) = star {
+            // Let the last `*` consume one more character
+            p = star_p + 1;
+            h = star_h + 1;
+            star = Some((star_p, star_h + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
@@ -2,92 +2,68 @@ use anyhow::{Context, Result};
 use glob::glob;
 use log::{debug, warn};
 use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 
+use crate::workspaces::error::WorkspaceError;
 use crate::workspaces::models::{Workspace, WorkspaceSource};
-use crate::workspaces::paths::expand_tilde;
+use crate::workspaces::paths::{expand_tilde, workspace_id_to_storage_path, write_file_atomic};
 
-/// Get workspaces from workspace storage files
-pub fn get_workspaces_from_storage(profile_path: &str) -> Result<Vec<Workspace>> {
+/// Additional workspace storage directories scanned for every profile
+static EXTRA_STORAGE_DIRS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Set additional workspace storage directories to scan (e.g. from `--extra-storage`)
+pub fn set_extra_storage_dirs(dirs: Vec<String>) {
+    if let Ok(mut extra) = EXTRA_STORAGE_DIRS.lock() {
+        *extra = dirs;
+    }
+}
+
+/// Get the additional workspace storage directories set with `set_extra_storage_dirs`
+pub fn extra_storage_dirs() -> Vec<String> {
+    EXTRA_STORAGE_DIRS.lock().map(|dirs| dirs.clone()).unwrap_or_default()
+}
+
+/// Get workspaces from workspace storage files of the profile and any extra storage directories
+///
+/// Workspaces from extra directories are tagged with the full path of their workspace.json,
+/// profile workspaces with the path relative to `User/`.
+pub fn get_workspaces_from_storage(profile_path: &str, extra_storage_dirs: &[String]) -> Result<Vec<Workspace>> {
     let profile_path = expand_tilde(profile_path)?;
     let storage_path = format!("{}/User/workspaceStorage/*/workspace.json", profile_path);
 
     let mut workspaces = Vec::new();
+    scan_storage_dir(&storage_path, true, &mut workspaces)?;
 
-    for entry in glob(&storage_path).context("Failed to read glob pattern")? {
-        match entry {
-            Ok(path) => {
-                debug!("Reading workspace file: {:?}", path);
-
-                // Get file metadata for fallback timestamp
-                let metadata = match fs::metadata(path.parent().unwrap()) {
-                    Ok(meta) => Some(meta),
-                    Err(e) => {
-                        warn!(
-                            "Failed to read metadata for workspace file: {:?} - {}",
-                            path, e
-                        );
-                        None
-                    }
-                };
-
-                // Get the folder modification time as a fallback for last_used
-                let mut file_mtime = metadata
-                    .and_then(|meta| meta.modified().ok())
-                    .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
-                    .map(|duration| duration.as_secs() as i64 * 1000) // Convert to milliseconds
-                    .unwrap_or(0);
-
-                // if there is state.vscdb in the parent directory, update the last_used to max of the two
-                let state_vscdb_path = path.parent().unwrap().join("state.vscdb");
-                if let Ok(meta) = fs::metadata(&state_vscdb_path) {
-                    let state_vscdb_mtime = meta
-                        .modified()
-                        .ok()
-                        .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
-                        .map(|duration| duration.as_secs() as i64 * 1000) // Convert to milliseconds
-                        .unwrap_or(0);
-                    file_mtime = file_mtime.max(state_vscdb_mtime);
-                }
+    for dir in extra_storage_dirs {
+        let dir = expand_tilde(dir)?;
+        if !Path::new(&dir).is_dir() {
+            warn!("Extra storage directory does not exist: {}", dir);
+            continue;
+        }
+
+        workspaces.extend(get_workspaces_from_storage_dir(&dir)?);
+    }
 
-                let content = fs::read_to_string(&path)
-                    .with_context(|| format!("Failed to read workspace file: {:?}", path))?;
-
-                // Get the ID from the parent directory name
-                let id = path
-                    .parent()
-                    .and_then(|p| p.file_name())
-                    .and_then(|n| n.to_str())
-                    .unwrap_or("unknown")
-                    .to_string();
-
-                // Parse the workspace file
-                let workspace_json: serde_json::Value = serde_json::from_str(&content)
-                    .with_context(|| format!("Failed to parse workspace file: {:?}", path))?;
-
-                if let Some(folder_uri) = workspace_json["folder"].as_str() {
-                    // Remove the file:// prefix
-                    let folder_path = folder_uri.replace("file://", "");
-
-                    // Get the storage path relative to the workspace storage directory
-                    let relative_storage_path = path.to_string_lossy().to_string();
-                    let storage_path_parts: Vec<&str> =
-                        relative_storage_path.split("workspaceStorage").collect();
-                    let relative_path = if storage_path_parts.len() > 1 {
-                        format!("workspaceStorage{}", storage_path_parts[1])
-                    } else {
-                        relative_storage_path
-                    };
-
-                    let workspace = Workspace {
-                        id,
-                        name: None, // Will be filled from state.vscdb
-                        path: folder_path,
-                        last_used: file_mtime, // Use file modification time as fallback
-                        storage_path: Some(relative_path.clone()),
-                        sources: vec![WorkspaceSource::Storage(relative_path)],
-                        parsed_info: None,
-                    };
+    Ok(workspaces)
+}
+
+/// Get workspaces from a workspace storage directory outside the profile's `User/` directory,
+/// tagged with the full path of their workspace.json
+pub fn get_workspaces_from_storage_dir(dir: &str) -> Result<Vec<Workspace>> {
+    let pattern = format!("{}/*/workspace.json", dir.trim_end_matches(['/', '\\']));
+
+    let mut workspaces = Vec::new();
+    scan_storage_dir(&pattern, false, &mut workspaces)?;
+    Ok(workspaces)
+}
 
+/// Read all workspaces matching a `*/workspace.json` glob pattern
+fn scan_storage_dir(pattern: &str, in_profile: bool, workspaces: &mut Vec<Workspace>) -> Result<()> {
+    for entry in glob(pattern).context("Failed to read glob pattern")? {
+        match entry {
+            Ok(path) => {
+                if let Some(workspace) = read_storage_workspace(&path, in_profile)? {
                     workspaces.push(workspace);
                 }
             }
@@ -95,5 +71,222 @@ pub fn get_workspaces_from_storage(profile_path: &str) -> Result<Vec<Workspace>>
         }
     }
 
-    Ok(workspaces)
+    Ok(())
+}
+
+/// Get a single workspace from its storage directory, reading only its workspace.json
+pub fn get_workspace_from_storage(profile_path: &str, workspace_id: &str) -> Result<Option<Workspace>> {
+    let profile_path = expand_tilde(profile_path)?;
+    let path = workspace_id_to_storage_path(&profile_path, workspace_id).join("workspace.json");
+
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    read_storage_workspace(&path, true)
+}
+
+/// Get the full path of a workspace storage directory from the path of a
+/// `WorkspaceSource::Storage` (relative to the profile's `User/` directory or absolute)
+pub fn build_storage_dir_path(profile_path: &str, storage_path: &str) -> Option<String> {
+    // Workspaces from extra storage directories carry the full path to their workspace.json
+    let path = Path::new(storage_path);
+    if path.is_absolute() {
+        return path.parent().map(|dir| dir.to_string_lossy().to_string());
+    }
+
+    // Extract the workspace ID from the storage path
+    // Expected format: workspaceStorage/WORKSPACE_ID/workspace.json
+    let workspace_id = path.strip_prefix("workspaceStorage").ok()?.iter().next()?;
+    let storage_dir = workspace_id_to_storage_path(profile_path, &workspace_id.to_string_lossy());
+    Some(storage_dir.to_string_lossy().to_string())
+}
+
+/// Store a display name in the `"name"` key of the workspace.json in the workspace's storage
+/// directory (in the profile or an extra storage directory), for tools that only read
+/// workspace storage. An empty name removes the key.
+pub fn update_workspace_name_in_storage(profile_path: &str, workspace_id: &str, name: &str) -> Result<()> {
+    let profile_path = expand_tilde(profile_path)?;
+    let mut dirs = vec![workspace_id_to_storage_path(&profile_path, workspace_id)];
+    for dir in extra_storage_dirs() {
+        dirs.push(Path::new(&expand_tilde(&dir)?).join(workspace_id));
+    }
+    let paths: Vec<PathBuf> = dirs.into_iter()
+        .map(|dir| dir.join("workspace.json"))
+        .filter(|path| path.exists())
+        .collect();
+
+    if paths.is_empty() {
+        return Err(WorkspaceError::NotFound(workspace_id.to_string()).into());
+    }
+
+    for path in paths {
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read workspace file: {:?}", path))?;
+        let mut workspace_json: serde_json::Value = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse workspace file: {:?}", path))?;
+        let Some(object) = workspace_json.as_object_mut() else {
+            return Err(WorkspaceError::Parse(path.display().to_string()).into());
+        };
+        if name.is_empty() {
+            object.remove("name");
+        } else {
+            object.insert("name".to_string(), serde_json::Value::String(name.to_string()));
+        }
+
+        write_file_atomic(&path, &serde_json::to_string_pretty(&workspace_json)?)?;
+        debug!("Stored name {:?} in {:?}", name, path);
+    }
+
+    Ok(())
+}
+
+/// Read a workspace from a workspace.json file inside a workspace storage directory.
+/// Workspaces outside the profile (`in_profile` unset) keep the full path as storage path.
+fn read_storage_workspace(path: &Path, in_profile: bool) -> Result<Option<Workspace>> {
+    debug!("Reading workspace file: {:?}", path);
+
+    // Get file metadata for fallback timestamp
+    let metadata = match fs::metadata(path.parent().unwrap()) {
+        Ok(meta) => Some(meta),
+        Err(e) => {
+            warn!(
+                "Failed to read metadata for workspace file: {:?} - {}",
+                path, e
+            );
+            None
+        }
+    };
+
+    // Get the folder modification time as a fallback for last_used
+    let mut file_mtime = metadata
+        .and_then(|meta| meta.modified().ok())
+        .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|duration| duration.as_secs() as i64 * 1000) // Convert to milliseconds
+        .unwrap_or(0);
+
+    // if there is state.vscdb in the parent directory, update the last_used to max of the two
+    let state_vscdb_path = path.parent().unwrap().join("state.vscdb");
+    if let Ok(meta) = fs::metadata(&state_vscdb_path) {
+        let state_vscdb_mtime = meta
+            .modified()
+            .ok()
+            .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|duration| duration.as_secs() as i64 * 1000) // Convert to milliseconds
+            .unwrap_or(0);
+        file_mtime = file_mtime.max(state_vscdb_mtime);
+    }
+
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read workspace file: {:?}", path))?;
+
+    // Get the ID from the parent directory name
+    let id = path
+        .parent()
+        .and_then(|p| p.file_name())
+        .and_then(|n| n.to_str())
+        .unwrap_or("unknown")
+        .to_string();
+
+    // Parse the workspace file
+    let workspace_json: serde_json::Value = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse workspace file: {:?}", path))?;
+
+    if let Some(folder_uri) = workspace_json["folder"].as_str() {
+        // Local folders are stored as paths, remote URIs are kept as-is
+        let folder_path = crate::workspaces::paths::path_from_uri(folder_uri)
+            .unwrap_or_else(|_| folder_uri.to_string());
+
+        // Get the storage path relative to the workspace storage directory
+        let relative_storage_path = path.to_string_lossy().to_string();
+        let storage_path_parts: Vec<&str> =
+            relative_storage_path.split("workspaceStorage").collect();
+        let relative_path = if in_profile && storage_path_parts.len() > 1 {
+            format!("workspaceStorage{}", storage_path_parts[1])
+        } else {
+            relative_storage_path
+        };
+
+        let workspace = Workspace {
+            id,
+            // Set by update_workspace_name_in_storage, otherwise filled from state.vscdb
+            name: workspace_json["name"].as_str().map(|name| name.to_string()),
+            path: folder_path,
+            last_used: file_mtime, // Use file modification time as fallback
+            storage_path: Some(relative_path.clone()),
+            sources: vec![WorkspaceSource::Storage(relative_path)],
+            parsed_info: None,
+            pinned: false,
+            exists: false,
+            exists_checked: false,
+            open_count: None,
+            recent_file_count: 0,
+            git_branch: None,
+            color: None,
+            recommended_extensions: Vec::new(),
+        };
+
+        return Ok(Some(workspace));
+    }
+
+    Ok(None)
+}
+
+/// Get the extensions recommended in `.vscode/extensions.json` of a local workspace folder
+/// (plain path or file:// URI). Returns an empty list if the file is missing or invalid.
+pub fn read_workspace_extension_recommendations(path: &str) -> Vec<String> {
+    let folder = crate::workspaces::paths::normalize_path(path);
+    let extensions_path = Path::new(&folder).join(".vscode").join("extensions.json");
+    let Ok(content) = fs::read_to_string(&extensions_path) else {
+        return Vec::new();
+    };
+
+    // extensions.json allows comments and trailing commas like the other VSCode config files
+    let json: serde_json::Value = match serde_json::from_str(&crate::workspaces::utils::strip_jsonc(&content)) {
+        Ok(json) => json,
+        Err(e) => {
+            warn!("Failed to parse {:?}: {}", extensions_path, e);
+            return Vec::new();
+        }
+    };
+
+    json.get("recommendations")
+        .and_then(|recommendations| recommendations.as_array())
+        .map(|recommendations| {
+            recommendations.iter()
+                .filter_map(|id| id.as_str())
+                .map(|id| id.to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_workspace_name_in_storage() {
+        let root = std::env::temp_dir().join(format!("vscode-workspaces-editor-rename-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let storage_dir = root.join("User/workspaceStorage/abc");
+        fs::create_dir_all(&storage_dir).unwrap();
+        let workspace_json = storage_dir.join("workspace.json");
+        fs::write(&workspace_json, r#"{"folder": "file:///home/alice/api"}"#).unwrap();
+        let profile_path = root.to_string_lossy().to_string();
+
+        update_workspace_name_in_storage(&profile_path, "abc", "API").unwrap();
+        let json: serde_json::Value = serde_json::from_str(&fs::read_to_string(&workspace_json).unwrap()).unwrap();
+        assert_eq!(json["name"], "API");
+        assert_eq!(json["folder"], "file:///home/alice/api");
+        let workspace = read_storage_workspace(&workspace_json, true).unwrap().unwrap();
+        assert_eq!(workspace.name.as_deref(), Some("API"));
+
+        update_workspace_name_in_storage(&profile_path, "abc", "").unwrap();
+        let json: serde_json::Value = serde_json::from_str(&fs::read_to_string(&workspace_json).unwrap()).unwrap();
+        assert!(json.get("name").is_none());
+
+        assert!(update_workspace_name_in_storage(&profile_path, "missing", "API").is_err());
+        fs::remove_dir_all(&root).unwrap();
+    }
 }
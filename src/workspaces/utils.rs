@@ -1,9 +1,13 @@
 use log::info;
 use std::path::Path;
-use anyhow::Result;
-use crate::workspaces::models::Workspace;
+use anyhow::{Context, Result};
+use crate::workspaces::models::{Workspace, WorkspaceStats};
 use crate::workspaces::parser::WorkspaceType;
 use log::debug;
+use lru::LruCache;
+use std::num::NonZeroUsize;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
 
 /// Check if a directory exists
 #[allow(dead_code)]
@@ -24,6 +28,11 @@ pub fn directory_exists(path: &str) -> bool {
 
 /// Check if a workspace path exists (handles both local and remote paths)
 pub fn workspace_exists(workspace: &Workspace) -> bool {
+    // Prefer the result of an explicit check (see batch_check_workspace_existence)
+    if workspace.exists_checked {
+        return workspace.exists;
+    }
+    
     // Parse workspace path if not already parsed
     // Using clone to avoid mutable borrow
     let mut workspace_clone = workspace.clone();
@@ -31,27 +40,32 @@ pub fn workspace_exists(workspace: &Workspace) -> bool {
     
     // Check if this is a remote workspace
     let is_remote = if let Some(info) = parsed_info {
-        info.remote_authority.is_some()
+        info.is_remote()
     } else {
         false
     };
     
     if is_remote {
-        // For remote workspaces, we can't check directly
-        // TODO: Implement actual remote path checking in the future
+        // For remote workspaces, we can't check synchronously
+        // (see check_remote_workspace_exists for an SSH based check)
         debug!("Remote workspace existence check not implemented: {}", workspace.path);
         return true; // Assume remote paths exist
     }
     
-    // For local paths, check if the file or directory exists
-    let path = Path::new(&workspace.path);
-    let path_str = path.to_string_lossy();
+    ExistenceCache::shared().exists_cached(&workspace.path)
+}
+
+/// Check if a local workspace path (plain path or file:// URI) exists
+fn local_path_exists(path: &str) -> bool {
+    // Normalizing decodes file:// URIs into local paths, including UNC shares (\\server\share)
+    // which Path handles natively on Windows.
+    let clean_path = crate::workspaces::paths::normalize_path(path);
     
-    // Remove file:// prefix if present
-    let clean_path = if path_str.starts_with("file://") {
-        path_str.replace("file://", "")
+    // Windows paths stored by VSCode on Windows are reachable through /mnt from WSL
+    let clean_path = if crate::workspaces::paths::is_wsl() {
+        crate::workspaces::paths::convert_windows_path_to_wsl(&clean_path).unwrap_or(clean_path)
     } else {
-        path_str.to_string()
+        clean_path
     };
     
     // Check if this is a workspace or a folder/file
@@ -81,6 +95,174 @@ pub fn workspace_exists(workspace: &Workspace) -> bool {
     }
 }
 
+/// Number of paths remembered by an [`ExistenceCache`]
+const EXISTENCE_CACHE_CAPACITY: usize = 1024;
+
+/// How long an [`ExistenceCache`] entry stays valid
+const EXISTENCE_CACHE_TTL: Duration = Duration::from_secs(10);
+
+/// Short-lived cache of local path existence checks, to avoid repeated filesystem stats
+/// while the TUI filters and redraws
+pub struct ExistenceCache {
+    entries: Mutex<LruCache<String, (bool, Instant)>>,
+}
+
+impl Default for ExistenceCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ExistenceCache {
+    /// Create an empty cache
+    pub fn new() -> Self {
+        Self {
+            entries: Mutex::new(LruCache::new(NonZeroUsize::new(EXISTENCE_CACHE_CAPACITY).unwrap())),
+        }
+    }
+    
+    /// Cache used by [`workspace_exists`]
+    pub fn shared() -> &'static ExistenceCache {
+        static SHARED: OnceLock<ExistenceCache> = OnceLock::new();
+        SHARED.get_or_init(ExistenceCache::new)
+    }
+    
+    /// Check if a local path exists, reusing the result of a recent check
+    pub fn exists_cached(&self, path: &str) -> bool {
+        if let Ok(mut entries) = self.entries.lock() {
+            if let Some(&(exists, checked_at)) = entries.get(path) {
+                if checked_at.elapsed() < EXISTENCE_CACHE_TTL {
+                    return exists;
+                }
+            }
+        }
+        
+        self.refresh(path)
+    }
+    
+    /// Check if a local path exists now and remember the result
+    pub fn refresh(&self, path: &str) -> bool {
+        // Stat outside the lock so parallel checks don't wait on each other
+        let exists = local_path_exists(path);
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.put(path.to_string(), (exists, Instant::now()));
+        }
+        exists
+    }
+    
+    /// Forget all cached results
+    pub fn clear(&self) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.clear();
+        }
+    }
+}
+
+/// Check whether an SSH remote workspace exists by running `test -e` on the host.
+/// Returns `None` if the workspace is not an SSH remote, SSH is unavailable or the check times out.
+#[allow(dead_code)]
+pub async fn check_remote_workspace_exists(workspace: &Workspace) -> Option<bool> {
+    let mut workspace = workspace.clone();
+    let info = workspace.parse_path()?.clone();
+    
+    if !info.remote_authority.as_deref()?.starts_with("ssh-remote+") {
+        return None;
+    }
+    
+    let host = info.remote_host.as_deref()?;
+    let destination = match &info.remote_user {
+        Some(user) => format!("{}@{}", user, host),
+        None => host.to_string(),
+    };
+    
+    let mut command = tokio::process::Command::new("ssh");
+    command.args(["-o", "ConnectTimeout=3", "-o", "BatchMode=yes"]);
+    if let Some(port) = info.remote_port {
+        command.arg("-p").arg(port.to_string());
+    }
+    // The remote command is run by a shell, so quote the path
+    command.arg(destination)
+        .arg(format!("test -e '{}'", info.path.replace('\'', "'\\''")))
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .kill_on_drop(true);
+    
+    let status = match tokio::time::timeout(std::time::Duration::from_secs(10), command.status()).await {
+        Ok(Ok(status)) => status,
+        Ok(Err(e)) => {
+            info!("Failed to run ssh: {}", e);
+            return None;
+        }
+        Err(_) => {
+            info!("SSH existence check timed out for {}", host);
+            return None;
+        }
+    };
+    
+    // test exits with 1 when the path is missing; ssh exits with 255 on connection errors
+    match status.code() {
+        Some(0) => Some(true),
+        Some(1) => Some(false),
+        _ => None,
+    }
+}
+
+/// Check the existence of many workspaces, populating `exists` and `exists_checked`.
+///
+/// Local workspaces are checked directly. SSH remote workspaces are checked over SSH, one task
+/// per host running in parallel, and each host gets `timeout` to finish its checks. Workspaces
+/// that could not be checked (other remotes, unreachable hosts) are left unchecked.
+#[allow(dead_code)]
+pub async fn batch_check_workspace_existence(workspaces: &mut [Workspace], timeout: std::time::Duration) -> Result<()> {
+    let mut by_host: std::collections::HashMap<String, Vec<(usize, Workspace)>> = std::collections::HashMap::new();
+    
+    for (index, workspace) in workspaces.iter_mut().enumerate() {
+        workspace.exists_checked = false;
+        
+        let remote_host = workspace.parse_path()
+            .filter(|info| info.is_remote())
+            .map(|info| info.remote_host.clone());
+        
+        match remote_host {
+            None => {
+                // An explicit check bypasses the cache and refreshes it
+                workspace.exists = ExistenceCache::shared().refresh(&workspace.path);
+                workspace.exists_checked = true;
+            }
+            Some(Some(host)) => by_host.entry(host).or_default().push((index, workspace.clone())),
+            Some(None) => debug!("Skipping existence check for remote without host: {}", workspace.path),
+        }
+    }
+    
+    let tasks: Vec<_> = by_host.into_iter().map(|(host, host_workspaces)| {
+        tokio::spawn(async move {
+            let mut results = Vec::new();
+            let checks = async {
+                for (index, workspace) in &host_workspaces {
+                    results.push((*index, check_remote_workspace_exists(workspace).await));
+                }
+            };
+            if tokio::time::timeout(timeout, checks).await.is_err() {
+                info!("Existence checks timed out for host {}", host);
+            }
+            results
+        })
+    }).collect();
+    
+    for task in tasks {
+        let results = task.await.context("Remote existence check task failed")?;
+        for (index, exists) in results {
+            if let Some(exists) = exists {
+                workspaces[index].exists = exists;
+                workspaces[index].exists_checked = true;
+            }
+        }
+    }
+    
+    Ok(())
+}
+
 /// Check if VSCode is installed and available
 #[allow(dead_code)]
 pub fn is_vscode_available() -> bool {
@@ -95,24 +277,120 @@ pub fn is_vscode_available() -> bool {
     }
 }
 
+/// Check if a command (the first word of `command`) can be found in PATH
+#[allow(dead_code)]
+pub fn is_command_available(command: &str) -> bool {
+    let Some(program) = command.split_whitespace().next() else {
+        return false;
+    };
+    
+    // Explicit paths are checked directly
+    if program.contains('/') || program.contains('\\') {
+        return Path::new(program).is_file();
+    }
+    
+    #[cfg(target_os = "windows")]
+    let extensions: Vec<String> = std::env::var("PATHEXT")
+        .unwrap_or_else(|_| ".EXE;.CMD;.BAT;.COM".to_string())
+        .split(';')
+        .map(|ext| ext.to_string())
+        .chain(std::iter::once(String::new()))
+        .collect();
+    
+    #[cfg(not(target_os = "windows"))]
+    let extensions = [String::new()];
+    
+    let Some(path_var) = std::env::var_os("PATH") else {
+        return false;
+    };
+    
+    std::env::split_paths(&path_var).any(|dir| {
+        extensions.iter().any(|ext| dir.join(format!("{}{}", program, ext)).is_file())
+    })
+}
+
 /// Process workspaces to add parsed information
 pub fn process_workspaces(workspaces: &mut [Workspace]) -> Result<()> {
     for workspace in workspaces.iter_mut() {
         // Parse and add workspace path information
         let _ = workspace.parse_path();
+        add_vcs_tags(workspace);
     }
     Ok(())
 }
 
+/// Version control directories and the tag added to folders that contain them
+const VCS_MARKERS: [(&str, &str); 3] = [(".git", "git"), (".svn", "svn"), (".hg", "hg")];
+
+/// Tag local folder workspaces that are Git, Subversion or Mercurial checkouts.
+/// Remote workspaces are skipped, checking them would be too slow.
+fn add_vcs_tags(workspace: &mut Workspace) {
+    if workspace.is_remote() {
+        return;
+    }
+    
+    let folder = crate::workspaces::paths::normalize_path(&workspace.path);
+    let Some(info) = workspace.parsed_info.as_mut() else {
+        return;
+    };
+    if info.workspace_type != WorkspaceType::Folder {
+        return;
+    }
+    
+    for (marker, tag) in VCS_MARKERS {
+        // .git can also be a file, for worktrees and submodules
+        if Path::new(&folder).join(marker).exists() && !info.tags.iter().any(|t| t == tag) {
+            debug!("Found {} in {}", marker, folder);
+            info.tags.push(tag.to_string());
+        }
+    }
+    
+    if is_git_worktree(&folder) && !info.tags.iter().any(|t| t == "git-worktree") {
+        info.tags.push("git-worktree".to_string());
+    }
+}
+
+/// Check whether a local folder (plain path or file:// URI) is a linked Git worktree,
+/// created with `git worktree add`. Its `.git` is a file with a `gitdir:` pointer into
+/// the `worktrees` directory of the main repository; submodules point elsewhere.
+pub fn is_git_worktree(path: &str) -> bool {
+    let folder = crate::workspaces::paths::normalize_path(path);
+    let dot_git = Path::new(&folder).join(".git");
+    if !dot_git.is_file() {
+        return false;
+    }
+    
+    std::fs::read_to_string(&dot_git).ok()
+        .and_then(|content| content.trim().strip_prefix("gitdir:").map(|target| target.trim().to_string()))
+        .is_some_and(|target| Path::new(&target).parent().and_then(|dir| dir.file_name()) == Some("worktrees".as_ref()))
+}
+
+/// Get the checked out branch of a local Git checkout (plain path or file:// URI).
+/// Returns `None` if the folder is not a Git checkout or HEAD is detached.
+pub fn extract_git_branch(workspace_path: &str) -> Option<String> {
+    let folder = crate::workspaces::paths::normalize_path(workspace_path);
+    let dot_git = Path::new(&folder).join(".git");
+    
+    // Worktrees and submodules have a .git file pointing to the actual git directory
+    let git_dir = if dot_git.is_file() {
+        let content = std::fs::read_to_string(&dot_git).ok()?;
+        let target = content.trim().strip_prefix("gitdir:")?.trim();
+        Path::new(&folder).join(target)
+    } else {
+        dot_git
+    };
+    
+    let head = std::fs::read_to_string(git_dir.join("HEAD")).ok()?;
+    head.trim()
+        .strip_prefix("ref: refs/heads/")
+        .map(|branch| branch.to_string())
+}
+
 /// Extract the folder basename from a path
 /// Handles different types of paths including remote and container paths
 pub fn extract_folder_basename(path: &str) -> String {
-    // If it's a file:// URI, remove the prefix
-    let clean_path = if path.starts_with("file://") {
-        path.replace("file://", "")
-    } else {
-        path.to_string()
-    };
+    // If it's a file:// URI, get its local path
+    let clean_path = crate::workspaces::paths::path_from_uri(path).unwrap_or_else(|_| path.to_string());
     
     // For local paths, just extract the basename
     if !path.starts_with("vscode-remote://") {
@@ -159,6 +437,7 @@ pub fn filter_workspaces<'a>(workspaces: &'a mut [Workspace], query: &str) -> Ve
     let mut path_filter: Option<Vec<&str>> = None;
     let mut tag_filter: Option<Vec<&str>> = None;
     let mut existing_filter: Option<bool> = None;
+    let mut source_filter: Option<Vec<&str>> = None;
     let mut text_query = String::new();
     
     for part in query_parts {
@@ -172,7 +451,9 @@ pub fn filter_workspaces<'a>(workspaces: &'a mut [Workspace], query: &str) -> Ve
             tag_filter = Some(stripped.split(',').collect());
         } else if let Some(stripped) = part.strip_prefix(":tags:") {
             tag_filter = Some(stripped.split(',').collect());
-        } else if let Some(stripped) = part.strip_prefix(":existing:") {
+        } else if let Some(stripped) = part.strip_prefix(":source:") {
+            source_filter = Some(stripped.split(',').collect());
+        } else if let Some(stripped) = part.strip_prefix(":existing:").or_else(|| part.strip_prefix(":exists:")) {
             let value = stripped;
             if value == "true" || value == "yes" || value == "1" {
                 existing_filter = Some(true);
@@ -257,6 +538,13 @@ pub fn filter_workspaces<'a>(workspaces: &'a mut [Workspace], query: &str) -> Ve
                 }
             }
             
+            // Check source filter (e.g. :source:profile:work)
+            if let Some(source_values) = &source_filter {
+                if !source_values.iter().any(|&val| ws.sources.iter().any(|source| source.matches_filter(val))) {
+                    return false;
+                }
+            }
+            
             // Check tag filter
             if let Some(tag_values) = &tag_filter {
                 if let Some(info) = &ws.parsed_info {
@@ -280,4 +568,159 @@ pub fn filter_workspaces<'a>(workspaces: &'a mut [Workspace], query: &str) -> Ve
             true
         })
         .collect()
-} 
\ No newline at end of file
+} 
+
+/// Count remote workspaces per remote host; local workspaces are not counted
+pub fn workspace_count_by_host(workspaces: &[Workspace]) -> std::collections::HashMap<String, usize> {
+    let mut counts = std::collections::HashMap::new();
+    for workspace in workspaces {
+        let parsed;
+        let info = match &workspace.parsed_info {
+            Some(info) => info,
+            None => match crate::workspaces::parser::parse_workspace_path(&workspace.path) {
+                Ok(info) => {
+                    parsed = info;
+                    &parsed
+                }
+                Err(_) => continue,
+            },
+        };
+        
+        if let Some(host) = &info.remote_host {
+            *counts.entry(host.clone()).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
+/// Compute aggregate statistics for the given workspaces
+pub fn workspace_statistics(workspaces: &mut [Workspace]) -> WorkspaceStats {
+    let mut stats = WorkspaceStats {
+        total: workspaces.len(),
+        ..Default::default()
+    };
+    let mut hosts = std::collections::BTreeSet::new();
+    
+    for workspace in workspaces.iter_mut() {
+        *stats.by_type.entry(workspace.get_type()).or_insert(0) += 1;
+        
+        if workspace.is_remote() {
+            stats.remote_count += 1;
+            if let Some(host) = workspace.parse_path().and_then(|info| info.remote_host.clone()) {
+                hosts.insert(host);
+            }
+        } else {
+            stats.local_count += 1;
+            if !workspace_exists(workspace) {
+                stats.missing_count += 1;
+            }
+        }
+    }
+    
+    stats.hosts = hosts.into_iter().collect();
+    stats.by_host = workspace_count_by_host(workspaces);
+    stats.most_recent = workspaces.iter()
+        .max_by_key(|ws| ws.last_used)
+        .cloned();
+    stats.oldest_used = workspaces.iter()
+        .filter(|ws| ws.last_used > 0)
+        .min_by_key(|ws| ws.last_used)
+        .cloned();
+    
+    stats
+}
+
+/// Remove comments and trailing commas from JSON with comments (JSONC)
+pub fn strip_jsonc(content: &str) -> String {
+    let mut stripped = String::with_capacity(content.len());
+    let mut chars = content.chars().peekable();
+    let mut in_string = false;
+    // Index of a comma that is dropped if the next token closes an object or array
+    let mut pending_comma: Option<usize> = None;
+
+    while let Some(c) = chars.next() {
+        if in_string {
+            stripped.push(c);
+            match c {
+                '\\' => stripped.extend(chars.next()),
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+
+        match (c, chars.peek()) {
+            ('/', Some('/')) => {
+                while chars.next_if(|&next| next != '\n').is_some() {}
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut previous = ' ';
+                for next in chars.by_ref() {
+                    if previous == '*' && next == '/' {
+                        break;
+                    }
+                    previous = next;
+                }
+            }
+            (c, _) if c.is_whitespace() => stripped.push(c),
+            _ => {
+                if let Some(comma) = pending_comma.take() {
+                    if c == '}' || c == ']' {
+                        stripped.remove(comma);
+                    }
+                }
+                match c {
+                    '"' => in_string = true,
+                    ',' => pending_comma = Some(stripped.len()),
+                    _ => {}
+                }
+                stripped.push(c);
+            }
+        }
+    }
+
+    stripped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_jsonc_comments() {
+        let content = "{\n  // line comment\n  \"a\": 1, /* block\n comment */ \"b\": 2\n}";
+        let json: serde_json::Value = serde_json::from_str(&strip_jsonc(content)).unwrap();
+        assert_eq!(json, serde_json::json!({"a": 1, "b": 2}));
+    }
+
+    #[test]
+    fn test_strip_jsonc_trailing_commas() {
+        let content = r#"{"recommendations": ["a", "b",], "nested": {"c": 1,},}"#;
+        let json: serde_json::Value = serde_json::from_str(&strip_jsonc(content)).unwrap();
+        assert_eq!(json, serde_json::json!({"recommendations": ["a", "b"], "nested": {"c": 1}}));
+    }
+
+    #[test]
+    fn test_strip_jsonc_keeps_strings() {
+        let content = r#"{"url": "https://example.com/*x*/", "quote": "say \"hi\" // not a comment",}"#;
+        let json: serde_json::Value = serde_json::from_str(&strip_jsonc(content)).unwrap();
+        assert_eq!(json["url"], "https://example.com/*x*/");
+        assert_eq!(json["quote"], "say \"hi\" // not a comment");
+    }
+
+    #[test]
+    fn test_existence_cache() {
+        let dir = std::env::temp_dir().join(format!("vscode-workspaces-editor-exists-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.to_string_lossy().to_string();
+        
+        let cache = ExistenceCache::new();
+        assert!(cache.exists_cached(&path));
+        std::fs::remove_dir(&dir).unwrap();
+        // Still cached until the entry expires or is refreshed
+        assert!(cache.exists_cached(&path));
+        assert!(!cache.refresh(&path));
+        assert!(!cache.exists_cached(&path));
+    }
+}
@@ -1,10 +1,10 @@
 use anyhow::{Context, Result};
 use home::home_dir;
 use log::{debug, info, warn};
-use rusqlite::Connection;
 use std::path::PathBuf;
 
 use crate::workspaces::{
+    database::{backup_database, open_database, with_transaction},
     models::{Workspace, WorkspaceSource},
     parser::WorkspacePathInfo,
 };
@@ -104,11 +104,60 @@ pub fn get_zed_workspaces() -> Result<Vec<Workspace>> {
     Ok(all_workspaces)
 }
 
+/// Delete a workspace from the Zed database of the given channel
+pub fn delete_zed_workspace(channel: &str, workspace_id: &str, dry_run: bool) -> Result<()> {
+    let workspace_id: i64 = workspace_id
+        .parse()
+        .with_context(|| format!("Invalid Zed workspace ID: {}", workspace_id))?;
+
+    let db_file = get_zed_db_path()?.join(channel).join("db.sqlite");
+    if !db_file.exists() {
+        warn!("Zed database not found: {}", db_file.display());
+        return Ok(());
+    }
+
+    if dry_run {
+        info!(
+            "Dry run: would delete workspace {} from Zed database {}",
+            workspace_id,
+            db_file.display()
+        );
+        return Ok(());
+    }
+
+    let conn = open_database(&db_file)
+        .with_context(|| format!("Failed to open Zed database: {}", db_file.display()))?;
+
+    // Back up the database before the first write
+    backup_database(&conn, &db_file.to_string_lossy())?;
+
+    // Let Zed's foreign keys clean up the panes and items of the workspace
+    conn.execute_batch("PRAGMA foreign_keys = ON")?;
+
+    let deleted = with_transaction(&conn, |conn| {
+        conn.execute(
+            "DELETE FROM workspaces WHERE workspace_id = ?",
+            [workspace_id],
+        )
+        .with_context(|| format!("Failed to delete workspace {} from Zed database", workspace_id))
+    })?;
+
+    if deleted == 0 {
+        warn!(
+            "Workspace {} not found in Zed database {}",
+            workspace_id,
+            db_file.display()
+        );
+    }
+
+    Ok(())
+}
+
 /// Get workspaces from a specific Zed database file
 fn get_workspaces_from_db(db_path: &PathBuf, channel: &str) -> Result<Vec<Workspace>> {
     let mut workspaces = Vec::new();
 
-    let conn = Connection::open(db_path)
+    let conn = open_database(db_path)
         .with_context(|| format!("Failed to open Zed database: {}", db_path.display()))?;
 
     // Check if the workspaces table exists
@@ -224,6 +273,9 @@ fn get_workspaces_from_db(db_path: &PathBuf, channel: &str) -> Result<Vec<Worksp
                     remote_host,
                     remote_user,
                     remote_port,
+                    resolved_host: None,
+                    resolved_user: None,
+                    resolved_port: None,
                     path: primary_path.clone(),
                     container_path: None,
                     label: None,
@@ -241,6 +293,9 @@ fn get_workspaces_from_db(db_path: &PathBuf, channel: &str) -> Result<Vec<Worksp
                 remote_host: None,
                 remote_user: None,
                 remote_port: None,
+                resolved_host: None,
+                resolved_user: None,
+                resolved_port: None,
                 path: primary_path.clone(),
                 container_path: None,
                 label: None,
@@ -258,6 +313,14 @@ fn get_workspaces_from_db(db_path: &PathBuf, channel: &str) -> Result<Vec<Worksp
             storage_path: None,
             sources: vec![WorkspaceSource::Zed(channel.to_string())],
             parsed_info,
+            pinned: false,
+            exists: false,
+            exists_checked: false,
+            open_count: None,
+            recent_file_count: 0,
+            git_branch: None,
+            color: None,
+            recommended_extensions: Vec::new(),
         };
 
         workspaces.push(workspace);